@@ -0,0 +1,250 @@
+// Std
+use std::mem;
+
+// Third Party
+use proc_macro2::{Span, TokenStream};
+use syn::{self, Attribute, Ident, Lit, LitStr, Meta, NestedMeta};
+use syn::spanned::Spanned;
+
+/// What a single `#[clap(...)]` key turned into.
+pub enum Method {
+    /// `key = "lit"` or `key(lit, ..)`: call the builder method `key` with those arguments
+    Call(Ident, TokenStream),
+    /// `setting(Name)` or `global_setting(Name)`: call the builder method with `AppSettings::Name`
+    Setting(Ident, Ident),
+}
+
+/// The attributes that can be attached to a container (struct, enum or enum variant), i.e. the
+/// things that end up on an `App`.
+#[derive(Default)]
+pub struct AppAttrs {
+    pub name: Option<LitStr>,
+    pub about: Option<String>,
+    pub long_about: Option<String>,
+    pub methods: Vec<Method>,
+}
+
+/// The attributes that can be attached to a struct field, i.e. the things that end up on an `Arg`
+/// (or a subcommand, when `subcommand` is used).
+#[derive(Default)]
+pub struct FieldAttrs {
+    pub name: Option<LitStr>,
+    pub short: Option<Option<LitStr>>,
+    pub long: Option<Option<LitStr>>,
+    pub help: Option<String>,
+    pub long_help: Option<String>,
+    pub default_value: Option<LitStr>,
+    pub possible_values: Vec<LitStr>,
+    pub subcommand: bool,
+    pub arg_enum: bool,
+    pub from_occurrences: bool,
+    pub methods: Vec<Method>,
+}
+
+impl AppAttrs {
+    pub fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut ret = AppAttrs::default();
+        let (help, long_help) = doc_comments(attrs);
+        ret.about = help;
+        ret.long_about = long_help;
+        for meta in clap_metas(attrs)? {
+            match meta {
+                Meta::NameValue(ref nv) if nv.path.is_ident("name") => {
+                    ret.name = Some(lit_str(&nv.lit)?);
+                }
+                Meta::NameValue(ref nv) if nv.path.is_ident("about") => {
+                    ret.about = Some(lit_str(&nv.lit)?.value());
+                    ret.long_about = None;
+                }
+                Meta::NameValue(ref nv) if nv.path.is_ident("long_about") => {
+                    ret.long_about = Some(lit_str(&nv.lit)?.value());
+                }
+                Meta::Path(ref p) if p.is_ident("version") => {
+                    let ident = Ident::new("version", p.span());
+                    ret.methods.push(Method::Call(
+                        ident,
+                        quote!(env!("CARGO_PKG_VERSION")),
+                    ));
+                }
+                meta => ret.methods.push(method(meta)?),
+            }
+        }
+        Ok(ret)
+    }
+}
+
+impl FieldAttrs {
+    pub fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut ret = FieldAttrs::default();
+        let (help, long_help) = doc_comments(attrs);
+        ret.help = help;
+        ret.long_help = long_help;
+        for meta in clap_metas(attrs)? {
+            match meta {
+                Meta::NameValue(ref nv) if nv.path.is_ident("name") => {
+                    ret.name = Some(lit_str(&nv.lit)?);
+                }
+                Meta::NameValue(ref nv) if nv.path.is_ident("short") => {
+                    ret.short = Some(Some(lit_str(&nv.lit)?));
+                }
+                Meta::Path(ref p) if p.is_ident("short") => ret.short = Some(None),
+                Meta::NameValue(ref nv) if nv.path.is_ident("long") => {
+                    ret.long = Some(Some(lit_str(&nv.lit)?));
+                }
+                Meta::Path(ref p) if p.is_ident("long") => ret.long = Some(None),
+                Meta::NameValue(ref nv) if nv.path.is_ident("help") => {
+                    ret.help = Some(lit_str(&nv.lit)?.value());
+                    ret.long_help = None;
+                }
+                Meta::NameValue(ref nv) if nv.path.is_ident("long_help") => {
+                    ret.long_help = Some(lit_str(&nv.lit)?.value());
+                }
+                Meta::NameValue(ref nv) if nv.path.is_ident("default_value") => {
+                    ret.default_value = Some(lit_str(&nv.lit)?);
+                }
+                Meta::List(ref l) if l.path.is_ident("possible_values") => {
+                    for n in &l.nested {
+                        match *n {
+                            NestedMeta::Lit(ref lit) => ret.possible_values.push(lit_str(lit)?),
+                            ref n => {
+                                return Err(syn::Error::new(n.span(), "expected a string literal"))
+                            }
+                        }
+                    }
+                }
+                Meta::Path(ref p) if p.is_ident("subcommand") => ret.subcommand = true,
+                Meta::Path(ref p) if p.is_ident("arg_enum") => ret.arg_enum = true,
+                Meta::Path(ref p) if p.is_ident("from_occurrences") => {
+                    ret.from_occurrences = true
+                }
+                meta => ret.methods.push(method(meta)?),
+            }
+        }
+        Ok(ret)
+    }
+}
+
+// Turns any key we don't handle ourselves into a plain builder method call, so that every
+// `App`/`Arg` method taking literals (or a bool) can be used without the derive knowing about it.
+fn method(meta: Meta) -> syn::Result<Method> {
+    match meta {
+        Meta::Path(p) => {
+            let ident = path_ident(&p)?;
+            Ok(Method::Call(ident, quote!(true)))
+        }
+        Meta::NameValue(nv) => {
+            let ident = path_ident(&nv.path)?;
+            let lit = nv.lit;
+            Ok(Method::Call(ident, quote!(#lit)))
+        }
+        Meta::List(l) => {
+            let ident = path_ident(&l.path)?;
+            if ident == "setting" || ident == "global_setting" || ident == "unset_setting" {
+                let mut nested = l.nested.iter();
+                match (nested.next(), nested.next()) {
+                    (Some(&NestedMeta::Meta(Meta::Path(ref p))), None) => {
+                        Ok(Method::Setting(ident, path_ident(p)?))
+                    }
+                    _ => Err(syn::Error::new(
+                        l.span(),
+                        "expected a single AppSettings variant, i.e. `setting(ColoredHelp)`",
+                    )),
+                }
+            } else {
+                let args = l.nested.iter().map(|n| match *n {
+                    NestedMeta::Lit(ref lit) => Ok(quote!(#lit)),
+                    NestedMeta::Meta(ref m) => {
+                        Err(syn::Error::new(m.span(), "expected a literal"))
+                    }
+                });
+                let args = args.collect::<syn::Result<Vec<_>>>()?;
+                Ok(Method::Call(ident, quote!(#(#args),*)))
+            }
+        }
+    }
+}
+
+fn path_ident(p: &syn::Path) -> syn::Result<Ident> {
+    p.get_ident()
+        .cloned()
+        .ok_or_else(|| syn::Error::new(p.span(), "expected an identifier"))
+}
+
+fn lit_str(lit: &Lit) -> syn::Result<LitStr> {
+    match *lit {
+        Lit::Str(ref s) => Ok(s.clone()),
+        ref l => Err(syn::Error::new(l.span(), "expected a string literal")),
+    }
+}
+
+fn clap_metas(attrs: &[Attribute]) -> syn::Result<Vec<Meta>> {
+    let mut metas = vec![];
+    for attr in attrs.iter().filter(|a| a.path.is_ident("clap")) {
+        match attr.parse_meta()? {
+            Meta::List(l) => for n in l.nested {
+                match n {
+                    NestedMeta::Meta(m) => metas.push(m),
+                    NestedMeta::Lit(lit) => {
+                        return Err(syn::Error::new(lit.span(), "expected `key` or `key = value`"))
+                    }
+                }
+            },
+            m => {
+                return Err(syn::Error::new(
+                    m.span(),
+                    "expected `#[clap(...)]` with a list of keys",
+                ))
+            }
+        }
+    }
+    Ok(metas)
+}
+
+// Splits doc comments into the short help (the first paragraph) and, when there is more than one
+// paragraph, the long help (everything).
+fn doc_comments(attrs: &[Attribute]) -> (Option<String>, Option<String>) {
+    let mut paragraphs = vec![];
+    let mut cur = vec![];
+    for attr in attrs.iter().filter(|a| a.path.is_ident("doc")) {
+        if let Ok(Meta::NameValue(nv)) = attr.parse_meta() {
+            if let Lit::Str(s) = nv.lit {
+                let line = s.value().trim().to_owned();
+                if line.is_empty() {
+                    if !cur.is_empty() {
+                        paragraphs.push(mem::take(&mut cur).join(" "));
+                    }
+                } else {
+                    cur.push(line);
+                }
+            }
+        }
+    }
+    if !cur.is_empty() {
+        paragraphs.push(cur.join(" "));
+    }
+    match paragraphs.len() {
+        0 => (None, None),
+        1 => (paragraphs.pop(), None),
+        _ => (Some(paragraphs[0].clone()), Some(paragraphs.join("\n\n"))),
+    }
+}
+
+/// Converts a Rust identifier (`CamelCase` or `snake_case`) into `kebab-case`.
+pub fn kebab_case(s: &str) -> String {
+    let mut ret = String::with_capacity(s.len() + 4);
+    for (i, c) in s.trim_start_matches("r#").chars().enumerate() {
+        if c == '_' {
+            ret.push('-');
+        } else if c.is_uppercase() {
+            if i != 0 && !ret.ends_with('-') {
+                ret.push('-');
+            }
+            ret.extend(c.to_lowercase());
+        } else {
+            ret.push(c);
+        }
+    }
+    ret
+}
+
+pub fn lit(s: &str) -> LitStr { LitStr::new(s, Span::call_site()) }
@@ -0,0 +1,478 @@
+//! Custom derive for [`clap`](https://docs.rs/clap), building an `App` from a struct or enum
+//! definition and converting the resulting `ArgMatches` back into that type.
+//!
+//! This crate is not meant to be used directly, enable `clap`'s `derive` feature instead and see
+//! the documentation of `clap::Clap` there.
+
+#![recursion_limit = "256"]
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+mod attrs;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{Data, DataEnum, DeriveInput, Field, Fields, GenericArgument, Ident, PathArguments,
+          Type};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::token::Comma;
+
+use attrs::{kebab_case, lit, AppAttrs, FieldAttrs, Method};
+
+/// Generates the `IntoApp` and `FromArgMatches` implementations (and `Subcommand` for enums).
+#[proc_macro_derive(Clap, attributes(clap))]
+pub fn derive_clap(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    let ret = match input.data {
+        Data::Struct(ref s) => match s.fields {
+            Fields::Named(ref fields) => derive_struct(&input, &fields.named),
+            Fields::Unit => derive_struct(&input, &Punctuated::new()),
+            Fields::Unnamed(_) => Err(syn::Error::new(
+                input.ident.span(),
+                "#[derive(Clap)] is only supported on structs with named fields",
+            )),
+        },
+        Data::Enum(ref e) => derive_enum(&input, e),
+        Data::Union(_) => Err(syn::Error::new(
+            input.ident.span(),
+            "#[derive(Clap)] is not supported on unions",
+        )),
+    };
+    ret.unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+fn derive_struct(input: &DeriveInput, fields: &Punctuated<Field, Comma>) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let attrs = AppAttrs::from_attrs(&input.attrs)?;
+    let app = gen_app(&attrs, &kebab_case(&ident.to_string()));
+    let augment = gen_augment(fields)?;
+    let ctor = gen_constructor(&quote!(#ident), fields, &quote!(Err(e)))?;
+
+    Ok(quote! {
+        impl #impl_generics ::clap::IntoApp for #ident #ty_generics #where_clause {
+            fn into_app<'a, 'b>() -> ::clap::App<'a, 'b> {
+                let app = #app;
+                #augment
+                app
+            }
+        }
+
+        impl #impl_generics ::clap::FromArgMatches for #ident #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn try_from_argmatches<'a>(
+                matches: &::clap::ArgMatches<'a>,
+            ) -> ::std::result::Result<Self, ::clap::Error> {
+                Ok(#ctor)
+            }
+        }
+    })
+}
+
+fn derive_enum(input: &DeriveInput, e: &DataEnum) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let attrs = AppAttrs::from_attrs(&input.attrs)?;
+    let app = gen_app(&attrs, &kebab_case(&ident.to_string()));
+
+    let mut subcmds = vec![];
+    let mut arms = vec![];
+    for v in &e.variants {
+        let v_ident = &v.ident;
+        let v_attrs = AppAttrs::from_attrs(&v.attrs)?;
+        let name = v_attrs
+            .name
+            .clone()
+            .unwrap_or_else(|| lit(&kebab_case(&v_ident.to_string())));
+        match v.fields {
+            Fields::Unit => {
+                let sc = gen_app(&v_attrs, &name.value());
+                subcmds.push(quote!(let app = app.subcommand(#sc);));
+                arms.push(quote!((#name, Some(_)) => Some(Ok(#ident::#v_ident)),));
+            }
+            Fields::Named(ref fields) => {
+                let sc = gen_app(&v_attrs, &name.value());
+                let augment = gen_augment(&fields.named)?;
+                let ctor = gen_constructor(
+                    &quote!(#ident::#v_ident),
+                    &fields.named,
+                    &quote!(Some(Err(e))),
+                )?;
+                subcmds.push(quote! {
+                    let app = app.subcommand({
+                        let app = #sc;
+                        #augment
+                        app
+                    });
+                });
+                arms.push(quote!((#name, Some(matches)) => Some(Ok(#ctor)),));
+            }
+            Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed[0].ty;
+                let methods = gen_app_methods(&v_attrs);
+                subcmds.push(quote! {
+                    let app = app.subcommand(
+                        <#ty as ::clap::IntoApp>::into_app().name(#name)#methods
+                    );
+                });
+                arms.push(quote! {
+                    (#name, Some(matches)) => Some(
+                        <#ty as ::clap::FromArgMatches>::try_from_argmatches(matches)
+                            .map(#ident::#v_ident)
+                    ),
+                });
+            }
+            Fields::Unnamed(ref fields) => {
+                return Err(syn::Error::new(
+                    fields.span(),
+                    "#[derive(Clap)] only supports tuple variants with exactly one field",
+                ))
+            }
+        }
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::clap::Subcommand for #ident #ty_generics #where_clause {
+            fn augment_subcommands<'a, 'b>(app: ::clap::App<'a, 'b>) -> ::clap::App<'a, 'b> {
+                #(#subcmds)*
+                app
+            }
+
+            #[allow(unused_variables)]
+            fn from_subcommand<'a>(
+                name: &str,
+                sub: ::std::option::Option<&::clap::ArgMatches<'a>>,
+            ) -> ::std::option::Option<::std::result::Result<Self, ::clap::Error>> {
+                match (name, sub) {
+                    #(#arms)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl #impl_generics ::clap::IntoApp for #ident #ty_generics #where_clause {
+            fn into_app<'a, 'b>() -> ::clap::App<'a, 'b> {
+                <Self as ::clap::Subcommand>::augment_subcommands(#app)
+                    .setting(::clap::AppSettings::SubcommandRequiredElseHelp)
+            }
+        }
+
+        impl #impl_generics ::clap::FromArgMatches for #ident #ty_generics #where_clause {
+            fn try_from_argmatches<'a>(
+                matches: &::clap::ArgMatches<'a>,
+            ) -> ::std::result::Result<Self, ::clap::Error> {
+                let (name, sub) = matches.subcommand();
+                <Self as ::clap::Subcommand>::from_subcommand(name, sub)
+                    .unwrap_or_else(|| Err(::clap::Error::with_description(
+                        "A subcommand is required but one was not provided",
+                        ::clap::ErrorKind::MissingSubcommand,
+                    )))
+            }
+        }
+    })
+}
+
+// `App::new(..)` plus everything set through container attributes
+fn gen_app(attrs: &AppAttrs, default_name: &str) -> TokenStream2 {
+    let name = attrs.name.clone().unwrap_or_else(|| lit(default_name));
+    let methods = gen_app_methods(attrs);
+    quote!(::clap::App::new(#name)#methods)
+}
+
+fn gen_app_methods(attrs: &AppAttrs) -> TokenStream2 {
+    let about = attrs.about.as_ref().map(|a| quote!(.about(#a)));
+    let long_about = attrs.long_about.as_ref().map(|a| quote!(.long_about(#a)));
+    let methods = gen_methods(&attrs.methods);
+    quote!(#about #long_about #methods)
+}
+
+fn gen_methods(methods: &[Method]) -> TokenStream2 {
+    let calls = methods.iter().map(|m| match *m {
+        Method::Call(ref ident, ref args) => quote!(.#ident(#args)),
+        Method::Setting(ref ident, ref s) => quote!(.#ident(::clap::AppSettings::#s)),
+    });
+    quote!(#(#calls)*)
+}
+
+/// How a field's type maps onto clap's notion of an argument.
+enum Kind<'a> {
+    /// `bool`: a flag, `true` when present
+    Flag,
+    /// `Option<T>`: an optional argument taking a single value
+    Optional(&'a Type),
+    /// `Vec<T>`: an argument taking any number of values
+    Multiple(&'a Type),
+    /// Anything else: an argument taking a single value, required unless it has a default
+    Required(&'a Type),
+}
+
+impl<'a> Kind<'a> {
+    fn of(ty: &'a Type) -> Self {
+        if is_ident(ty, "bool") {
+            Kind::Flag
+        } else if let Some(inner) = generic_inner(ty, "Option") {
+            Kind::Optional(inner)
+        } else if let Some(inner) = generic_inner(ty, "Vec") {
+            Kind::Multiple(inner)
+        } else {
+            Kind::Required(ty)
+        }
+    }
+}
+
+fn is_ident(ty: &Type, name: &str) -> bool {
+    match *ty {
+        Type::Path(ref tp) if tp.qself.is_none() => tp.path.is_ident(name),
+        _ => false,
+    }
+}
+
+// If `ty` is `name<T>` returns `T`
+fn generic_inner<'a>(ty: &'a Type, name: &str) -> Option<&'a Type> {
+    let seg = match *ty {
+        Type::Path(ref tp) if tp.qself.is_none() => tp.path.segments.last()?,
+        _ => return None,
+    };
+    if seg.ident != name {
+        return None;
+    }
+    match seg.arguments {
+        PathArguments::AngleBracketed(ref args) if args.args.len() == 1 => match args.args[0] {
+            GenericArgument::Type(ref t) => Some(t),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn field_ident(field: &Field) -> &Ident {
+    field
+        .ident
+        .as_ref()
+        .expect("#[derive(Clap)] only handles named fields")
+}
+
+fn arg_name(field: &Field, attrs: &FieldAttrs) -> syn::LitStr {
+    attrs
+        .name
+        .clone()
+        .unwrap_or_else(|| lit(field_ident(field).to_string().trim_start_matches("r#")))
+}
+
+// The statements adding every field (as an `Arg` or as subcommands) to a local `app`
+fn gen_augment(fields: &Punctuated<Field, Comma>) -> syn::Result<TokenStream2> {
+    let mut stmts = vec![];
+    for field in fields {
+        let attrs = FieldAttrs::from_attrs(&field.attrs)?;
+        if attrs.subcommand {
+            let (ty, required) = match generic_inner(&field.ty, "Option") {
+                Some(inner) => (inner, false),
+                None => (&field.ty, true),
+            };
+            let required = if required {
+                Some(quote!(.setting(::clap::AppSettings::SubcommandRequiredElseHelp)))
+            } else {
+                None
+            };
+            stmts.push(quote! {
+                let app = <#ty as ::clap::Subcommand>::augment_subcommands(app)#required;
+            });
+            continue;
+        }
+
+        let name = arg_name(field, &attrs);
+        let fname = field_ident(field).to_string();
+        let short = attrs.short.as_ref().map(|s| {
+            // The first character, not byte, so that non-ASCII field names don't panic
+            let s = s.clone().unwrap_or_else(|| {
+                let first = fname.trim_start_matches("r#").chars().next().unwrap();
+                lit(&first.to_string())
+            });
+            quote!(.short(#s))
+        });
+        let long = attrs.long.as_ref().map(|l| {
+            let l = l.clone().unwrap_or_else(|| lit(&kebab_case(&fname)));
+            quote!(.long(#l))
+        });
+        // A flag has to be reachable somehow, so default to a long when nothing was asked for
+        let kind = Kind::of(&field.ty);
+        let long = match (&kind, &short, long) {
+            (&Kind::Flag, &None, None) => {
+                let l = lit(&kebab_case(&fname));
+                Some(quote!(.long(#l)))
+            }
+            (_, _, long) => long,
+        };
+        let help = attrs.help.as_ref().map(|h| quote!(.help(#h)));
+        let long_help = attrs.long_help.as_ref().map(|h| quote!(.long_help(#h)));
+        let default_value = attrs
+            .default_value
+            .as_ref()
+            .map(|d| quote!(.default_value(#d)));
+        let possible_values = if attrs.possible_values.is_empty() {
+            None
+        } else {
+            let pvs = &attrs.possible_values;
+            Some(quote!(.possible_values(&[#(#pvs),*])))
+        };
+
+        let modifiers = match kind {
+            _ if attrs.from_occurrences => quote!(.multiple(true)),
+            Kind::Flag => quote!(),
+            Kind::Optional(ty) => {
+                let v = gen_value_modifiers(ty, &attrs);
+                quote!(.takes_value(true)#v)
+            }
+            Kind::Multiple(ty) => {
+                let v = gen_value_modifiers(ty, &attrs);
+                quote!(.takes_value(true).multiple(true)#v)
+            }
+            Kind::Required(ty) => {
+                let v = gen_value_modifiers(ty, &attrs);
+                let required = attrs.default_value.is_none();
+                quote!(.takes_value(true).required(#required)#v)
+            }
+        };
+        let methods = gen_methods(&attrs.methods);
+
+        stmts.push(quote! {
+            let app = app.arg(
+                ::clap::Arg::with_name(#name)
+                    #short
+                    #long
+                    #help
+                    #long_help
+                    #modifiers
+                    #default_value
+                    #possible_values
+                    #methods
+            );
+        });
+    }
+    Ok(quote!(#(#stmts)*))
+}
+
+// Possible values and a validator so that bad values are reported while parsing, along with the
+// usage string, instead of when converting the `ArgMatches`
+fn gen_value_modifiers(ty: &Type, attrs: &FieldAttrs) -> TokenStream2 {
+    let arg_enum = if attrs.arg_enum {
+        Some(quote!(.possible_values(&<#ty>::variants()).case_insensitive(true)))
+    } else {
+        None
+    };
+    let validator = if is_ident(ty, "String") {
+        None
+    } else {
+        Some(quote! {
+            .validator(|s| {
+                <#ty as ::std::str::FromStr>::from_str(&s)
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            })
+        })
+    };
+    quote!(#arg_enum #validator)
+}
+
+// An expression building `path { field: value, .. }` out of `matches`, where any error is
+// propagated with `return #ret_err` (`e` being the error)
+fn gen_constructor(
+    path: &TokenStream2,
+    fields: &Punctuated<Field, Comma>,
+    ret_err: &TokenStream2,
+) -> syn::Result<TokenStream2> {
+    let mut inits = vec![];
+    for field in fields {
+        let ident = field_ident(field);
+        let attrs = FieldAttrs::from_attrs(&field.attrs)?;
+        let try_ = |expr: TokenStream2| {
+            quote! {
+                match #expr {
+                    Ok(v) => v,
+                    Err(e) => return #ret_err,
+                }
+            }
+        };
+        let parse = |ty: &Type| {
+            quote! {
+                |v: &str| <#ty as ::std::str::FromStr>::from_str(v).map_err(|_| {
+                    ::clap::Error::value_validation_auto(
+                        format!("The argument '{}' isn't a valid value", v))
+                })
+            }
+        };
+
+        let value = if attrs.subcommand {
+            match generic_inner(&field.ty, "Option") {
+                Some(ty) => {
+                    let sub = try_(quote!(sub));
+                    quote! {{
+                        let (name, sub) = matches.subcommand();
+                        match <#ty as ::clap::Subcommand>::from_subcommand(name, sub) {
+                            Some(sub) => Some(#sub),
+                            None => None,
+                        }
+                    }}
+                }
+                None => {
+                    let ty = &field.ty;
+                    let sub = try_(quote! {
+                        <#ty as ::clap::Subcommand>::from_subcommand(name, sub)
+                            .unwrap_or_else(|| Err(::clap::Error::with_description(
+                                "A subcommand is required but one was not provided",
+                                ::clap::ErrorKind::MissingSubcommand,
+                            )))
+                    });
+                    quote! {{
+                        let (name, sub) = matches.subcommand();
+                        #sub
+                    }}
+                }
+            }
+        } else {
+            let name = arg_name(field, &attrs);
+            match Kind::of(&field.ty) {
+                _ if attrs.from_occurrences => {
+                    let ty = &field.ty;
+                    quote!(matches.occurrences_of(#name) as #ty)
+                }
+                Kind::Flag => quote!(matches.is_present(#name)),
+                Kind::Optional(ty) => {
+                    let parse = parse(ty);
+                    let v = try_(quote!((#parse)(v)));
+                    quote! {
+                        match matches.value_of(#name) {
+                            Some(v) => Some(#v),
+                            None => None,
+                        }
+                    }
+                }
+                Kind::Multiple(ty) => {
+                    let parse = parse(ty);
+                    try_(quote! {
+                        matches
+                            .values_of(#name)
+                            .map(|vs| vs.map(#parse).collect())
+                            .unwrap_or_else(|| Ok(vec![]))
+                    })
+                }
+                Kind::Required(ty) => {
+                    let parse = parse(ty);
+                    try_(quote! {
+                        matches
+                            .value_of(#name)
+                            .ok_or_else(|| ::clap::Error::argument_not_found_auto(#name))
+                            .and_then(#parse)
+                    })
+                }
+            }
+        };
+        inits.push(quote!(#ident: #value));
+    }
+    Ok(quote!(#path { #(#inits),* }))
+}
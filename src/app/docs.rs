@@ -0,0 +1,388 @@
+// Std
+#[allow(unused_imports)]
+use std::ascii::AsciiExt;
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+// Internal
+use app::AppSettings as AS;
+use app::help::{as_arg_trait, documented_subcommands, sort_args, sort_subcommands, val_str,
+                ArgWithDisplay, ArgWithOrder};
+use app::parser::Parser;
+use app::usage;
+use args::{AnyArg, ArgSettings};
+
+/// Describes which format to produce reference documentation in
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DocFormat {
+    /// Generates a Markdown (CommonMark) document
+    Markdown,
+    /// Generates an HTML fragment, suitable for embedding in a page
+    Html,
+}
+
+impl DocFormat {
+    /// A list of possible variants in `&'static str` form
+    pub fn variants() -> [&'static str; 2] { ["markdown", "html"] }
+
+    /// The file extension of documents in this format
+    pub fn extension(&self) -> &'static str {
+        match *self {
+            DocFormat::Markdown => "md",
+            DocFormat::Html => "html",
+        }
+    }
+}
+
+impl FromStr for DocFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "MARKDOWN" | _ if s.eq_ignore_ascii_case("markdown") => Ok(DocFormat::Markdown),
+            "HTML" | _ if s.eq_ignore_ascii_case("html") => Ok(DocFormat::Html),
+            _ => Err(String::from("[valid values: markdown, html]")),
+        }
+    }
+}
+
+impl fmt::Display for DocFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DocFormat::Markdown => write!(f, "MARKDOWN"),
+            DocFormat::Html => write!(f, "HTML"),
+        }
+    }
+}
+
+// Writes the reference documentation of a `Parser`, its subcommands each get a section of their
+// own which the list of subcommands links to
+pub struct DocGen<'a, 'b>
+where
+    'a: 'b,
+{
+    p: &'b Parser<'a, 'b>,
+    fmt: DocFormat,
+}
+
+impl<'a, 'b> DocGen<'a, 'b> {
+    pub fn new(p: &'b Parser<'a, 'b>, fmt: DocFormat) -> Self { DocGen { p: p, fmt: fmt } }
+
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        debugln!("DocGen::write_to;");
+        let p = self.p;
+        let name = p.meta.bin_name.as_ref().unwrap_or(&p.meta.name);
+        let title = match p.meta.version {
+            Some(v) => format!("{} {}", name, v),
+            None => name.clone(),
+        };
+        self.heading(w, 1, &title, &anchor(name))?;
+        self.write_parser(w, p, 2)?;
+        for sc in documented_subcommands(p) {
+            self.write_subcommand(w, &sc.p)?;
+        }
+        Ok(())
+    }
+
+    fn write_subcommand<W: Write>(&self, w: &mut W, p: &Parser) -> io::Result<()> {
+        debugln!("DocGen::write_subcommand: {}", p.meta.name);
+        let name = p.meta.bin_name.as_ref().unwrap_or(&p.meta.name);
+        self.heading(w, 2, name, &anchor(name))?;
+        self.write_parser(w, p, 3)?;
+        for sc in documented_subcommands(p) {
+            self.write_subcommand(w, &sc.p)?;
+        }
+        Ok(())
+    }
+
+    // Writes the description, usage, arguments, groups and subcommands of a single parser, using
+    // `level` for the headings
+    fn write_parser<W: Write>(&self, w: &mut W, p: &Parser, level: usize) -> io::Result<()> {
+        if let Some(about) = p.meta.long_about.or(p.meta.about) {
+            self.text(w, about)?;
+        }
+        self.usage(w, &usage::create_usage_no_title(p, &[]))?;
+
+        let visible = |a: &&ArgWithOrder| !a.is_set(ArgSettings::Hidden);
+        if p.is_set(AS::UnifiedHelpMessage) {
+            let args = sort_args(
+                p.flags()
+                    .map(as_arg_trait)
+                    .chain(p.opts().map(as_arg_trait))
+                    .filter(&visible),
+            );
+            self.write_args(w, "Options", level, &args)?;
+        } else {
+            let flags = sort_args(p.flags().map(as_arg_trait).filter(&visible));
+            self.write_args(w, "Flags", level, &flags)?;
+            let opts = sort_args(p.opts().map(as_arg_trait).filter(&visible));
+            self.write_args(w, "Options", level, &opts)?;
+        }
+        // Like the help message, positionals are listed by index
+        let pos: Vec<_> = p.positionals().map(as_arg_trait).filter(&visible).collect();
+        self.write_args(w, "Arguments", level, &pos)?;
+
+        self.write_groups(w, p, level)?;
+
+        if p.has_visible_subcommands() {
+            self.heading(w, level, "Subcommands", "")?;
+            self.list_start(w)?;
+            let documented = documented_subcommands(p);
+            for sc in sort_subcommands(p) {
+                let link = if documented.iter().any(|d| d.p.meta.name == sc.p.meta.name) {
+                    sc.p.meta.bin_name.as_ref().map(|bn| anchor(bn))
+                } else {
+                    None
+                };
+                let mut extras = vec![];
+                if let Some(ref aliases) = sc.p.meta.aliases {
+                    let visible: Vec<_> = aliases.iter().filter(|a| a.1).map(|a| a.0).collect();
+                    if !visible.is_empty() {
+                        extras.push(("Aliases", visible.iter().map(|a| a.to_string()).collect()));
+                    }
+                }
+                self.item(w, &sc.p.meta.name, link, sc.p.meta.about, &extras)?;
+            }
+            self.list_end(w)?;
+        }
+        Ok(())
+    }
+
+    fn write_args<W: Write>(
+        &self,
+        w: &mut W,
+        title: &str,
+        level: usize,
+        args: &[&ArgWithOrder],
+    ) -> io::Result<()> {
+        if args.is_empty() {
+            return Ok(());
+        }
+        self.heading(w, level, title, "")?;
+        self.list_start(w)?;
+        for a in args {
+            let a = a.as_base();
+            let mut extras = vec![];
+            if !a.is_set(ArgSettings::HideDefaultValue) {
+                if let Some(dv) = a.default_val() {
+                    extras.push(("Default value", vec![dv.to_string_lossy().into_owned()]));
+                }
+            }
+            if !a.is_set(ArgSettings::HidePossibleValues) {
+                if let Some(pv) = a.possible_vals() {
+                    extras.push(("Possible values", pv.iter().map(|v| v.to_string()).collect()));
+                }
+            }
+            if let Some(aliases) = a.aliases() {
+                extras.push(("Aliases", aliases.iter().map(|a| a.to_string()).collect()));
+            }
+            if let Some((name, _)) = a.env() {
+                extras.push(("Environment variable", vec![name.to_string_lossy().into_owned()]));
+            }
+            let help = a.long_help().or_else(|| a.help());
+            self.item(w, &arg_tag(a), None, help, &extras)?;
+        }
+        self.list_end(w)
+    }
+
+    fn write_groups<W: Write>(&self, w: &mut W, p: &Parser, level: usize) -> io::Result<()> {
+        let mut groups = vec![];
+        for g in &p.groups {
+            // Like in the usage string, members are shown as they are written on the command line
+            let members: Vec<String> = g.args
+                .iter()
+                .filter_map(|n| match p.find_any_arg(n) {
+                    Some(a) if a.is_set(ArgSettings::Hidden) => None,
+                    Some(a) => Some(a.to_string()),
+                    None => Some(n.to_string()),
+                })
+                .collect();
+            if !members.is_empty() {
+                groups.push((g, members));
+            }
+        }
+        if groups.is_empty() {
+            return Ok(());
+        }
+        self.heading(w, level, "Groups", "")?;
+        self.list_start(w)?;
+        for (g, members) in groups {
+            let help = match (g.required, g.multiple) {
+                (true, false) => Some("Exactly one of these arguments must be used"),
+                (true, true) => Some("At least one of these arguments must be used"),
+                (false, false) => Some("At most one of these arguments may be used"),
+                (false, true) => None,
+            };
+            self.item(w, g.name, None, help, &[("Arguments", members)])?;
+        }
+        self.list_end(w)
+    }
+
+    fn heading<W: Write>(&self, w: &mut W, level: usize, text: &str, id: &str) -> io::Result<()> {
+        match self.fmt {
+            DocFormat::Markdown => writeln!(w, "{} {}\n", "#".repeat(level), md(text)),
+            DocFormat::Html if id.is_empty() => {
+                writeln!(w, "<h{l}>{}</h{l}>", html(text), l = level)
+            }
+            DocFormat::Html => writeln!(w, "<h{l} id=\"{}\">{}</h{l}>", id, html(text), l = level),
+        }
+    }
+
+    // Free form text, such as descriptions, where empty lines separate paragraphs
+    fn text<W: Write>(&self, w: &mut W, text: &str) -> io::Result<()> {
+        for para in paragraphs(text) {
+            match self.fmt {
+                DocFormat::Markdown => writeln!(w, "{}\n", md(&para))?,
+                DocFormat::Html => writeln!(w, "<p>{}</p>", html(&para))?,
+            }
+        }
+        Ok(())
+    }
+
+    fn usage<W: Write>(&self, w: &mut W, usage: &str) -> io::Result<()> {
+        let lines: Vec<_> = usage.lines().map(|l| l.trim()).collect();
+        match self.fmt {
+            DocFormat::Markdown => writeln!(w, "```text\n{}\n```\n", lines.join("\n")),
+            DocFormat::Html => writeln!(w, "<pre><code>{}</code></pre>", html(&lines.join("\n"))),
+        }
+    }
+
+    fn list_start<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        match self.fmt {
+            DocFormat::Markdown => Ok(()),
+            DocFormat::Html => writeln!(w, "<dl>"),
+        }
+    }
+
+    fn list_end<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        match self.fmt {
+            DocFormat::Markdown => writeln!(w),
+            DocFormat::Html => writeln!(w, "</dl>"),
+        }
+    }
+
+    // A list item for `term`, which is written as code, followed by its help message and the
+    // extra values such as defaults
+    fn item<W: Write>(
+        &self,
+        w: &mut W,
+        term: &str,
+        link: Option<String>,
+        help: Option<&str>,
+        extras: &[(&str, Vec<String>)],
+    ) -> io::Result<()> {
+        let paras = help.map_or(vec![], paragraphs);
+        match self.fmt {
+            DocFormat::Markdown => {
+                match link {
+                    Some(l) => write!(w, "- [`{}`](#{})", term, l)?,
+                    None => write!(w, "- `{}`", term)?,
+                }
+                let mut it = paras.iter();
+                match it.next() {
+                    Some(first) => writeln!(w, ": {}", md(first).replace("\n", "\n  "))?,
+                    None => writeln!(w)?,
+                }
+                for para in it {
+                    writeln!(w, "\n  {}", md(para).replace("\n", "\n  "))?;
+                }
+                for &(label, ref vals) in extras {
+                    let vals: Vec<_> = vals.iter().map(|v| format!("`{}`", v)).collect();
+                    writeln!(w, "  - {}: {}", label, vals.join(", "))?;
+                }
+            }
+            DocFormat::Html => {
+                match link {
+                    Some(l) => writeln!(w, "<dt><a href=\"#{}\"><code>{}</code></a></dt>", l, html(term))?,
+                    None => writeln!(w, "<dt><code>{}</code></dt>", html(term))?,
+                }
+                write!(w, "<dd>")?;
+                for para in &paras {
+                    write!(w, "<p>{}</p>", html(para))?;
+                }
+                if !extras.is_empty() {
+                    write!(w, "<ul>")?;
+                    for &(label, ref vals) in extras {
+                        let vals: Vec<_> = vals.iter()
+                            .map(|v| format!("<code>{}</code>", html(v)))
+                            .collect();
+                        write!(w, "<li>{}: {}</li>", label, vals.join(", "))?;
+                    }
+                    write!(w, "</ul>")?;
+                }
+                writeln!(w, "</dd>")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// How an argument is written on the command line, e.g. `-c, --config <FILE>` or `<input>...`
+fn arg_tag<'a, 'b>(a: &ArgWithDisplay<'a, 'b>) -> String {
+    if !a.has_switch() {
+        return val_str(a);
+    }
+    let mut tag = vec![];
+    if let Some(s) = a.short() {
+        tag.push(format!("-{}", s));
+    }
+    if let Some(l) = a.long() {
+        if a.is_set(ArgSettings::Negatable) && !a.takes_value() {
+            tag.push(format!("--[no-]{}", l));
+        } else {
+            tag.push(format!("--{}", l));
+        }
+    }
+    let mut tag = tag.join(", ");
+    if a.takes_value() {
+        tag.push(if a.is_set(ArgSettings::RequireEquals) {
+            '='
+        } else {
+            ' '
+        });
+        tag.push_str(&val_str(a));
+    }
+    tag
+}
+
+// The same anchors as the ones generated for headings by most Markdown renderers
+fn anchor(name: &str) -> String { name.to_lowercase().replace(" ", "-") }
+
+fn paragraphs(text: &str) -> Vec<String> {
+    let mut paras = vec![];
+    let mut cur: Vec<&str> = vec![];
+    let text = text.replace("{n}", "\n");
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            if !cur.is_empty() {
+                paras.push(cur.join("\n"));
+                cur.clear();
+            }
+        } else {
+            cur.push(line);
+        }
+    }
+    if !cur.is_empty() {
+        paras.push(cur.join("\n"));
+    }
+    paras
+}
+
+fn md(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if "\\`*_[]<>|".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -31,15 +31,30 @@ fn str_width(s: &str) -> usize { UnicodeWidthStr::width(s) }
 
 const TAB: &'static str = "    ";
 
+// Whether a flag is listed as `--[no-]<long>`
+fn is_negatable<'b, 'c>(arg: &ArgWithDisplay<'b, 'c>) -> bool {
+    arg.is_set(ArgSettings::Negatable) && !arg.takes_value() && arg.long().is_some()
+}
+
+// The width of an argument in the help message, not counting the short of one with a long
+fn arg_width<'b, 'c>(arg: &ArgWithDisplay<'b, 'c>) -> usize {
+    let w = str_width(arg.to_string().as_str());
+    if is_negatable(arg) {
+        w + "[no-]".len()
+    } else {
+        w
+    }
+}
+
 // These are just convenient traits to make the code easier to read.
-trait ArgWithDisplay<'b, 'c>: AnyArg<'b, 'c> + Display {}
+pub trait ArgWithDisplay<'b, 'c>: AnyArg<'b, 'c> + Display {}
 impl<'b, 'c, T> ArgWithDisplay<'b, 'c> for T
 where
     T: AnyArg<'b, 'c> + Display,
 {
 }
 
-trait ArgWithOrder<'b, 'c>: ArgWithDisplay<'b, 'c> + DispOrder {
+pub trait ArgWithOrder<'b, 'c>: ArgWithDisplay<'b, 'c> + DispOrder {
     fn as_base(&self) -> &ArgWithDisplay<'b, 'c>;
 }
 impl<'b, 'c, T> ArgWithOrder<'b, 'c> for T
@@ -49,7 +64,84 @@ where
     fn as_base(&self) -> &ArgWithDisplay<'b, 'c> { self }
 }
 
-fn as_arg_trait<'a, 'b, T: ArgWithOrder<'a, 'b>>(x: &T) -> &ArgWithOrder<'a, 'b> { x }
+pub fn as_arg_trait<'a, 'b, T: ArgWithOrder<'a, 'b>>(x: &T) -> &ArgWithOrder<'a, 'b> { x }
+
+/// The value names of an argument joined together, i.e. `<FILE>`, `<a> <b>` or `<name>...`.
+pub fn val_str<'b, 'c>(arg: &ArgWithDisplay<'b, 'c>) -> String {
+    let (names, delim, ellipsis) = val_names(arg);
+    let mut s = names.join(&*delim.to_string());
+    if ellipsis {
+        s.push_str("...");
+    }
+    s
+}
+
+/// Sorts arguments by display order, then by name, which is the order of the help message.
+pub fn sort_args<'b: 'd, 'c: 'd, 'd, I>(args: I) -> Vec<&'d ArgWithOrder<'b, 'c>>
+where
+    I: Iterator<Item = &'d ArgWithOrder<'b, 'c>> + 'd,
+{
+    let mut ord_m = VecMap::new();
+    for arg in args {
+        let btm = ord_m.entry(arg.disp_ord()).or_insert(BTreeMap::new());
+        btm.insert(arg.name(), arg);
+    }
+    ord_m
+        .values()
+        .flat_map(|btm| btm.values().cloned())
+        .collect()
+}
+
+/// Sorts the visible subcommands by display order, then by name, which is the order of the help
+/// message.
+pub fn sort_subcommands<'a, 'b, 'z>(p: &'z Parser<'a, 'b>) -> Vec<&'z App<'a, 'b>> {
+    let mut ord_m = VecMap::new();
+    for sc in p.subcommands
+        .iter()
+        .filter(|s| !s.p.is_set(AppSettings::Hidden))
+    {
+        let btm = ord_m.entry(sc.p.meta.disp_ord).or_insert(BTreeMap::new());
+        btm.insert(&*sc.p.meta.name, sc);
+    }
+    ord_m
+        .values()
+        .flat_map(|btm| btm.values().cloned())
+        .collect()
+}
+
+/// The subcommands which are documented on their own, i.e. the visible subcommands except the
+/// generated `help` subcommand, in the order of the help message.
+pub fn documented_subcommands<'a, 'b, 'z>(p: &'z Parser<'a, 'b>) -> Vec<&'z App<'a, 'b>> {
+    let auto_help = p.is_set(AppSettings::NeedsSubcommandHelp)
+        && !p.is_set(AppSettings::DisableHelpSubcommand);
+    sort_subcommands(p)
+        .into_iter()
+        .filter(|sc| !(auto_help && sc.p.meta.name == "help"))
+        .collect()
+}
+
+/// The value names of an argument which takes values as displayed in the help message, i.e.
+/// `<FILE>`, `<name>` or `<arg>...`, along with the delimiter between them and whether an ellipsis
+/// follows them.
+pub fn val_names<'b, 'c>(arg: &ArgWithDisplay<'b, 'c>) -> (Vec<String>, char, bool) {
+    let delim = if arg.is_set(ArgSettings::RequireDelimiter) {
+        arg.val_delim().expect(INTERNAL_ERROR_MSG)
+    } else {
+        ' '
+    };
+    let mult = arg.is_set(ArgSettings::Multiple);
+    if let Some(vec) = arg.val_names() {
+        let names = vec.values().map(|val| format!("<{}>", val)).collect();
+        (names, delim, mult && vec.len() == 1)
+    } else if let Some(num) = arg.num_vals() {
+        let names = (0..num).map(|_| format!("<{}>", arg.name())).collect();
+        (names, delim, mult && num == 1)
+    } else if arg.has_switch() {
+        (vec![format!("<{}>", arg.name())], delim, mult)
+    } else {
+        (vec![arg.to_string()], delim, false)
+    }
+}
 
 impl<'b, 'c> DispOrder for App<'b, 'c> {
     fn disp_ord(&self) -> usize { 999 }
@@ -206,7 +298,7 @@ impl<'a> Help<'a> {
             !(arg.is_set(ArgSettings::Hidden)) || arg.is_set(ArgSettings::NextLineHelp)
         }) {
             if arg.longest_filter() {
-                self.longest = cmp::max(self.longest, str_width(arg.to_string().as_str()));
+                self.longest = cmp::max(self.longest, arg_width(arg));
             }
             arg_v.push(arg)
         }
@@ -230,32 +322,26 @@ impl<'a> Help<'a> {
         debugln!("Help::write_args;");
         // The shortest an arg can legally be is 2 (i.e. '-x')
         self.longest = 2;
-        let mut ord_m = VecMap::new();
-        // Determine the longest
-        for arg in args.filter(|arg| {
+        let args = sort_args(args.filter(|arg| {
             // If it's NextLineHelp, but we don't care to compute how long because it may be
             // NextLineHelp on purpose *because* it's so long and would throw off all other
             // args alignment
             !arg.is_set(ArgSettings::Hidden) || arg.is_set(ArgSettings::NextLineHelp)
-        }) {
-            if arg.longest_filter() {
-                debugln!("Help::write_args: Current Longest...{}", self.longest);
-                self.longest = cmp::max(self.longest, str_width(arg.to_string().as_str()));
-                debugln!("Help::write_args: New Longest...{}", self.longest);
-            }
-            let btm = ord_m.entry(arg.disp_ord()).or_insert(BTreeMap::new());
-            btm.insert(arg.name(), arg);
+        }));
+        // Determine the longest
+        for arg in args.iter().filter(|arg| arg.longest_filter()) {
+            debugln!("Help::write_args: Current Longest...{}", self.longest);
+            self.longest = cmp::max(self.longest, arg_width(arg));
+            debugln!("Help::write_args: New Longest...{}", self.longest);
         }
         let mut first = true;
-        for btm in ord_m.values() {
-            for arg in btm.values() {
-                if first {
-                    first = false;
-                } else {
-                    self.writer.write_all(b"\n")?;
-                }
-                self.write_arg(arg.as_base())?;
+        for arg in args {
+            if first {
+                first = false;
+            } else {
+                self.writer.write_all(b"\n")?;
             }
+            self.write_arg(arg.as_base())?;
         }
         Ok(())
     }
@@ -307,7 +393,11 @@ impl<'a> Help<'a> {
             if arg.short().is_some() {
                 write!(self.writer, ", ")?;
             }
-            color!(self, "--{}", l, good)?;
+            if is_negatable(arg) {
+                color!(self, "--[no-]{}", l, good)?;
+            } else {
+                color!(self, "--{}", l, good)?;
+            }
         }
         Ok(())
     }
@@ -316,41 +406,16 @@ impl<'a> Help<'a> {
     fn val<'b, 'c>(&mut self, arg: &ArgWithDisplay<'b, 'c>) -> Result<String, io::Error> {
         debugln!("Help::val: arg={}", arg);
         if arg.takes_value() {
-            let delim = if arg.is_set(ArgSettings::RequireDelimiter) {
-                arg.val_delim().expect(INTERNAL_ERROR_MSG)
-            } else {
-                ' '
-            };
-            if let Some(vec) = arg.val_names() {
-                let mut it = vec.iter().peekable();
-                while let Some((_, val)) = it.next() {
-                    color!(self, "<{}>", val, good)?;
-                    if it.peek().is_some() {
-                        write!(self.writer, "{}", delim)?;
-                    }
+            let (names, delim, ellipsis) = val_names(arg);
+            let mut it = names.iter().peekable();
+            while let Some(val) = it.next() {
+                color!(self, "{}", val, good)?;
+                if it.peek().is_some() {
+                    write!(self.writer, "{}", delim)?;
                 }
-                let num = vec.len();
-                if arg.is_set(ArgSettings::Multiple) && num == 1 {
-                    color!(self, "...", good)?;
-                }
-            } else if let Some(num) = arg.num_vals() {
-                let mut it = (0..num).peekable();
-                while let Some(_) = it.next() {
-                    color!(self, "<{}>", arg.name(), good)?;
-                    if it.peek().is_some() {
-                        write!(self.writer, "{}", delim)?;
-                    }
-                }
-                if arg.is_set(ArgSettings::Multiple) && num == 1 {
-                    color!(self, "...", good)?;
-                }
-            } else if arg.has_switch() {
-                color!(self, "<{}>", arg.name(), good)?;
-                if arg.is_set(ArgSettings::Multiple) {
-                    color!(self, "...", good)?;
-                }
-            } else {
-                color!(self, "{}", arg, good)?;
+            }
+            if ellipsis {
+                color!(self, "...", good)?;
             }
         }
 
@@ -379,7 +444,7 @@ impl<'a> Help<'a> {
             debug!("Help::val: next_line...");
             if !(nlh || self.force_next_line) {
                 sdebugln!("No");
-                let self_len = str_width(arg.to_string().as_str());
+                let self_len = arg_width(arg);
                 // subtract ourself
                 let mut spcs = self.longest - self_len;
                 // Since we're writing spaces from the tab point we first need to know if we
@@ -400,7 +465,7 @@ impl<'a> Help<'a> {
             sdebugln!("No, and not next_line");
             write_nspaces!(
                 self.writer,
-                self.longest + 4 - (str_width(arg.to_string().as_str()))
+                self.longest + 4 - (arg_width(arg))
             );
         } else {
             sdebugln!("No");
@@ -530,6 +595,10 @@ impl<'a> Help<'a> {
                 ));
             }
         }
+        if let Some(max) = a.max_occurs() {
+            debugln!("Help::spec_vals: Found max occurrences...{}", max);
+            spec_vals.push(format!(" [max: {}]", max));
+        }
         if let Some(ref aliases) = a.aliases() {
             debugln!("Help::spec_vals: Found aliases...{:?}", aliases);
             spec_vals.push(format!(
@@ -560,6 +629,20 @@ impl<'a> Help<'a> {
                     format!(" [possible values: {}]", pv.join(", "))
                 });
             }
+            if let Some(pk) = a.possible_keys() {
+                debugln!("Help::spec_vals: Found possible keys...{:?}", pk);
+                spec_vals.push(if self.color {
+                    format!(
+                        " [possible keys: {}]",
+                        pk.iter()
+                            .map(|k| format!("{}", self.cizer.good(k)))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                } else {
+                    format!(" [possible keys: {}]", pk.join(", "))
+                });
+            }
         }
         spec_vals.join(" ")
     }
@@ -580,7 +663,7 @@ impl<'a> Help<'a> {
             .filter(|arg| !arg.is_set(ArgSettings::Hidden))
             .count() > 0;
         let opts = parser.has_opts();
-        let subcmds = parser.has_visible_subcommands();
+        let subcmds = parser.has_visible_subcommands() || !parser.plugins().is_empty();
 
         let unified_help = parser.is_set(AppSettings::UnifiedHelpMessage);
 
@@ -646,6 +729,11 @@ impl<'a> Help<'a> {
             //self.longest = cmp::max(self.longest, sc.p.meta.name.len());
             btm.insert(sc.p.meta.name.clone(), sc.clone());
         }
+        for (name, _) in parser.plugins() {
+            let btm = ord_m.entry(999).or_insert(BTreeMap::new());
+            self.longest = cmp::max(self.longest, str_width(name.as_str()));
+            btm.insert(name.clone(), App::new(name));
+        }
 
         let mut first = true;
         for btm in ord_m.values() {
@@ -742,7 +830,7 @@ impl<'a> Help<'a> {
         let flags = parser.has_flags();
         let pos = parser.has_positionals();
         let opts = parser.has_opts();
-        let subcmds = parser.has_subcommands();
+        let subcmds = parser.has_subcommands() || !parser.plugins().is_empty();
 
         if flags || opts || pos || subcmds {
             self.write_all_args(parser)?;
@@ -0,0 +1,237 @@
+// Std
+use std::io::{self, Write};
+
+// Internal
+use app::parser::Parser;
+use app::usage;
+use app::help::{as_arg_trait, documented_subcommands, sort_args, sort_subcommands, val_str,
+                ArgWithDisplay, ArgWithOrder};
+use args::{AnyArg, ArgSettings};
+
+// Writes a man(7) page for a `Parser`, its subcommands each get a section of their own
+pub struct Man<'a, 'b>
+where
+    'a: 'b,
+{
+    p: &'b Parser<'a, 'b>,
+}
+
+impl<'a, 'b> Man<'a, 'b> {
+    pub fn new(p: &'b Parser<'a, 'b>) -> Self { Man { p: p } }
+
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        debugln!("Man::write_to;");
+        let p = self.p;
+        let name = p.meta.bin_name.as_ref().unwrap_or(&p.meta.name);
+        let source = match p.meta.version {
+            Some(v) => format!("{} {}", name, v),
+            None => name.clone(),
+        };
+        writeln!(
+            w,
+            ".TH \"{}\" 1 \"\" \"{}\"",
+            escape(&name.to_uppercase()),
+            escape(&source)
+        )?;
+
+        writeln!(w, ".SH NAME")?;
+        match p.meta.about {
+            Some(about) => writeln!(w, "{} \\- {}", escape(name), escape(first_line(about)))?,
+            None => writeln!(w, "{}", escape(name))?,
+        }
+
+        self.write_parser(w, p, ".SH")?;
+        for sc in documented_subcommands(p) {
+            self.write_subcommand(w, &sc.p)?;
+        }
+
+        self.write_env(w)?;
+        if let Some(v) = p.meta.long_version.or(p.meta.version) {
+            writeln!(w, ".SH VERSION")?;
+            write_text(w, v)?;
+        }
+        if let Some(a) = p.meta.author {
+            writeln!(w, ".SH AUTHORS")?;
+            write_text(w, a)?;
+        }
+        Ok(())
+    }
+
+    fn write_subcommand<W: Write>(&self, w: &mut W, p: &Parser) -> io::Result<()> {
+        debugln!("Man::write_subcommand: {}", p.meta.name);
+        let name = p.meta.bin_name.as_ref().unwrap_or(&p.meta.name);
+        writeln!(w, ".SH \"{}\"", escape(&name.to_uppercase()))?;
+        self.write_parser(w, p, ".SS")?;
+        for sc in documented_subcommands(p) {
+            self.write_subcommand(w, &sc.p)?;
+        }
+        Ok(())
+    }
+
+    // Writes the synopsis, description, options, arguments and subcommands of a single parser,
+    // using `sh` for the headings
+    fn write_parser<W: Write>(&self, w: &mut W, p: &Parser, sh: &str) -> io::Result<()> {
+        writeln!(w, "{} SYNOPSIS", sh)?;
+        let usage = usage::create_usage_no_title(p, &[]);
+        for (i, line) in usage.lines().enumerate() {
+            if i > 0 {
+                writeln!(w, ".br")?;
+            }
+            writeln!(w, "\\fB{}\\fR", escape(line.trim()))?;
+        }
+
+        if let Some(about) = p.meta.long_about.or(p.meta.about) {
+            writeln!(w, "{} DESCRIPTION", sh)?;
+            write_text(w, about)?;
+        }
+
+        // Like the help message, options are sorted by display order then name
+        let switches = sort_args(
+            p.flags()
+                .map(as_arg_trait)
+                .chain(p.opts().map(as_arg_trait))
+                .filter(|a| !a.is_set(ArgSettings::Hidden)),
+        );
+        if !switches.is_empty() {
+            writeln!(w, "{} OPTIONS", sh)?;
+            for a in switches {
+                write_arg(w, a.as_base())?;
+            }
+        }
+
+        if p.positionals()
+            .any(|pos| !pos.is_set(ArgSettings::Hidden))
+        {
+            writeln!(w, "{} ARGUMENTS", sh)?;
+            for pos in p.positionals()
+                .filter(|pos| !pos.is_set(ArgSettings::Hidden))
+            {
+                write_arg(w, pos)?;
+            }
+        }
+
+        if p.has_visible_subcommands() {
+            writeln!(w, "{} SUBCOMMANDS", sh)?;
+            for sc in sort_subcommands(p) {
+                writeln!(w, ".TP")?;
+                writeln!(w, "\\fB{}\\fR", escape(&sc.p.meta.name))?;
+                if let Some(about) = sc.p.meta.about {
+                    write_text(w, about)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // All the environment variables read by the app or any of its subcommands
+    fn write_env<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        fn collect<'a, 'b>(p: &'b Parser<'a, 'b>, env: &mut Vec<&'b ArgWithOrder<'a, 'b>>) {
+            let args = p.opts()
+                .map(as_arg_trait)
+                .chain(p.positionals().map(as_arg_trait));
+            for a in args.filter(|a| a.env().is_some() && !a.is_set(ArgSettings::Hidden)) {
+                if !env.iter().any(|e| e.env().unwrap().0 == a.env().unwrap().0) {
+                    env.push(a);
+                }
+            }
+            for sc in documented_subcommands(p) {
+                collect(&sc.p, env);
+            }
+        }
+
+        let mut env = vec![];
+        collect(self.p, &mut env);
+        if env.is_empty() {
+            return Ok(());
+        }
+        writeln!(w, ".SH ENVIRONMENT")?;
+        for a in env {
+            writeln!(w, ".TP")?;
+            writeln!(
+                w,
+                "\\fB{}\\fR",
+                escape(&a.env().unwrap().0.to_string_lossy())
+            )?;
+            if let Some(h) = a.help() {
+                write_text(w, h)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_arg<'a, 'b, W: Write>(w: &mut W, a: &ArgWithDisplay<'a, 'b>) -> io::Result<()> {
+    writeln!(w, ".TP")?;
+    let mut tag = vec![];
+    if let Some(s) = a.short() {
+        tag.push(format!("\\fB\\-{}\\fR", escape(&s.to_string())));
+    }
+    if let Some(l) = a.long() {
+        let no = if a.is_set(ArgSettings::Negatable) && !a.takes_value() {
+            "[no\\-]"
+        } else {
+            ""
+        };
+        tag.push(format!("\\fB\\-\\-{}{}\\fR", no, escape(l)));
+    }
+    let mut tag = tag.join(", ");
+    if !a.has_switch() {
+        tag = format!("\\fI{}\\fR", escape(&val_str(a)));
+    } else if a.takes_value() {
+        tag.push(if a.is_set(ArgSettings::RequireEquals) {
+            '='
+        } else {
+            ' '
+        });
+        tag.push_str(&format!("\\fI{}\\fR", escape(&val_str(a))));
+    }
+    writeln!(w, "{}", tag)?;
+
+    if let Some(h) = a.long_help().or_else(|| a.help()) {
+        write_text(w, h)?;
+    }
+    let mut spec_vals = vec![];
+    if !a.is_set(ArgSettings::HideDefaultValue) {
+        if let Some(dv) = a.default_val() {
+            spec_vals.push(format!("[default: {}]", dv.to_string_lossy()));
+        }
+    }
+    if let Some(aliases) = a.aliases() {
+        spec_vals.push(format!("[aliases: {}]", aliases.join(", ")));
+    }
+    if !a.is_set(ArgSettings::HidePossibleValues) {
+        if let Some(pv) = a.possible_vals() {
+            spec_vals.push(format!("[possible values: {}]", pv.join(", ")));
+        }
+    }
+    if !spec_vals.is_empty() {
+        writeln!(w, ".sp")?;
+        writeln!(w, "{}", escape(&spec_vals.join(" ")))?;
+    }
+    Ok(())
+}
+
+// Writes free form text, such as help messages, as roff lines where empty lines separate
+// paragraphs
+fn write_text<W: Write>(w: &mut W, text: &str) -> io::Result<()> {
+    for line in text.replace("{n}", "\n").lines() {
+        if line.trim().is_empty() {
+            writeln!(w, ".sp")?;
+        } else {
+            writeln!(w, "{}", escape(line))?;
+        }
+    }
+    Ok(())
+}
+
+fn first_line(s: &str) -> &str { s.lines().next().unwrap_or("") }
+
+fn escape(s: &str) -> String {
+    let s = s.replace('\\', "\\e").replace('-', "\\-");
+    // lines starting with a control character would be taken as requests
+    if s.starts_with('.') || s.starts_with('\'') {
+        format!("\\&{}", s)
+    } else {
+        s
+    }
+}
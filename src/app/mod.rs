@@ -1,7 +1,12 @@
 mod settings;
 pub mod parser;
 mod meta;
+mod docs;
 mod help;
+mod man;
+mod plugins;
+mod repl;
+mod response;
 mod validator;
 mod usage;
 
@@ -10,7 +15,7 @@ use std::env;
 use std::ffi::{OsStr, OsString};
 use std::fmt;
 use std::io::{self, BufRead, BufWriter, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process;
 use std::rc::Rc;
 use std::result::Result as StdResult;
@@ -22,9 +27,12 @@ use yaml_rust::Yaml;
 // Internal
 use app::help::Help;
 use app::parser::Parser;
-use args::{AnyArg, Arg, ArgGroup, ArgMatcher, ArgMatches, ArgSettings};
+use args::{AnyArg, Arg, ArgGroup, ArgMatcher, ArgMatches, ArgSettings, Constraint,
+           ValueProvider};
 use errors::Result as ClapResult;
 pub use self::settings::AppSettings;
+pub use self::docs::DocFormat;
+pub use self::repl::Repl;
 use completions::Shell;
 use map::{self, VecMap};
 
@@ -1038,6 +1046,31 @@ impl<'a, 'b> App<'a, 'b> {
         self
     }
 
+    /// Adds a [`Constraint`] which the arguments used with this [`App`] must satisfy. Constraints
+    /// are checked in the order they were added, after the [required] arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Constraint, ErrorKind};
+    /// let res = App::new("prog")
+    ///     .args_from_usage(
+    ///         "-a 'a'
+    ///          -b 'b'
+    ///          -c 'c'")
+    ///     .constraint(Constraint::exactly_one(vec!["a", "b"]).unless("c"))
+    ///     .get_matches_from_safe(vec!["prog", "-a", "-b"]);
+    ///
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::ConstraintViolation);
+    /// ```
+    /// [`Constraint`]: ./struct.Constraint.html
+    /// [`App`]: ./struct.App.html
+    /// [required]: ./struct.Arg.html#method.required
+    pub fn constraint(mut self, c: Constraint<'a>) -> Self {
+        self.p.constraints.push(c);
+        self
+    }
+
     /// Adds a [`SubCommand`] to the list of valid possibilities. Subcommands are effectively
     /// sub-[`App`]s, because they can contain their own arguments, subcommands, version, usage,
     /// etc. They also function just like [`App`]s, in that they get their own auto generated help,
@@ -1086,6 +1119,121 @@ impl<'a, 'b> App<'a, 'b> {
         self
     }
 
+    /// Adds a [`ValueProvider`], such as a configuration file, which is asked for the values of
+    /// arguments that weren't given on the command line nor through their [environment
+    /// variable], before falling back to their [default value].
+    ///
+    /// Providers are consulted in the order they were added, the first one returning values for
+    /// an argument wins. Providers added to an app are also used by its [`SubCommand`]s, after the
+    /// ones added to the subcommand itself.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, SubCommand, ValueProvider};
+    /// # use std::ffi::OsString;
+    /// struct Config;
+    ///
+    /// impl ValueProvider for Config {
+    ///     fn name(&self) -> &str { "~/.config/prog/config.toml" }
+    ///
+    ///     fn values(&self, cmd: &[&str], arg: &str) -> Option<Vec<OsString>> {
+    ///         match (cmd, arg) {
+    ///             (&["remote"], "url") => Some(vec!["https://example.com".into()]),
+    ///             _ => None,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let m = App::new("prog")
+    ///     .value_provider(Config)
+    ///     .subcommand(SubCommand::with_name("remote")
+    ///         .arg(Arg::with_name("url")
+    ///             .long("url")
+    ///             .env("PROG_URL")
+    ///             .takes_value(true)))
+    ///     .get_matches_from(vec!["prog", "remote"]);
+    ///
+    /// let sub_m = m.subcommand_matches("remote").unwrap();
+    /// assert_eq!(sub_m.value_of("url"), Some("https://example.com"));
+    /// ```
+    /// [`ValueProvider`]: ./trait.ValueProvider.html
+    /// [environment variable]: ./struct.Arg.html#method.env
+    /// [default value]: ./struct.Arg.html#method.default_value
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    pub fn value_provider<P: ValueProvider + 'static>(mut self, provider: P) -> Self {
+        self.p.providers.push(Rc::new(provider));
+        self
+    }
+
+    /// Adds a function which is given all the matches of this [`App`] or [`SubCommand`] once they
+    /// passed every other validation, for the rules which involve more than one argument. Its
+    /// [`Error`] is returned by the [`App::get_matches`] family of methods as is, use
+    /// [`ArgMatches::error`] to create one which looks like the ones of clap itself.
+    ///
+    /// **NOTE:** The values of [global arguments] used before a [`SubCommand`] aren't in its
+    /// matches yet when its validator is run.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let app = App::new("server")
+    ///     .arg(Arg::with_name("host").long("host").takes_value(true))
+    ///     .arg(Arg::with_name("port").long("port").takes_value(true))
+    ///     .validator(|m| {
+    ///         let unix_socket = m.value_of("host").map_or(false, |h| h.starts_with('/'));
+    ///         if !unix_socket && !m.is_present("port") {
+    ///             Err(m.error(
+    ///                 ErrorKind::MissingRequiredArgument,
+    ///                 "'--port' is required unless '--host' is a unix socket path",
+    ///             ))
+    ///         } else {
+    ///             Ok(())
+    ///         }
+    ///     });
+    ///
+    /// assert!(app.clone()
+    ///     .get_matches_from_safe(vec!["server", "--host", "/run/server.sock"])
+    ///     .is_ok());
+    ///
+    /// let res = app.get_matches_from_safe(vec!["server", "--host", "localhost"]);
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+    /// ```
+    /// [`App`]: ./struct.App.html
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [`Error`]: ./struct.Error.html
+    /// [`App::get_matches`]: ./struct.App.html#method.get_matches
+    /// [`ArgMatches::error`]: ./struct.ArgMatches.html#method.error
+    /// [global arguments]: ./struct.Arg.html#method.global
+    pub fn validator<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&ArgMatches) -> ClapResult<()> + 'static,
+    {
+        self.p.validator = Some(Rc::new(f));
+        self
+    }
+
+    /// Adds a directory to search for plugins in, instead of those of the `PATH` environment
+    /// variable. Directories are searched in the order they were added.
+    ///
+    /// **NOTE:** This implicitly sets [`AppSettings::PluginSubcommands`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::App;
+    /// let m = App::new("tool")
+    ///     .plugin_dir("/usr/lib/tool/plugins")
+    ///     .plugin_dir("/usr/local/lib/tool/plugins")
+    ///     .get_matches();
+    /// ```
+    /// [`AppSettings::PluginSubcommands`]: ./enum.AppSettings.html#variant.PluginSubcommands
+    pub fn plugin_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.p.plugin_dirs.push(dir.into());
+        self.setting(AppSettings::PluginSubcommands)
+    }
+
     /// Allows custom ordering of [`SubCommand`]s within the help message. Subcommands with a lower
     /// value will be displayed first in the help message. This is helpful when one would like to
     /// emphasise frequently used subcommands, or prioritize those towards the top of the list.
@@ -1385,6 +1533,11 @@ impl<'a, 'b> App<'a, 'b> {
     /// `<project>/target/debug/build/myapp-<hash>/out/myapp.bash`.
     ///
     /// Fish shell completions will use the file format `{bin_name}.fish`
+    ///
+    /// When using [`AppSettings::Multicall`], a file is also written for each subcommand, which
+    /// completes it as a binary of its own, i.e. `{subcommand}.bash`.
+    ///
+    /// [`AppSettings::Multicall`]: ./enum.AppSettings.html#variant.Multicall
     pub fn gen_completions<T: Into<OsString>, S: Into<String>>(
         &mut self,
         bin_name: S,
@@ -1438,6 +1591,188 @@ impl<'a, 'b> App<'a, 'b> {
         self.p.gen_completions_to(for_shell, buf);
     }
 
+    /// Generate a completions file for a specified shell at compile time, like
+    /// [`App::gen_completions`], except that the script calls back into the binary to compute
+    /// the completions for the current command line every time the user presses `<TAB>`. This
+    /// allows completing values which depend on runtime state, such as git branches, remote hosts
+    /// or files of a given extension, by way of [`Arg::completer`].
+    ///
+    /// The binary must use [`AppSettings::DynamicCompletion`] in order to answer the hidden
+    /// `--complete` entry point used by the script. The file names are the same as for
+    /// [`App::gen_completions`].
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // src/cli.rs
+    ///
+    /// use clap::{App, Arg, AppSettings};
+    ///
+    /// pub fn build_cli() -> App<'static, 'static> {
+    ///     App::new("mygit")
+    ///         .setting(AppSettings::DynamicCompletion)
+    ///         .arg(Arg::with_name("branch")
+    ///             .index(1)
+    ///             .completer(|cur| list_branches(cur)))
+    /// }
+    /// ```
+    ///
+    /// ```ignore
+    /// // build.rs
+    ///
+    /// let mut app = build_cli();
+    /// app.gen_dynamic_completions("mygit", Shell::Bash, outdir);
+    /// ```
+    /// [`App::gen_completions`]: ./struct.App.html#method.gen_completions
+    /// [`Arg::completer`]: ./struct.Arg.html#method.completer
+    /// [`AppSettings::DynamicCompletion`]: ./enum.AppSettings.html#variant.DynamicCompletion
+    pub fn gen_dynamic_completions<T: Into<OsString>, S: Into<String>>(
+        &mut self,
+        bin_name: S,
+        for_shell: Shell,
+        out_dir: T,
+    ) {
+        self.p.meta.bin_name = Some(bin_name.into());
+        self.p.gen_dynamic_completions(for_shell, out_dir.into());
+    }
+
+    /// Generate a completions file which calls back into the binary, like
+    /// [`App::gen_dynamic_completions`], but write it to `buf` at runtime instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, AppSettings, Shell};
+    /// let mut buf = vec![];
+    /// App::new("myapp")
+    ///     .setting(AppSettings::DynamicCompletion)
+    ///     .gen_dynamic_completions_to("myapp", Shell::Bash, &mut buf);
+    ///
+    /// let script = String::from_utf8(buf).unwrap();
+    /// assert!(script.contains("--complete"));
+    /// ```
+    /// [`App::gen_dynamic_completions`]: ./struct.App.html#method.gen_dynamic_completions
+    pub fn gen_dynamic_completions_to<W: Write, S: Into<String>>(
+        &mut self,
+        bin_name: S,
+        for_shell: Shell,
+        buf: &mut W,
+    ) {
+        self.p.meta.bin_name = Some(bin_name.into());
+        self.p.gen_dynamic_completions_to(for_shell, buf);
+    }
+
+    /// Generate a `man(7)` page for the `App` at compile time, similar to
+    /// [`App::gen_completions`]. The page is written to `{bin_name}.1` in `out_dir` and is made
+    /// from the same metadata as the help message, so the two can't drift apart:
+    ///
+    /// * `NAME` from the binary name and [`App::about`]
+    /// * `SYNOPSIS` from the usage string
+    /// * `DESCRIPTION` from [`App::long_about`], or [`App::about`]
+    /// * `OPTIONS` and `ARGUMENTS` from [`Arg::long_help`], or [`Arg::help`], along with the
+    ///   default and possible values of each argument. Hidden arguments are left out.
+    /// * `SUBCOMMANDS`, followed by a section for each subcommand with the same contents
+    /// * `ENVIRONMENT` from [`Arg::env`]
+    /// * `VERSION` and `AUTHORS` from [`App::version`] and [`App::author`]
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // build.rs
+    ///
+    /// let mut app = build_cli();
+    /// app.gen_man("myapp", outdir);
+    /// ```
+    /// [`App::gen_completions`]: ./struct.App.html#method.gen_completions
+    /// [`App::about`]: ./struct.App.html#method.about
+    /// [`App::long_about`]: ./struct.App.html#method.long_about
+    /// [`App::version`]: ./struct.App.html#method.version
+    /// [`App::author`]: ./struct.App.html#method.author
+    /// [`Arg::long_help`]: ./struct.Arg.html#method.long_help
+    /// [`Arg::help`]: ./struct.Arg.html#method.help
+    /// [`Arg::env`]: ./struct.Arg.html#method.env
+    pub fn gen_man<T: Into<OsString>, S: Into<String>>(&mut self, bin_name: S, out_dir: T) {
+        self.p.meta.bin_name = Some(bin_name.into());
+        self.p.gen_man(out_dir.into());
+    }
+
+    /// Generate a `man(7)` page for the `App` at runtime, like [`App::gen_man`], writing it
+    /// to `buf`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let mut buf = vec![];
+    /// App::new("myapp")
+    ///     .about("does things")
+    ///     .arg(Arg::with_name("verbose").short("v").help("prints more"))
+    ///     .gen_man_to("myapp", &mut buf);
+    ///
+    /// let page = String::from_utf8(buf).unwrap();
+    /// assert!(page.contains(".SH NAME\nmyapp \\- does things\n"));
+    /// ```
+    /// [`App::gen_man`]: ./struct.App.html#method.gen_man
+    pub fn gen_man_to<W: Write, S: Into<String>>(&mut self, bin_name: S, buf: &mut W) {
+        self.p.meta.bin_name = Some(bin_name.into());
+        self.p.gen_man_to(buf);
+    }
+
+    /// Generate reference documentation for the `App` in Markdown or HTML at compile time,
+    /// similar to [`App::gen_man`]. The document is written to `{bin_name}.md` or
+    /// `{bin_name}.html` in `out_dir`.
+    ///
+    /// The document describes the `App` and each of its subcommands with their usage, flags,
+    /// options, positional arguments (along with their aliases, default values, possible values and
+    /// environment variables), groups and subcommands. Like in the help message, hidden arguments
+    /// and subcommands are left out and arguments are sorted by [`Arg::display_order`].
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // build.rs
+    ///
+    /// let mut app = build_cli();
+    /// app.gen_docs("myapp", DocFormat::Markdown, outdir);
+    /// ```
+    /// [`App::gen_man`]: ./struct.App.html#method.gen_man
+    /// [`Arg::display_order`]: ./struct.Arg.html#method.display_order
+    pub fn gen_docs<T: Into<OsString>, S: Into<String>>(
+        &mut self,
+        bin_name: S,
+        format: DocFormat,
+        out_dir: T,
+    ) {
+        self.p.meta.bin_name = Some(bin_name.into());
+        self.p.gen_docs(format, out_dir.into());
+    }
+
+    /// Generate reference documentation for the `App` in Markdown or HTML at runtime, like
+    /// [`App::gen_docs`], writing it to `buf`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, DocFormat};
+    /// let mut buf = vec![];
+    /// App::new("myapp")
+    ///     .arg(Arg::with_name("verbose").short("v").help("prints more"))
+    ///     .gen_docs_to("myapp", DocFormat::Markdown, &mut buf);
+    ///
+    /// let doc = String::from_utf8(buf).unwrap();
+    /// assert!(doc.contains("- `-v`: prints more\n"));
+    /// ```
+    /// [`App::gen_docs`]: ./struct.App.html#method.gen_docs
+    pub fn gen_docs_to<W: Write, S: Into<String>>(
+        &mut self,
+        bin_name: S,
+        format: DocFormat,
+        buf: &mut W,
+    ) {
+        self.p.meta.bin_name = Some(bin_name.into());
+        self.p.gen_docs_to(format, buf);
+    }
+
     /// Starts the parsing process, upon a failed parse an error will be displayed to the user and
     /// the process will exit with the appropriate error code. By default this method gets all user
     /// provided arguments from [`env::args_os`] in order to allow for invalid UTF-8 code points,
@@ -1570,6 +1905,53 @@ impl<'a, 'b> App<'a, 'b> {
         self.get_matches_from_safe_borrow(itr)
     }
 
+    /// Starts the parsing process like [`App::get_matches_from_safe`], except that arguments
+    /// which aren't recognized are returned, in their original order, instead of failing with an
+    /// [`ErrorKind::UnknownArgument`] error. This allows peeling off an application's own
+    /// arguments and forwarding the rest to another program.
+    ///
+    /// Unknown arguments are returned as they were given, i.e. `--name=value` stays in one piece
+    /// and so does a group of shorts from the first unknown one on, like `-xyz`. The argument
+    /// following an unknown option, such as `value` in `--name value`, is assumed to be its value
+    /// and returned as well, unless it starts with a `-`, or a [positional argument] or
+    /// [`SubCommand`] of the application can take it. Arguments after `--` are only returned if
+    /// no positional argument takes them, and then the `--` is returned in front of them.
+    ///
+    /// **NOTE:** All other errors, such as missing required arguments or invalid values, are
+    /// still reported.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// # use std::ffi::OsString;
+    /// let (m, rest) = App::new("wrapper")
+    ///     .arg(Arg::from_usage("-v, --verbose 'log what is being run'"))
+    ///     .get_known_matches_from_safe(vec![
+    ///         "wrapper", "--jobs", "4", "-v", "--release", "-x"
+    ///     ])
+    ///     .unwrap();
+    ///
+    /// assert!(m.is_present("verbose"));
+    /// assert_eq!(rest, ["--jobs", "4", "--release", "-x"].iter().map(OsString::from).collect::<Vec<_>>());
+    /// ```
+    /// [`App::get_matches_from_safe`]: ./struct.App.html#method.get_matches_from_safe
+    /// [`ErrorKind::UnknownArgument`]: ./enum.ErrorKind.html#variant.UnknownArgument
+    /// [positional argument]: ./struct.Arg.html#method.index
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    pub fn get_known_matches_from_safe<I, T>(
+        mut self,
+        itr: I,
+    ) -> ClapResult<(ArgMatches<'a>, Vec<OsString>)>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        self.p.unknown = Some(vec![]);
+        let m = self.get_matches_from_safe_borrow(itr)?;
+        Ok((m, self.p.unknown.take().unwrap_or_default()))
+    }
+
     /// Starts the parsing process without consuming the [`App`] struct `self`. This is normally not
     /// the desired functionality, instead prefer [`App::get_matches_from_safe`] which *does*
     /// consume `self`.
@@ -1596,14 +1978,7 @@ impl<'a, 'b> App<'a, 'b> {
         I: IntoIterator<Item = T>,
         T: Into<OsString> + Clone,
     {
-        // If there are global arguments, or settings we need to propagate them down to subcommands
-        // before parsing incase we run into a subcommand
-        if !self.p.is_set(AppSettings::Propagated) {
-            self.p.propagate_globals();
-            self.p.propagate_settings();
-            self.p.derive_display_order();
-            self.p.set(AppSettings::Propagated);
-        }
+        self.p.propagate_once();
 
         let mut matcher = ArgMatcher::new();
 
@@ -1615,12 +1990,16 @@ impl<'a, 'b> App<'a, 'b> {
         // will have two arguments, './target/release/my_prog', '-a' but we don't want
         // to display
         // the full path when displaying help messages and such
+        let mut applet = None;
         if !self.p.is_set(AppSettings::NoBinaryName) {
             if let Some(name) = it.next() {
                 let bn_os = name.into();
                 let p = Path::new(&*bn_os);
                 if let Some(f) = p.file_name() {
                     if let Some(s) = f.to_os_string().to_str() {
+                        if self.p.is_set(AppSettings::Multicall) {
+                            applet = self.p.applet(s).map(|sc| (sc, s.to_owned()));
+                        }
                         if self.p.meta.bin_name.is_none() {
                             self.p.meta.bin_name = Some(s.to_owned());
                         }
@@ -1630,8 +2009,15 @@ impl<'a, 'b> App<'a, 'b> {
         }
 
         // do the real parsing
-        if let Err(e) = self.p.get_matches_with(&mut matcher, &mut it.peekable()) {
-            return Err(e);
+        let args: Box<Iterator<Item = OsString>> = if self.p.is_set(AppSettings::ResponseFiles) {
+            Box::new(response::expand(it.map(Into::into), self.p.color())?.into_iter())
+        } else {
+            Box::new(it.map(Into::into))
+        };
+        if let Some((sc, bin)) = applet {
+            self.p.parse_applet(&sc, &bin, &mut matcher, &mut args.peekable())?;
+        } else {
+            self.p.get_matches_with(&mut matcher, &mut args.peekable())?;
         }
 
         let global_arg_vec: Vec<&str> = (&self).p.global_args.iter().map(|ga| ga.b.name).collect();
@@ -1802,6 +2188,7 @@ impl<'n, 'e> AnyArg<'n, 'e> for App<'n, 'e> {
     }
     fn has_switch(&self) -> bool { false }
     fn max_vals(&self) -> Option<u64> { None }
+    fn max_occurs(&self) -> Option<u64> { None }
     fn num_vals(&self) -> Option<u64> { None }
     fn possible_vals(&self) -> Option<&[&'e str]> { None }
     fn validator(&self) -> Option<&Rc<Fn(String) -> StdResult<(), String>>> { None }
@@ -1810,6 +2197,8 @@ impl<'n, 'e> AnyArg<'n, 'e> for App<'n, 'e> {
     fn short(&self) -> Option<char> { None }
     fn long(&self) -> Option<&'e str> { None }
     fn val_delim(&self) -> Option<char> { None }
+    fn key_value_delim(&self) -> Option<char> { None }
+    fn possible_keys(&self) -> Option<&[&'e str]> { None }
     fn takes_value(&self) -> bool { true }
     fn help(&self) -> Option<&'e str> { self.p.meta.about }
     fn long_help(&self) -> Option<&'e str> { self.p.meta.long_about }
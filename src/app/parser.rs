@@ -8,7 +8,9 @@ use std::os::unix::ffi::OsStrExt;
 use std::path::PathBuf;
 use std::slice::Iter;
 use std::iter::Peekable;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
+use std::env::consts::EXE_SUFFIX;
+use std::rc::Rc;
 
 // Internal
 use INTERNAL_ERROR_MSG;
@@ -16,12 +18,16 @@ use INVALID_UTF8;
 use SubCommand;
 use app::App;
 use app::help::Help;
+use app::man::Man;
+use app::docs::{DocFormat, DocGen};
 use app::meta::AppMeta;
+use app::plugins;
 use app::settings::AppFlags;
-use args::{AnyArg, Arg, ArgGroup, ArgMatcher, Base, FlagBuilder, OptBuilder, PosBuilder, Switched};
+use args::{AnyArg, Arg, ArgGroup, ArgMatcher, ArgMatches, Base, Constraint, FlagBuilder,
+           OptBuilder, PosBuilder, Switched, ValueProvider, ValueSource};
 use args::settings::ArgSettings;
-use completions::ComplGen;
-use errors::{Error, ErrorKind};
+use completions::{self, ComplGen, DynamicGen};
+use errors::{self, Error, ErrorKind};
 use errors::Result as ClapResult;
 use fmt::ColorWhen;
 use osstringext::OsStrExt2;
@@ -32,6 +38,9 @@ use app::validator::Validator;
 use app::usage;
 use map::{self, VecMap};
 
+#[doc(hidden)]
+pub type AppValidator = Fn(&ArgMatches) -> ClapResult<()>;
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 #[doc(hidden)]
 pub enum ParseResult<'a> {
@@ -42,6 +51,7 @@ pub enum ParseResult<'a> {
     MaybeNegNum,
     NotFound,
     ValuesDone,
+    Unknown,
 }
 
 #[allow(missing_debug_implementations)]
@@ -63,12 +73,24 @@ where
     pub required: Vec<&'a str>,
     pub r_ifs: Vec<(&'a str, &'b str, &'a str)>,
     pub overrides: Vec<(&'b str, &'a str)>,
-    help_short: Option<char>,
-    version_short: Option<char>,
+    pub help_short: Option<char>,
+    pub version_short: Option<char>,
     cache: Option<&'a str>,
     pub help_message: Option<&'a str>,
     pub version_message: Option<&'a str>,
+    pub providers: Vec<Rc<ValueProvider>>,
+    pub validator: Option<Rc<AppValidator>>,
+    pub constraints: Vec<Constraint<'a>>,
+    sc_path: Vec<String>,
     cur_idx: Cell<usize>,
+    pub unknown: Option<Vec<OsString>>,
+    // With `AppSettings::ChainSubcommands` on the parent, the names of the sibling subcommands
+    // which end this one's arguments, and the one which did
+    siblings: Vec<String>,
+    chained_to: Option<String>,
+    pub plugin_dirs: Vec<PathBuf>,
+    // The plugins found by the last search, cleared before parsing
+    plugins: RefCell<Option<Vec<(String, PathBuf)>>>,
 }
 
 impl<'a, 'b> Parser<'a, 'b>
@@ -101,6 +123,96 @@ where
     }
 
     pub fn gen_completions_to<W: Write>(&mut self, for_shell: Shell, buf: &mut W) {
+        self.propagate_all();
+        let plugins = self.plugins();
+        if plugins.is_empty() {
+            return ComplGen::new(self).generate(for_shell, buf);
+        }
+        // The scripts complete plugins like subcommands without arguments of their own
+        let mut p = self.clone();
+        for (name, _) in plugins {
+            p.subcommands.push(App::new(name));
+        }
+        p.build_bin_names();
+        ComplGen::new(&p).generate(for_shell, buf)
+    }
+
+    pub fn gen_completions(&mut self, for_shell: Shell, od: OsString) {
+        let mut file = self.completions_file(for_shell, od.clone());
+        self.gen_completions_to(for_shell, &mut file);
+        if self.is_set(AS::Multicall) {
+            for sc in &self.subcommands {
+                let applet = Parser::applet_parser(sc);
+                let mut file = applet.completions_file(for_shell, od.clone());
+                ComplGen::new(&applet).generate(for_shell, &mut file)
+            }
+        }
+    }
+
+    pub fn gen_dynamic_completions_to<W: Write>(&mut self, for_shell: Shell, buf: &mut W) {
+        self.propagate_all();
+        DynamicGen::new(self).generate(for_shell, buf)
+    }
+
+    pub fn gen_dynamic_completions(&mut self, for_shell: Shell, od: OsString) {
+        let mut file = self.completions_file(for_shell, od);
+        self.gen_dynamic_completions_to(for_shell, &mut file)
+    }
+
+    pub fn gen_man_to<W: Write>(&mut self, buf: &mut W) {
+        self.propagate_all();
+        if let Err(e) = Man::new(self).write_to(buf) {
+            panic!("Failed to write man page: {}", e);
+        }
+    }
+
+    pub fn gen_man(&mut self, od: OsString) {
+        use std::error::Error;
+
+        let out_dir = PathBuf::from(od);
+        let file_name = format!("{}.1", self.meta.bin_name.as_ref().unwrap());
+        let mut file = match File::create(out_dir.join(file_name)) {
+            Err(why) => panic!("couldn't create man page: {}", why.description()),
+            Ok(file) => file,
+        };
+        self.gen_man_to(&mut file)
+    }
+
+    pub fn gen_docs_to<W: Write>(&mut self, format: DocFormat, buf: &mut W) {
+        self.propagate_all();
+        if let Err(e) = DocGen::new(self, format).write_to(buf) {
+            panic!("Failed to write documentation: {}", e);
+        }
+    }
+
+    pub fn gen_docs(&mut self, format: DocFormat, od: OsString) {
+        use std::error::Error;
+
+        let out_dir = PathBuf::from(od);
+        let file_name = format!(
+            "{}.{}",
+            self.meta.bin_name.as_ref().unwrap(),
+            format.extension()
+        );
+        let mut file = match File::create(out_dir.join(file_name)) {
+            Err(why) => panic!("couldn't create documentation file: {}", why.description()),
+            Ok(file) => file,
+        };
+        self.gen_docs_to(format, &mut file)
+    }
+
+    // If there are global arguments, or settings we need to propagate them down to subcommands
+    // before parsing incase we run into a subcommand
+    pub fn propagate_once(&mut self) {
+        if !self.is_set(AS::Propagated) {
+            self.propagate_globals();
+            self.propagate_settings();
+            self.derive_display_order();
+            self.set(AS::Propagated);
+        }
+    }
+
+    fn propagate_all(&mut self) {
         if !self.is_set(AS::Propagated) {
             self.propagate_help_version();
             self.build_bin_names();
@@ -108,11 +220,9 @@ where
             self.propagate_settings();
             self.set(AS::Propagated);
         }
-
-        ComplGen::new(self).generate(for_shell, buf)
     }
 
-    pub fn gen_completions(&mut self, for_shell: Shell, od: OsString) {
+    fn completions_file(&self, for_shell: Shell, od: OsString) -> File {
         use std::error::Error;
 
         let out_dir = PathBuf::from(od);
@@ -122,13 +232,15 @@ where
             Shell::Fish => format!("{}.fish", name),
             Shell::Zsh => format!("_{}", name),
             Shell::PowerShell => format!("_{}.ps1", name),
+            Shell::Elvish => format!("{}.elv", name),
+            Shell::Nushell => format!("{}.nu", name),
+            Shell::__Nonexhaustive => unreachable!(),
         };
 
-        let mut file = match File::create(out_dir.join(file_name)) {
+        match File::create(out_dir.join(file_name)) {
             Err(why) => panic!("couldn't create completion file: {}", why.description()),
             Ok(file) => file,
-        };
-        self.gen_completions_to(for_shell, &mut file)
+        }
     }
 
     #[inline]
@@ -163,6 +275,39 @@ where
                     || self.groups.iter().any(|g| &&g.name == arg)))
                 .unwrap()
         );
+        let valued = self.opts
+            .iter()
+            .map(|o| (o.b.name, o.b.max_occurs))
+            .chain(self.positionals.values().map(|p| (p.b.name, p.b.max_occurs)));
+        for (name, max) in valued {
+            assert!(
+                max.is_none(),
+                "The argument '{}' takes values, but max_occurrences only applies to flags.",
+                name
+            );
+        }
+        for f in &self.flags {
+            if let Some(target) = f.b.decrements {
+                assert!(
+                    self.flags.iter().any(|t| t.b.name == target),
+                    "The flag '{}' decrements the flag '{}' that doesn't actually exist.",
+                    f.b.name,
+                    target
+                );
+            }
+        }
+        for c in &self.constraints {
+            for name in c.arg_names() {
+                assert!(
+                    self.flags.iter().any(|f| f.b.name == name)
+                        || self.opts.iter().any(|o| o.b.name == name)
+                        || self.positionals.values().any(|p| p.b.name == name)
+                        || self.groups.iter().any(|g| g.name == name),
+                    "A constraint uses the arg '{}' that doesn't actually exist.",
+                    name
+                );
+            }
+        }
         true
     }
 
@@ -359,6 +504,8 @@ where
             grp.requires = group.requires.clone();
             grp.conflicts = group.conflicts.clone();
             grp.required = group.required;
+            grp.min_args = group.min_args;
+            grp.max_args = group.max_args;
         } else {
             self.groups.push(group);
         }
@@ -408,6 +555,14 @@ where
                 sc.p.g_settings = sc.p.g_settings | self.g_settings;
                 sc.p.meta.term_w = self.meta.term_w;
                 sc.p.meta.max_w = self.meta.max_w;
+                // Providers of a subcommand take precedence over the ones of its parents
+                for vp in &self.providers {
+                    if !sc.p.providers.iter().any(|p| Rc::ptr_eq(p, vp)) {
+                        sc.p.providers.push(Rc::clone(vp));
+                    }
+                }
+                sc.p.sc_path = self.sc_path.clone();
+                sc.p.sc_path.push(sc.p.meta.name.clone());
             }
             sc.p.propagate_settings();
         }
@@ -846,8 +1001,6 @@ where
         ret
     }
 
-    // The actual parsing function
-    #[cfg_attr(feature = "lints", allow(while_let_on_iterator, collapsible_if))]
     pub fn get_matches_with<I, T>(
         &mut self,
         matcher: &mut ArgMatcher<'a>,
@@ -858,6 +1011,31 @@ where
         T: Into<OsString> + Clone,
     {
         debugln!("Parser::get_matches_with;");
+        // Overridden arguments are taken out of the requirements while parsing, and some
+        // settings only describe the command line being parsed. Both are undone afterwards so the
+        // same parser can be used again, i.e. by `App::get_matches_from_safe_borrow`.
+        let required = self.required.clone();
+        self.overrides.clear();
+        self.cache = None;
+        self.unset(AS::TrailingValues);
+        self.unset(AS::ValidArgFound);
+        *self.plugins.borrow_mut() = None;
+        let res = self.parse_args(matcher, it);
+        self.required = required;
+        res
+    }
+
+    // The actual parsing function
+    #[cfg_attr(feature = "lints", allow(while_let_on_iterator, collapsible_if))]
+    fn parse_args<I, T>(
+        &mut self,
+        matcher: &mut ArgMatcher<'a>,
+        it: &mut Peekable<I>,
+    ) -> ClapResult<()>
+    where
+        I: Iterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
         // Verify all positional assertions pass
         debug_assert!(self.app_debug_asserts());
         if self.positionals.values().any(|a| {
@@ -872,13 +1050,29 @@ where
         }
         let has_args = self.has_args();
 
+        // A completion script is asking for the candidates of the current command line
+        if self.is_set(AS::DynamicCompletion)
+            && it.peek().map_or(false, |a| a.clone().into() == *"--complete")
+        {
+            return Err(self.complete(it.skip(1).map(|a| a.into())));
+        }
+
         // Next we create the `--help` and `--version` arguments and add them if
         // necessary
         self.create_help_and_version();
 
+        // Flags lowering the level of others, for `ArgMatches::verbosity`
+        matcher.0.decrements = self.flags
+            .iter()
+            .filter_map(|f| f.b.decrements.map(|target| (f.b.name, target)))
+            .collect();
+
         let mut subcmd_name: Option<String> = None;
+        let mut plugin_path: Option<PathBuf> = None;
         let mut needs_val_of: ParseResult<'a> = ParseResult::NotFound;
         let mut pos_counter = 1;
+        let all_errors = self.is_set(AS::ReportAllErrors);
+        let mut errors = vec![];
         while let Some(arg) = it.next() {
             let arg_os = arg.into();
             debugln!(
@@ -895,9 +1089,30 @@ where
             {
                 debugln!("Parser::get_matches_with: setting TrailingVals=true");
                 self.set(AS::TrailingValues);
+                // Trailing values none of our positionals take are passed on after the `--`, so
+                // they still aren't taken for options by whoever gets them
+                let passed_on = self.positionals.get(pos_counter).is_none()
+                    && !self.is_set(AS::ContainsLast);
+                if let (true, Some(unknown)) = (passed_on, self.unknown.as_mut()) {
+                    unknown.push(arg_os);
+                }
                 continue;
             }
 
+            // An unknown option is passed on along with what looks like its value, unless one of
+            // our positional arguments or subcommands could use it
+            if needs_val_of == ParseResult::Unknown {
+                needs_val_of = ParseResult::NotFound;
+                if !starts_new_arg && !self.is_set(AS::TrailingValues)
+                    && self.positionals.get(pos_counter).is_none()
+                    && !self.possible_subcommand(&arg_os).0
+                {
+                    debugln!("Parser::get_matches_with: passing on the unknown option's value");
+                    self.unknown.as_mut().expect(INTERNAL_ERROR_MSG).push(arg_os);
+                    continue;
+                }
+            }
+
             // Has the user already passed '--'? Meaning only positional args follow
             if !self.is_set(AS::TrailingValues) {
                 // Does the arg match a subcommand name, or any of it's aliases (if defined)
@@ -919,6 +1134,22 @@ where
                                 subcmd_name = Some(sc_name.to_owned());
                                 break;
                             }
+                            if let Some((name, path)) = self.find_plugin(&arg_os) {
+                                debugln!("Parser::get_matches_with: found plugin {:?}", path);
+                                subcmd_name = Some(name);
+                                plugin_path = Some(path);
+                                break;
+                            }
+                            if let Some(sibling) = arg_os.to_str() {
+                                if self.siblings.iter().any(|s| s == sibling) {
+                                    debugln!(
+                                        "Parser::get_matches_with: chaining to sibling sc={}",
+                                        sibling
+                                    );
+                                    self.chained_to = Some(sibling.to_owned());
+                                    break;
+                                }
+                            }
                         }
                     }
                 }
@@ -936,22 +1167,37 @@ where
                     }
 
                     if arg_os.starts_with(b"--") {
-                        needs_val_of = self.parse_long_arg(matcher, &arg_os)?;
+                        needs_val_of = match self.parse_long_arg(matcher, &arg_os) {
+                            Ok(res) => res,
+                            Err(e) => {
+                                errors::collect(all_errors, Err(e), &mut errors)?;
+                                needs_val_of = ParseResult::NotFound;
+                                continue;
+                            }
+                        };
                         debugln!(
                             "Parser:get_matches_with: After parse_long_arg {:?}",
                             needs_val_of
                         );
                         match needs_val_of {
-                            ParseResult::Flag | ParseResult::Opt(..) | ParseResult::ValuesDone => {
-                                continue
-                            }
+                            ParseResult::Flag
+                            | ParseResult::Opt(..)
+                            | ParseResult::ValuesDone
+                            | ParseResult::Unknown => continue,
                             _ => (),
                         }
                     } else if arg_os.starts_with(b"-") && arg_os.len_() != 1 {
                         // Try to parse short args like normal, if AllowLeadingHyphen or
                         // AllowNegativeNumbers is set, parse_short_arg will *not* throw
                         // an error, and instead return Ok(None)
-                        needs_val_of = self.parse_short_arg(matcher, &arg_os)?;
+                        needs_val_of = match self.parse_short_arg(matcher, &arg_os) {
+                            Ok(res) => res,
+                            Err(e) => {
+                                errors::collect(all_errors, Err(e), &mut errors)?;
+                                needs_val_of = ParseResult::NotFound;
+                                continue;
+                            }
+                        };
                         // If it's None, we then check if one of those two AppSettings was set
                         debugln!(
                             "Parser:get_matches_with: After parse_short_arg {:?}",
@@ -962,17 +1208,21 @@ where
                                 if !(arg_os.to_string_lossy().parse::<i64>().is_ok()
                                     || arg_os.to_string_lossy().parse::<f64>().is_ok())
                                 {
-                                    return Err(Error::unknown_argument(
+                                    let e = Error::unknown_argument(
                                         &*arg_os.to_string_lossy(),
                                         "",
+                                        None,
                                         &*usage::create_error_usage(self, matcher, None),
                                         self.color(),
-                                    ));
+                                    );
+                                    errors::collect(all_errors, Err(e), &mut errors)?;
+                                    continue;
                                 }
                             }
-                            ParseResult::Opt(..) | ParseResult::Flag | ParseResult::ValuesDone => {
-                                continue
-                            }
+                            ParseResult::Opt(..)
+                            | ParseResult::Flag
+                            | ParseResult::ValuesDone
+                            | ParseResult::Unknown => continue,
                             _ => (),
                         }
                     }
@@ -984,7 +1234,8 @@ where
                             .find(|o| o.b.name == name)
                             .expect(INTERNAL_ERROR_MSG);
                         // get the OptBuilder so we can check the settings
-                        needs_val_of = self.add_val_to_arg(arg, &arg_os, matcher)?;
+                        needs_val_of =
+                            self.add_val_to_arg(arg, &arg_os, &ValueSource::CommandLine, matcher)?;
                         // get the next value from the iterator
                         continue;
                     }
@@ -994,16 +1245,18 @@ where
             if !(self.is_set(AS::ArgsNegateSubcommands) && self.is_set(AS::ValidArgFound))
                 && !self.is_set(AS::InferSubcommands)
             {
-                if let Some(cdate) =
-                    suggestions::did_you_mean(&*arg_os.to_string_lossy(), sc_names!(self))
-                {
-                    return Err(Error::invalid_subcommand(
+                let plugins = self.plugins();
+                let names = sc_names!(self).chain(plugins.iter().map(|p| &*p.0));
+                if let Some(cdate) = suggestions::did_you_mean(&arg_os.to_string_lossy(), names) {
+                    let e = Error::invalid_subcommand(
                         arg_os.to_string_lossy().into_owned(),
                         cdate,
                         self.meta.bin_name.as_ref().unwrap_or(&self.meta.name),
                         &*usage::create_error_usage(self, matcher, None),
                         self.color(),
-                    ));
+                    );
+                    errors::collect(all_errors, Err(e), &mut errors)?;
+                    continue;
                 }
             }
 
@@ -1051,12 +1304,15 @@ where
             }
             if let Some(p) = self.positionals.get(pos_counter) {
                 if p.is_set(ArgSettings::Last) && !self.is_set(AS::TrailingValues) {
-                    return Err(Error::unknown_argument(
+                    let e = Error::unknown_argument(
                         &*arg_os.to_string_lossy(),
                         "",
+                        None,
                         &*usage::create_error_usage(self, matcher, None),
                         self.color(),
-                    ));
+                    );
+                    errors::collect(all_errors, Err(e), &mut errors)?;
+                    continue;
                 }
                 if !self.is_set(AS::TrailingValues)
                     && (self.is_set(AS::TrailingVarArg) && pos_counter == self.positionals.len())
@@ -1076,9 +1332,9 @@ where
                     }
                     self.cache = Some(p.b.name);
                 }
-                let _ = self.add_val_to_arg(p, &arg_os, matcher)?;
+                let _ = self.add_val_to_arg(p, &arg_os, &ValueSource::CommandLine, matcher)?;
 
-                matcher.inc_occurrence_of(p.b.name);
+                matcher.inc_occurrence_of(p.b.name, None);
                 let _ = self.groups_for_arg(p.b.name)
                     .and_then(|vec| Some(matcher.inc_occurrences_of(&*vec)));
 
@@ -1102,65 +1358,60 @@ where
                         arg_os.to_string_lossy().into_owned()
                     }
                 };
-
-                // Collect the external subcommand args
-                let mut sc_m = ArgMatcher::new();
-                while let Some(v) = it.next() {
-                    let a = v.into();
-                    if a.to_str().is_none() && !self.is_set(AS::StrictUtf8) {
-                        return Err(Error::invalid_utf8(
-                            &*usage::create_error_usage(self, matcher, None),
-                            self.color(),
-                        ));
-                    }
-                    sc_m.add_val_to("", &a);
-                }
-
-                matcher.subcommand(SubCommand {
-                    name: sc_name,
-                    matches: sc_m.into(),
-                });
+                self.parse_external_subcommand(sc_name, matcher, it)?;
+            } else if let Some(ref mut unknown) = self.unknown {
+                debugln!("Parser::get_matches_with: passing on unknown argument {:?}", arg_os);
+                unknown.push(arg_os);
             } else if !((self.is_set(AS::AllowLeadingHyphen)
                 || self.is_set(AS::AllowNegativeNumbers))
                 && arg_os.starts_with(b"-"))
                 && !self.is_set(AS::InferSubcommands)
             {
-                return Err(Error::unknown_argument(
+                let e = Error::unknown_argument(
                     &*arg_os.to_string_lossy(),
                     "",
+                    None,
                     &*usage::create_error_usage(self, matcher, None),
                     self.color(),
-                ));
+                );
+                errors::collect(all_errors, Err(e), &mut errors)?;
             } else if !has_args || self.is_set(AS::InferSubcommands) && self.has_subcommands() {
-                if let Some(cdate) =
-                    suggestions::did_you_mean(&*arg_os.to_string_lossy(), sc_names!(self))
-                {
-                    return Err(Error::invalid_subcommand(
+                let plugins = self.plugins();
+                let names = sc_names!(self).chain(plugins.iter().map(|p| &*p.0));
+                if let Some(cdate) = suggestions::did_you_mean(&arg_os.to_string_lossy(), names) {
+                    let e = Error::invalid_subcommand(
                         arg_os.to_string_lossy().into_owned(),
                         cdate,
                         self.meta.bin_name.as_ref().unwrap_or(&self.meta.name),
                         &*usage::create_error_usage(self, matcher, None),
                         self.color(),
-                    ));
+                    );
+                    errors::collect(all_errors, Err(e), &mut errors)?;
                 } else {
-                    return Err(Error::unrecognized_subcommand(
+                    let e = Error::unrecognized_subcommand(
                         arg_os.to_string_lossy().into_owned(),
                         self.meta.bin_name.as_ref().unwrap_or(&self.meta.name),
                         self.color(),
-                    ));
+                    );
+                    errors::collect(all_errors, Err(e), &mut errors)?;
                 }
             } else {
-                return Err(Error::unknown_argument(
+                let e = Error::unknown_argument(
                     &*arg_os.to_string_lossy(),
                     "",
+                    None,
                     &*usage::create_error_usage(self, matcher, None),
                     self.color(),
-                ));
+                );
+                errors::collect(all_errors, Err(e), &mut errors)?;
             }
         }
 
-        if let Some(ref pos_sc_name) = subcmd_name {
-            let sc_name = {
+        if let (Some(sc_name), Some(path)) = (subcmd_name.clone(), plugin_path) {
+            self.parse_external_subcommand(sc_name, matcher, it)?;
+            matcher.0.plugin = Some(path);
+        } else if let Some(ref pos_sc_name) = subcmd_name {
+            let mut sc_name = {
                 find_subcmd!(self, pos_sc_name)
                     .expect(INTERNAL_ERROR_MSG)
                     .p
@@ -1168,11 +1419,25 @@ where
                     .name
                     .clone()
             };
-            self.parse_subcommand(&*sc_name, matcher, it)?;
+            loop {
+                match self.parse_subcommand(&sc_name, matcher, it) {
+                    Ok(Some(next)) => sc_name = next,
+                    Ok(None) => break,
+                    Err(e) => {
+                        errors::collect(all_errors, Err(e), &mut errors)?;
+                        break;
+                    }
+                }
+            }
         } else if self.is_set(AS::SubcommandRequired) {
             let bn = self.meta.bin_name.as_ref().unwrap_or(&self.meta.name);
             return Err(Error::missing_subcommand(
                 bn,
+                self.subcommands
+                    .iter()
+                    .filter(|sc| !sc.p.is_set(AS::Hidden))
+                    .map(|sc| sc.p.meta.name.clone())
+                    .collect(),
                 &usage::create_error_usage(self, matcher, None),
                 self.color(),
             ));
@@ -1180,11 +1445,10 @@ where
             debugln!("Parser::get_matches_with: SubcommandRequiredElseHelp=true");
             let mut out = vec![];
             self.write_help_err(&mut out)?;
-            return Err(Error {
-                message: String::from_utf8_lossy(&*out).into_owned(),
-                kind: ErrorKind::MissingArgumentOrSubcommand,
-                info: None,
-            });
+            return Err(Error::displayed(
+                String::from_utf8_lossy(&out).into_owned(),
+                ErrorKind::MissingArgumentOrSubcommand,
+            ));
         }
 
         // In case the last arg was new, we  need to process it's overrides
@@ -1201,7 +1465,7 @@ where
 
         self.remove_overrides(matcher);
 
-        Validator::new(self).validate(needs_val_of, subcmd_name, matcher)
+        Validator::new(self).validate(needs_val_of, subcmd_name, matcher, errors)
     }
 
     fn remove_overrides(&mut self, matcher: &mut ArgMatcher) {
@@ -1232,7 +1496,7 @@ where
         }
     }
 
-    fn propagate_help_version(&mut self) {
+    pub fn propagate_help_version(&mut self) {
         debugln!("Parser::propagate_help_version;");
         self.create_help_and_version();
         for sc in &mut self.subcommands {
@@ -1276,12 +1540,73 @@ where
         }
     }
 
+    // Collects the rest of the arguments as those of an external subcommand, or a plugin
+    fn parse_external_subcommand<I, T>(
+        &self,
+        sc_name: String,
+        matcher: &mut ArgMatcher<'a>,
+        it: &mut Peekable<I>,
+    ) -> ClapResult<()>
+    where
+        I: Iterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        debugln!("Parser::parse_external_subcommand: sc={}", sc_name);
+        let mut sc_m = ArgMatcher::new();
+        for v in it {
+            let a = v.into();
+            if a.to_str().is_none() && !self.is_set(AS::StrictUtf8) {
+                return Err(Error::invalid_utf8(
+                    &*usage::create_error_usage(self, matcher, None),
+                    self.color(),
+                ));
+            }
+            sc_m.add_val_to("", &a, &ValueSource::CommandLine);
+        }
+
+        matcher.subcommand(SubCommand {
+            name: sc_name,
+            matches: sc_m.into(),
+        });
+        Ok(())
+    }
+
+    // The executables named `<bin>-<plugin>` found in the plugin directories, or `PATH`, when
+    // plugins are enabled. `<bin>` comes from the binary name the user ran rather than the name
+    // of the App, so a `tool` binary built from `App::new("Tool Suite")` finds `tool-lint`. Those
+    // shadowed by a subcommand are left out.
+    pub fn plugins(&self) -> Vec<(String, PathBuf)> {
+        if !self.is_set(AS::PluginSubcommands) {
+            return vec![];
+        }
+        if let Some(ref found) = *self.plugins.borrow() {
+            return found.clone();
+        }
+        let prefix = plugins::prefix(self.meta.bin_name.as_ref().unwrap_or(&self.meta.name));
+        let found: Vec<_> = plugins::discover(&prefix, &self.plugin_dirs)
+            .into_iter()
+            .filter(|p| find_subcmd!(self, p.0.as_str()).is_none())
+            .collect();
+        *self.plugins.borrow_mut() = Some(found.clone());
+        found
+    }
+
+    fn find_plugin(&self, arg_os: &OsStr) -> Option<(String, PathBuf)> {
+        if self.is_set(AS::ArgsNegateSubcommands) && self.is_set(AS::ValidArgFound) {
+            return None;
+        }
+        self.plugins()
+            .into_iter()
+            .find(|p| arg_os == OsStr::new(&p.0))
+    }
+
+    // Returns the name of the next subcommand when chaining them
     fn parse_subcommand<I, T>(
         &mut self,
         sc_name: &str,
         matcher: &mut ArgMatcher<'a>,
         it: &mut Peekable<I>,
-    ) -> ClapResult<()>
+    ) -> ClapResult<Option<String>>
     where
         I: Iterator<Item = T>,
         T: Into<OsString> + Clone,
@@ -1301,6 +1626,12 @@ where
             }
         }
         mid_string.push_str(" ");
+        let siblings = if self.is_set(AS::ChainSubcommands) {
+            self.sibling_names()
+        } else {
+            vec![]
+        };
+        let mut next = None;
         if let Some(ref mut sc) = self.subcommands
             .iter_mut()
             .find(|s| s.p.meta.name == sc_name)
@@ -1333,7 +1664,83 @@ where
                 sc.p.meta.name
             );
             debugln!("Parser::parse_subcommand: sc settings={:#?}", sc.p.settings);
+            sc.p.unknown = self.unknown.as_ref().map(|_| vec![]);
+            sc.p.siblings = siblings;
+            let res = sc.p.get_matches_with(&mut sc_matcher, it);
+            sc.p.siblings.clear();
+            next = sc.p.chained_to.take();
+            res?;
+            if let (Some(unknown), Some(sc_unknown)) = (self.unknown.as_mut(), sc.p.unknown.take()) {
+                unknown.extend(sc_unknown);
+            }
+            matcher.subcommand(SubCommand {
+                name: sc.p.meta.name.clone(),
+                matches: sc_matcher.into(),
+            });
+        }
+        Ok(next.map(|n| {
+            find_subcmd!(self, n)
+                .expect(INTERNAL_ERROR_MSG)
+                .p
+                .meta
+                .name
+                .clone()
+        }))
+    }
+
+    // The names and aliases a subcommand can be chained to, leaving out `help` which would
+    // only display the help of the parent
+    fn sibling_names(&self) -> Vec<String> {
+        let mut names = vec![];
+        for sc in &self.subcommands {
+            if sc.p.meta.name == "help" && self.is_set(AS::NeedsSubcommandHelp) {
+                continue;
+            }
+            names.push(sc.p.meta.name.clone());
+            if let Some(ref als) = sc.p.meta.aliases {
+                names.extend(als.iter().map(|&(a, _)| a.to_owned()));
+            }
+        }
+        names
+    }
+
+    // Finds the subcommand a multicall binary invoked as `bin` runs, if any
+    pub fn applet(&self, bin: &str) -> Option<String> {
+        debugln!("Parser::applet: bin={}", bin);
+        let stem = if !EXE_SUFFIX.is_empty() && bin.ends_with(EXE_SUFFIX) {
+            &bin[..bin.len() - EXE_SUFFIX.len()]
+        } else {
+            bin
+        };
+        find_subcmd!(self, stem).map(|sc| sc.p.meta.name.clone())
+    }
+
+    // Parses all arguments as the given subcommand, which the user invoked directly under the
+    // name `bin`, so the parent's own arguments and requirements don't apply
+    pub fn parse_applet<I, T>(
+        &mut self,
+        sc_name: &str,
+        bin: &str,
+        matcher: &mut ArgMatcher<'a>,
+        it: &mut Peekable<I>,
+    ) -> ClapResult<()>
+    where
+        I: Iterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        debugln!("Parser::parse_applet: sc={}, bin={}", sc_name, bin);
+        if let Some(ref mut sc) = self.subcommands
+            .iter_mut()
+            .find(|s| s.p.meta.name == sc_name)
+        {
+            let mut sc_matcher = ArgMatcher::new();
+            sc.p.meta.usage = Some(bin.to_owned());
+            sc.p.meta.bin_name = Some(bin.to_owned());
+            sc.p.unknown = self.unknown.as_ref().map(|_| vec![]);
             sc.p.get_matches_with(&mut sc_matcher, it)?;
+            if let (Some(unknown), Some(sc_unknown)) = (self.unknown.as_mut(), sc.p.unknown.take()) {
+                unknown.extend(sc_unknown);
+            }
             matcher.subcommand(SubCommand {
                 name: sc.p.meta.name.clone(),
                 matches: sc_matcher.into(),
@@ -1342,6 +1749,23 @@ where
         Ok(())
     }
 
+    // A copy of the given subcommand as it's seen when a multicall binary is invoked under its
+    // name, i.e. as a top level command
+    fn applet_parser(sc: &App<'a, 'b>) -> Parser<'a, 'b> {
+        let mut p = sc.p.clone();
+        p.meta.bin_name = Some(sc.p.meta.name.clone());
+        p.clear_bin_names();
+        p.build_bin_names();
+        p
+    }
+
+    fn clear_bin_names(&mut self) {
+        for sc in &mut self.subcommands {
+            sc.p.meta.bin_name = None;
+            sc.p.clear_bin_names();
+        }
+    }
+
     pub fn groups_for_arg(&self, name: &str) -> Option<Vec<&'a str>> {
         debugln!("Parser::groups_for_arg: name={}", name);
 
@@ -1394,6 +1818,22 @@ where
         args.iter().map(ToOwned::to_owned).collect()
     }
 
+    // How a group is shown in usage strings, e.g. `<--a|--b|--c>{2,3}`
+    pub fn group_usage(&self, group: &str) -> String {
+        let g = self.groups
+            .iter()
+            .find(|g| g.name == group)
+            .expect(INTERNAL_ERROR_MSG);
+        let bounds = match (g.min_args, g.max_args) {
+            (Some(min), Some(max)) if min == max => format!("{{{}}}", min),
+            (Some(min), Some(max)) => format!("{{{},{}}}", min, max),
+            (Some(min), None) => format!("{{{},}}", min),
+            (None, Some(max)) => format!("{{,{}}}", max),
+            (None, None) => String::new(),
+        };
+        format!("<{}>{}", self.args_in_group(group).join("|"), bounds)
+    }
+
     pub fn arg_names_in_group(&self, group: &str) -> Vec<&'a str> {
         let mut g_vec = vec![];
         let mut args = vec![];
@@ -1415,6 +1855,22 @@ where
         args.iter().map(|s| *s).collect()
     }
 
+    // Handles `--complete <CWORD> -- <WORDS>...`, anything unexpected simply yields no candidates
+    fn complete<I: Iterator<Item = OsString>>(&mut self, mut it: I) -> Error {
+        debugln!("Parser::complete;");
+        self.propagate_help_version();
+        let cword = it.next()
+            .and_then(|c| c.to_str().and_then(|c| c.parse::<usize>().ok()));
+        let words: Vec<String> = it.skip_while(|w| w != "--")
+            .skip(1)
+            .map(|w| w.to_string_lossy().into_owned())
+            .collect();
+        Error::displayed(
+            cword.map_or(String::new(), |c| completions::complete(self, c, &words)),
+            ErrorKind::CompletionsDisplayed,
+        )
+    }
+
     pub fn create_help_and_version(&mut self) {
         debugln!("Parser::create_help_and_version;");
         // name is "hclap_help" because flags are sorted by name
@@ -1526,11 +1982,10 @@ where
         let mut buf = vec![];
         match Help::write_parser_help(&mut buf, self, use_long) {
             Err(e) => e,
-            _ => Error {
-                message: String::from_utf8(buf).unwrap_or_default(),
-                kind: ErrorKind::HelpDisplayed,
-                info: None,
-            },
+            _ => Error::displayed(
+                String::from_utf8(buf).unwrap_or_default(),
+                ErrorKind::HelpDisplayed,
+            ),
         }
     }
 
@@ -1540,11 +1995,7 @@ where
         let mut buf_w = BufWriter::new(out.lock());
         match self.print_version(&mut buf_w, use_long) {
             Err(e) => e,
-            _ => Error {
-                message: String::new(),
-                kind: ErrorKind::VersionDisplayed,
-                info: None,
-            },
+            _ => Error::displayed(String::new(), ErrorKind::VersionDisplayed),
         }
     }
 
@@ -1571,6 +2022,20 @@ where
             full_arg.trim_left_matches(b'-')
         };
 
+        if let Some(name) = self.negated_flag(arg) {
+            debugln!("Parser::parse_long_arg: Found valid negated flag '{}'", name);
+            self.settings.set(AS::ValidArgFound);
+            let groups = self.groups_for_arg(name).unwrap_or_default();
+            matcher.negate(name, &groups);
+            return Ok(ParseResult::Flag);
+        }
+
+        let arg = if self.is_set(AS::InferLongArgs) {
+            self.infer_long_arg(arg, matcher)?
+        } else {
+            arg
+        };
+
         if let Some(opt) = find_opt_by_long!(@os self, arg) {
             debugln!(
                 "Parser::parse_long_arg: Found valid opt '{}'",
@@ -1608,10 +2073,137 @@ where
         }
 
         debugln!("Parser::parse_long_arg: Didn't match anything");
+        if let Some(ref mut unknown) = self.unknown {
+            unknown.push(full_arg.to_owned());
+            return Ok(if val.is_some() {
+                ParseResult::ValuesDone
+            } else {
+                ParseResult::Unknown
+            });
+        }
         self.did_you_mean_error(arg.to_str().expect(INVALID_UTF8), matcher)
             .map(|_| ParseResult::NotFound)
     }
 
+    // The name of the negatable flag turned off by `arg`, i.e. `no-` followed by the flag's long or
+    // one of its aliases, unless another argument is actually called that. With
+    // `AppSettings::InferLongArgs` the long may be abbreviated as well, as long as only one
+    // negatable flag starts with it and `arg` doesn't abbreviate another argument itself
+    fn negated_flag(&self, arg: &OsStr) -> Option<&'a str> {
+        let (arg, long) = match arg.to_str() {
+            Some(a) if a.starts_with("no-") => (a, &a[3..]),
+            _ => return None,
+        };
+        if find_opt_by_long!(self, arg).is_some() || find_flag_by_long!(self, arg).is_some() {
+            return None;
+        }
+        let negatable = |l: &str| {
+            find_flag_by_long!(self, l).and_then(|f| if f.is_set(ArgSettings::Negatable) {
+                Some(f.b.name)
+            } else {
+                None
+            })
+        };
+        if let Some(name) = negatable(long) {
+            return Some(name);
+        }
+        if !self.is_set(AS::InferLongArgs) || !self.long_candidates(arg).is_empty() {
+            return None;
+        }
+        let names: Vec<_> = self.long_candidates(long)
+            .into_iter()
+            .filter_map(&negatable)
+            .collect();
+        if names.len() == 1 {
+            Some(names[0])
+        } else {
+            None
+        }
+    }
+
+    // Resolves `arg` to the long it abbreviates, if any, leaving it as is otherwise so it gets
+    // reported as unknown by the caller
+    fn infer_long_arg<'s>(
+        &self,
+        arg: &'s OsStr,
+        matcher: &mut ArgMatcher<'a>,
+    ) -> ClapResult<&'s OsStr>
+    where
+        'b: 's,
+    {
+        let longs = match arg.to_str() {
+            Some(a) if !a.is_empty() => self.long_candidates(a),
+            _ => return Ok(arg),
+        };
+        debugln!("Parser::infer_long_arg: candidates={:?}", longs);
+        match longs.len() {
+            0 => Ok(arg),
+            1 => Ok(OsStr::new(longs[0])),
+            _ => Err(Error::ambiguous_argument(
+                format!("--{}", arg.to_string_lossy()),
+                &longs,
+                &*usage::create_error_usage(self, matcher, None),
+                self.color(),
+            )),
+        }
+    }
+
+    // The longs of the flags and options `arg` may stand for once abbreviations are allowed. A
+    // long or alias matching `arg` exactly is the only candidate, otherwise every long with
+    // itself or one of its aliases starting with `arg` is.
+    pub fn long_candidates(&self, arg: &str) -> Vec<&'b str> {
+        let switches = self.flags
+            .iter()
+            .map(|f| &f.s)
+            .chain(self.opts.iter().map(|o| &o.s));
+        let mut longs = vec![];
+        for s in switches {
+            let long = match s.long {
+                Some(l) => l,
+                None => continue,
+            };
+            let mut names = vec![long];
+            if let Some(ref aliases) = s.aliases {
+                names.extend(aliases.iter().map(|&(a, _)| a));
+            }
+            if names.contains(&arg) {
+                return vec![long];
+            }
+            if names.iter().any(|n| n.starts_with(arg)) {
+                longs.push(long);
+            }
+        }
+        longs
+    }
+
+    // Every abbreviation of `long` and its aliases which only stands for `long`, shortest first
+    pub fn long_abbreviations(&self, long: &str) -> Vec<String> {
+        let mut names = vec![long];
+        let mut switches = self.flags
+            .iter()
+            .map(|f| &f.s)
+            .chain(self.opts.iter().map(|o| &o.s));
+        if let Some(s) = switches.find(|s| s.long == Some(long)) {
+            if let Some(ref aliases) = s.aliases {
+                names.extend(aliases.iter().map(|&(a, _)| a));
+            }
+        }
+        let mut abbrevs: Vec<String> = vec![];
+        for n in &names {
+            for (i, _) in n.char_indices().skip(1) {
+                let abbrev = &n[..i];
+                if !names.contains(&abbrev)
+                    && !abbrevs.iter().any(|a| a == abbrev)
+                    && self.long_candidates(abbrev) == [long]
+                {
+                    abbrevs.push(abbrev.to_owned());
+                }
+            }
+        }
+        abbrevs.sort_by_key(|a| a.len());
+        abbrevs
+    }
+
     #[cfg_attr(feature = "lints", allow(len_zero))]
     fn parse_short_arg(
         &mut self,
@@ -1640,7 +2232,7 @@ where
         }
 
         let mut ret = ParseResult::NotFound;
-        for c in arg.chars() {
+        for (pos, c) in arg.char_indices() {
             debugln!("Parser::parse_short_arg:iter:{}", c);
 
             // update each index because `-abcd` is four indices to clap
@@ -1694,11 +2286,29 @@ where
                 if self.cache.map_or(true, |name| name != flag.b.name) {
                     self.cache = Some(flag.b.name);
                 }
+            } else if let Some(ref mut unknown) = self.unknown {
+                // The rest of the group is passed on as is since it may be the unknown short's
+                // value, or other shorts of it
+                let rest = if pos == 0 || arg_os.to_str().is_none() {
+                    full_arg.to_owned()
+                } else {
+                    let mut rest = OsString::from("-");
+                    rest.push(arg_os.split_at(pos).1);
+                    rest
+                };
+                let done = rest.len() > 1 + c.len_utf8();
+                unknown.push(rest);
+                return Ok(if done {
+                    ParseResult::ValuesDone
+                } else {
+                    ParseResult::Unknown
+                });
             } else {
                 let arg = format!("-{}", c);
                 return Err(Error::unknown_argument(
                     &*arg,
                     "",
+                    None,
                     &*usage::create_error_usage(self, matcher, None),
                     self.color(),
                 ));
@@ -1740,7 +2350,7 @@ where
                 fv,
                 fv.starts_with(&[b'='])
             );
-            self.add_val_to_arg(opt, v, matcher)?;
+            self.add_val_to_arg(opt, v, &ValueSource::CommandLine, matcher)?;
         } else if needs_eq && !(empty_vals || min_vals_zero) {
             sdebugln!("None, but requires equals...Error");
             return Err(Error::empty_value(
@@ -1752,7 +2362,7 @@ where
             sdebugln!("None");
         }
 
-        matcher.inc_occurrence_of(opt.b.name);
+        matcher.inc_occurrence_of(opt.b.name, None);
         // Increment or create the group "args"
         self.groups_for_arg(opt.b.name)
             .and_then(|vec| Some(matcher.inc_occurrences_of(&*vec)));
@@ -1774,6 +2384,7 @@ where
         &self,
         arg: &A,
         val: &OsStr,
+        source: &ValueSource,
         matcher: &mut ArgMatcher<'a>,
     ) -> ClapResult<ParseResult<'a>>
     where
@@ -1788,11 +2399,11 @@ where
         if !(self.is_set(AS::TrailingValues) && self.is_set(AS::DontDelimitTrailingValues)) {
             if let Some(delim) = arg.val_delim() {
                 if val.is_empty_() {
-                    Ok(self.add_single_val_to_arg(arg, val, matcher)?)
+                    Ok(self.add_single_val_to_arg(arg, val, source, matcher)?)
                 } else {
                     let mut iret = ParseResult::ValuesDone;
                     for v in val.split(delim as u32 as u8) {
-                        iret = self.add_single_val_to_arg(arg, v, matcher)?;
+                        iret = self.add_single_val_to_arg(arg, v, source, matcher)?;
                     }
                     // If there was a delimiter used, we're not looking for more values
                     if val.contains_byte(delim as u32 as u8)
@@ -1803,10 +2414,10 @@ where
                     Ok(iret)
                 }
             } else {
-                self.add_single_val_to_arg(arg, val, matcher)
+                self.add_single_val_to_arg(arg, val, source, matcher)
             }
         } else {
-            self.add_single_val_to_arg(arg, val, matcher)
+            self.add_single_val_to_arg(arg, val, source, matcher)
         }
     }
 
@@ -1814,6 +2425,7 @@ where
         &self,
         arg: &A,
         v: &OsStr,
+        source: &ValueSource,
         matcher: &mut ArgMatcher<'a>,
     ) -> ClapResult<ParseResult<'a>>
     where
@@ -1833,13 +2445,13 @@ where
             }
         }
 
-        matcher.add_val_to(arg.name(), v);
+        matcher.add_val_to(arg.name(), v, source);
         matcher.add_index_to(arg.name(), self.cur_idx.get());
 
         // Increment or create the group "args"
         if let Some(grps) = self.groups_for_arg(arg.name()) {
             for grp in grps {
-                matcher.add_val_to(&*grp, v);
+                matcher.add_val_to(grp, v, source);
             }
         }
 
@@ -1856,7 +2468,7 @@ where
     ) -> ClapResult<ParseResult<'a>> {
         debugln!("Parser::parse_flag;");
 
-        matcher.inc_occurrence_of(flag.b.name);
+        matcher.inc_occurrence_of(flag.b.name, flag.b.max_occurs);
         matcher.add_index_to(flag.b.name, self.cur_idx.get());
 
         // Increment or create the group "args"
@@ -1870,6 +2482,15 @@ where
         // Didn't match a flag or option
         let suffix = suggestions::did_you_mean_flag_suffix(arg, longs!(self), &self.subcommands);
 
+        // Parsing goes on when all errors are reported, so the suggestion mustn't end up matched
+        let mut usage_matcher;
+        let matcher = if self.is_set(AS::ReportAllErrors) {
+            usage_matcher = ArgMatcher(matcher.0.clone());
+            &mut usage_matcher
+        } else {
+            matcher
+        };
+
         // Add the arg to the matches to build a proper usage string
         if let Some(name) = suffix.1 {
             if let Some(opt) = find_opt_by_long!(self, name) {
@@ -1887,6 +2508,7 @@ where
         Err(Error::unknown_argument(
             &*used_arg,
             &*suffix.0,
+            suffix.1.map(|n| format!("--{}", n)),
             &*usage::create_error_usage(self, matcher, None),
             self.color(),
         ))
@@ -1940,13 +2562,14 @@ where
 
     pub fn add_defaults(&mut self, matcher: &mut ArgMatcher<'a>) -> ClapResult<()> {
         debugln!("Parser::add_defaults;");
+        use args::ValueSource::DefaultValue;
         macro_rules! add_val {
             (@default $_self:ident, $a:ident, $m:ident) => {
                 if let Some(ref val) = $a.v.default_val {
                     debugln!("Parser::add_defaults:iter:{}: has default vals", $a.b.name);
                     if $m.get($a.b.name).map(|ma| ma.vals.len()).map(|len| len == 0).unwrap_or(false) {
                         debugln!("Parser::add_defaults:iter:{}: has no user defined vals", $a.b.name);
-                        $_self.add_val_to_arg($a, OsStr::new(val), $m)?;
+                        $_self.add_val_to_arg($a, OsStr::new(val), &DefaultValue, $m)?;
 
                         if $_self.cache.map_or(true, |name| name != $a.name()) {
                             $_self.cache = Some($a.name());
@@ -1956,7 +2579,7 @@ where
                     } else {
                         debugln!("Parser::add_defaults:iter:{}: wasn't used", $a.b.name);
 
-                        $_self.add_val_to_arg($a, OsStr::new(val), $m)?;
+                        $_self.add_val_to_arg($a, OsStr::new(val), &DefaultValue, $m)?;
 
                         if $_self.cache.map_or(true, |name| name != $a.name()) {
                             $_self.cache = Some($a.name());
@@ -1982,7 +2605,7 @@ where
                                 false
                             };
                             if add {
-                                $_self.add_val_to_arg($a, OsStr::new(default), $m)?;
+                                $_self.add_val_to_arg($a, OsStr::new(default), &DefaultValue, $m)?;
                                 if $_self.cache.map_or(true, |name| name != $a.name()) {
                                     $_self.cache = Some($a.name());
                                 }
@@ -2016,22 +2639,18 @@ where
     pub fn add_env(&mut self, matcher: &mut ArgMatcher<'a>) -> ClapResult<()> {
         macro_rules! add_val {
             ($_self:ident, $a:ident, $m:ident) => {
-                if let Some(ref val) = $a.v.env {
-                    if $m.get($a.b.name).map(|ma| ma.vals.len()).map(|len| len == 0).unwrap_or(false) {
-                        if let Some(ref val) = val.1 {
-                            $_self.add_val_to_arg($a, OsStr::new(val), $m)?;
-
-                            if $_self.cache.map_or(true, |name| name != $a.name()) {
-                                $_self.cache = Some($a.name());
-                            }
+                if $m.get($a.b.name).map_or(true, |ma| ma.vals.is_empty()) {
+                    let vals = match $a.v.env {
+                        Some((_, Some(ref val))) => Some((vec![val.clone()], ValueSource::EnvVariable)),
+                        _ => $_self.provided_vals($a.b.name),
+                    };
+                    if let Some((vals, source)) = vals {
+                        for val in &vals {
+                            $_self.add_val_to_arg($a, val, &source, $m)?;
                         }
-                    } else {
-                        if let Some(ref val) = val.1 {
-                            $_self.add_val_to_arg($a, OsStr::new(val), $m)?;
 
-                            if $_self.cache.map_or(true, |name| name != $a.name()) {
-                                $_self.cache = Some($a.name());
-                            }
+                        if $_self.cache.map_or(true, |name| name != $a.name()) {
+                            $_self.cache = Some($a.name());
                         }
                     }
                 }
@@ -2047,6 +2666,16 @@ where
         Ok(())
     }
 
+    // The values of the first value provider which has some for `arg`
+    fn provided_vals(&self, arg: &str) -> Option<(Vec<OsString>, ValueSource)> {
+        let cmd: Vec<&str> = self.sc_path.iter().map(|s| &**s).collect();
+        self.providers.iter().filter_map(|vp| {
+            debugln!("Parser::provided_vals: arg={}, provider={}", arg, vp.name());
+            vp.values(&cmd, arg)
+                .map(|vals| (vals, ValueSource::Provider(vp.name().to_owned())))
+        }).next()
+    }
+
     pub fn flags(&self) -> Iter<FlagBuilder<'a, 'b>> {
         self.flags.iter()
     }
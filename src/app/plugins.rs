@@ -0,0 +1,80 @@
+// Std
+use std::env;
+use std::env::consts::EXE_SUFFIX;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Finds the executables named `<prefix><name>` in `dirs`, or in those of `PATH` if there are
+// none, as `(name, path)` pairs sorted by name. Like a shell would, the first directory with an
+// executable of a given name wins.
+pub fn discover(prefix: &str, dirs: &[PathBuf]) -> Vec<(String, PathBuf)> {
+    debugln!("plugins::discover: prefix={}", prefix);
+    let path_dirs: Vec<PathBuf> = if dirs.is_empty() {
+        env::var_os("PATH")
+            .map(|p| env::split_paths(&p).collect())
+            .unwrap_or_default()
+    } else {
+        dirs.to_vec()
+    };
+    let mut found: Vec<(String, PathBuf)> = vec![];
+    for dir in &path_dirs {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let file_name = entry.file_name();
+            let name = match file_name.to_str().and_then(|f| plugin_name(prefix, f)) {
+                Some(name) => name,
+                None => continue,
+            };
+            let path = entry.path();
+            if !found.iter().any(|p| p.0 == name) && is_executable(&path) {
+                debugln!("plugins::discover: found {} at {:?}", name, path);
+                found.push((name.to_owned(), path));
+            }
+        }
+    }
+    found.sort();
+    found
+}
+
+// The prefix of the plugins of the binary `bin_name`, e.g. `tool-` for `tool`, or `tool-remote-`
+// for the `remote` subcommand of `tool` (`tool remote`), without the executable suffix.
+pub fn prefix(bin_name: &str) -> String {
+    let mut prefix = String::new();
+    for part in bin_name.split(' ') {
+        if !EXE_SUFFIX.is_empty() && part.ends_with(EXE_SUFFIX) {
+            prefix.push_str(&part[..part.len() - EXE_SUFFIX.len()]);
+        } else {
+            prefix.push_str(part);
+        }
+        prefix.push('-');
+    }
+    prefix
+}
+
+// The name of the plugin in `file_name`, if any
+fn plugin_name<'f>(prefix: &str, file_name: &'f str) -> Option<&'f str> {
+    if !file_name.starts_with(prefix) || !file_name.ends_with(EXE_SUFFIX) {
+        return None;
+    }
+    let name = &file_name[prefix.len()..file_name.len() - EXE_SUFFIX.len()];
+    if name.is_empty() || name.starts_with('-') {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::metadata(path)
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool { fs::metadata(path).map(|m| m.is_file()).unwrap_or(false) }
@@ -0,0 +1,184 @@
+// Std
+use std::io::{self, BufRead, Write};
+
+// Internal
+use app::{App, AppSettings};
+use args::ArgMatches;
+use completions;
+use errors::{Error, Result as ClapResult};
+use shell_words;
+
+/// Runs an [`App`] as an interactive shell, such as the admin console of a daemon, where each
+/// line typed by the user is a command line of its own.
+///
+/// Lines are split into arguments the way a POSIX shell would, i.e. arguments are separated by
+/// whitespace and may be quoted with single or double quotes, but nothing else such as variables
+/// or globs is expanded. Since there is no binary name on these lines, the [`App`] is used with
+/// [`AppSettings::NoBinaryName`], and usually has one [`SubCommand`] per command of the shell.
+///
+/// The same [`App`] parses every line, so unlike [`App::get_matches`] nothing ever exits the
+/// process: help and version messages, as well as errors, are returned to the caller like any
+/// other [`Error`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, ErrorKind, Repl, SubCommand};
+/// let mut repl = Repl::new(App::new("console")
+///     .subcommand(SubCommand::with_name("say")
+///         .arg(Arg::with_name("words").multiple(true)))
+///     .subcommand(SubCommand::with_name("quit")));
+///
+/// let m = repl.parse_line("say 'hello world' again").unwrap().unwrap();
+/// let words: Vec<_> = m.subcommand_matches("say").unwrap().values_of("words").unwrap().collect();
+/// assert_eq!(words, ["hello world", "again"]);
+///
+/// // Blank lines don't mean anything
+/// assert!(repl.parse_line("  ").unwrap().is_none());
+///
+/// let err = repl.parse_line("say --help").unwrap_err();
+/// assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+///
+/// assert_eq!(repl.complete("q"), ["quit"]);
+/// ```
+/// [`App`]: ./struct.App.html
+/// [`App::get_matches`]: ./struct.App.html#method.get_matches
+/// [`AppSettings::NoBinaryName`]: ./enum.AppSettings.html#variant.NoBinaryName
+/// [`SubCommand`]: ./struct.SubCommand.html
+/// [`Error`]: ./struct.Error.html
+#[allow(missing_debug_implementations)]
+pub struct Repl<'a, 'b>
+where
+    'a: 'b,
+{
+    app: App<'a, 'b>,
+}
+
+impl<'a, 'b> Repl<'a, 'b> {
+    /// Creates a shell parsing each line with `app`.
+    pub fn new(app: App<'a, 'b>) -> Self {
+        Repl {
+            app: app.setting(AppSettings::NoBinaryName),
+        }
+    }
+
+    /// Parses a single line, returning `None` if it's blank.
+    ///
+    /// A line which can't be split into arguments results in an [`ErrorKind::InvalidLine`] error.
+    /// Just like [`App::get_matches_from_safe`], `--help` and `--version` result in an
+    /// [`ErrorKind::HelpDisplayed`] and [`ErrorKind::VersionDisplayed`] error respectively, whose
+    /// message is what should be shown to the user.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, Repl};
+    /// let mut repl = Repl::new(App::new("console")
+    ///     .arg(Arg::from_usage("<cmd> 'the command to run'")));
+    ///
+    /// let m = repl.parse_line("\"restart server\"").unwrap().unwrap();
+    /// assert_eq!(m.value_of("cmd"), Some("restart server"));
+    /// ```
+    /// [`ErrorKind::InvalidLine`]: ./enum.ErrorKind.html#variant.InvalidLine
+    /// [`ErrorKind::HelpDisplayed`]: ./enum.ErrorKind.html#variant.HelpDisplayed
+    /// [`ErrorKind::VersionDisplayed`]: ./enum.ErrorKind.html#variant.VersionDisplayed
+    /// [`App::get_matches_from_safe`]: ./struct.App.html#method.get_matches_from_safe
+    pub fn parse_line(&mut self, line: &str) -> ClapResult<Option<ArgMatches<'a>>> {
+        debugln!("Repl::parse_line: line={:?}", line);
+        let words = shell_words::split(line)
+            .map_err(|e| Error::invalid_line(line, &e, self.app.p.color()))?;
+        if words.is_empty() {
+            return Ok(None);
+        }
+        self.app.get_matches_from_safe_borrow(words).map(Some)
+    }
+
+    /// Returns the candidates to complete the last word of `line` with, i.e. when the user
+    /// presses `<TAB>` with the cursor at the end of the line. The candidates are the same the
+    /// shell completion scripts generated by [`App::gen_completions`] offer, as well as values
+    /// computed by [`Arg::completer`].
+    ///
+    /// The last word may start with an unclosed quote, which is left out of the candidates.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, Repl, SubCommand};
+    /// let mut repl = Repl::new(App::new("console")
+    ///     .subcommand(SubCommand::with_name("stop")
+    ///         .arg(Arg::from_usage("--force 'stop right away'"))
+    ///         .arg(Arg::from_usage("--format [fmt] 'the output format'"))));
+    ///
+    /// assert_eq!(repl.complete("stop --fo"), ["--force", "--format"]);
+    /// ```
+    /// [`App::gen_completions`]: ./struct.App.html#method.gen_completions
+    /// [`Arg::completer`]: ./struct.Arg.html#method.completer
+    pub fn complete(&mut self, line: &str) -> Vec<String> {
+        debugln!("Repl::complete: line={:?}", line);
+        self.app.p.propagate_once();
+        self.app.p.propagate_help_version();
+        let (prev, cur) = shell_words::split_incomplete(line);
+        completions::candidates(&self.app.p, &prev, &cur)
+            .into_iter()
+            .map(|(c, _)| c)
+            .collect()
+    }
+
+    /// Runs the shell until `input` is exhausted, or `f` returns `false`.
+    ///
+    /// Before reading each line `prompt` is written to `out`, and every line which isn't blank
+    /// is handed to `f` once parsed. Messages of errors, help, and version are written to `out`
+    /// as well, after which the shell carries on with the next line.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Repl, SubCommand};
+    /// let mut repl = Repl::new(App::new("console")
+    ///     .subcommand(SubCommand::with_name("ping"))
+    ///     .subcommand(SubCommand::with_name("quit")));
+    ///
+    /// let mut out = vec![];
+    /// let mut pings = 0;
+    /// repl.run("> ", &b"ping\nbogus\nping\nquit\nping\n"[..], &mut out, |m| {
+    ///     match m.subcommand_name() {
+    ///         Some("ping") => pings += 1,
+    ///         Some("quit") => return false,
+    ///         _ => (),
+    ///     }
+    ///     true
+    /// }).unwrap();
+    ///
+    /// assert_eq!(pings, 2);
+    /// assert!(String::from_utf8(out).unwrap().contains("'bogus' which wasn't expected"));
+    /// ```
+    pub fn run<R, W, F>(
+        &mut self,
+        prompt: &str,
+        mut input: R,
+        out: &mut W,
+        mut f: F,
+    ) -> io::Result<()>
+    where
+        R: BufRead,
+        W: Write,
+        F: FnMut(ArgMatches<'a>) -> bool,
+    {
+        let mut line = String::new();
+        loop {
+            write!(out, "{}", prompt)?;
+            out.flush()?;
+            line.clear();
+            if input.read_line(&mut line)? == 0 {
+                return Ok(());
+            }
+            match self.parse_line(&line) {
+                Ok(Some(m)) => if !f(m) {
+                    return Ok(());
+                },
+                Ok(None) => (),
+                Err(e) => writeln!(out, "{}", e.message)?,
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+// Std
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::PathBuf;
+
+// Internal
+use errors::{Error, Result as ClapResult};
+use fmt::ColorWhen;
+use shell_words;
+
+// Replaces each `@path` argument with the arguments read from the file at `path`, which may
+// include other response files themselves. Arguments following `--` are left alone.
+pub fn expand<I>(args: I, color: ColorWhen) -> ClapResult<Vec<OsString>>
+where
+    I: Iterator<Item = OsString>,
+{
+    debugln!("response::expand;");
+    let mut expanded = vec![];
+    let mut ex = Expander {
+        color: color,
+        trailing: false,
+        files: vec![],
+    };
+    for arg in args {
+        ex.add(arg, &mut expanded)?;
+    }
+    Ok(expanded)
+}
+
+struct Expander {
+    color: ColorWhen,
+    trailing: bool,
+    // The response files being read, to catch those including themselves
+    files: Vec<PathBuf>,
+}
+
+impl Expander {
+    fn add(&mut self, arg: OsString, expanded: &mut Vec<OsString>) -> ClapResult<()> {
+        if self.trailing {
+            expanded.push(arg);
+            return Ok(());
+        }
+        if arg == *"--" {
+            self.trailing = true;
+        }
+        let path = match arg.to_str() {
+            Some(s) if s.len() > 1 && s.starts_with('@') => s[1..].to_owned(),
+            _ => {
+                expanded.push(arg);
+                return Ok(());
+            }
+        };
+        debugln!("Expander::add: reading response file {}", path);
+
+        let err = |reason: String| Error::response_file(&path, &reason, self.color);
+        let canonical = fs::canonicalize(&path).map_err(|e| err(e.to_string()))?;
+        if self.files.contains(&canonical) {
+            return Err(err(String::from("it includes itself")));
+        }
+        let mut contents = String::new();
+        File::open(&canonical)
+            .and_then(|mut f| f.read_to_string(&mut contents))
+            .map_err(|e| err(e.to_string()))?;
+        let words = shell_words::split(&contents).map_err(&err)?;
+
+        self.files.push(canonical);
+        for word in words {
+            self.add(word.into(), expanded)?;
+        }
+        self.files.pop();
+        Ok(())
+    }
+}
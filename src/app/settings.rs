@@ -47,6 +47,13 @@ bitflags! {
         const INFER_SUBCOMMANDS    = 1 << 38;
         const CONTAINS_LAST        = 1 << 39;
         const ARGS_OVERRIDE_SELF   = 1 << 40;
+        const DYNAMIC_COMPLETION   = 1 << 41;
+        const RESPONSE_FILES       = 1 << 42;
+        const INFER_LONG_ARGS      = 1 << 43;
+        const MULTICALL            = 1 << 44;
+        const CHAIN_SUBCOMMANDS    = 1 << 45;
+        const PLUGIN_SUBCOMMANDS   = 1 << 46;
+        const REPORT_ALL_ERRORS    = 1 << 47;
     }
 }
 
@@ -82,6 +89,7 @@ impl AppFlags {
         AllowLeadingHyphen => Flags::LEADING_HYPHEN,
         AllowNegativeNumbers => Flags::ALLOW_NEG_NUMS,
         AllowMissingPositional => Flags::ALLOW_MISSING_POS,
+        ChainSubcommands => Flags::CHAIN_SUBCOMMANDS,
         ColoredHelp => Flags::COLORED_HELP,
         ColorAlways => Flags::COLOR_ALWAYS,
         ColorAuto => Flags::COLOR_AUTO,
@@ -91,15 +99,20 @@ impl AppFlags {
         DeriveDisplayOrder => Flags::DERIVE_DISP_ORDER,
         DisableHelpSubcommand => Flags::DISABLE_HELP_SC,
         DisableVersion => Flags::DISABLE_VERSION,
+        DynamicCompletion => Flags::DYNAMIC_COMPLETION,
         GlobalVersion => Flags::GLOBAL_VERSION,
         HidePossibleValuesInHelp => Flags::NO_POS_VALUES,
         Hidden => Flags::HIDDEN,
         LowIndexMultiplePositional => Flags::LOW_INDEX_MUL_POS,
+        Multicall => Flags::MULTICALL,
         NeedsLongHelp => Flags::NEEDS_LONG_HELP,
         NeedsLongVersion => Flags::NEEDS_LONG_VERSION,
         NeedsSubcommandHelp => Flags::NEEDS_SC_HELP,
         NoBinaryName => Flags::NO_BIN_NAME,
+        PluginSubcommands => Flags::PLUGIN_SUBCOMMANDS,
         PropagateGlobalValuesDown=> Flags::PROPAGATE_VALS_DOWN,
+        ReportAllErrors => Flags::REPORT_ALL_ERRORS,
+        ResponseFiles => Flags::RESPONSE_FILES,
         StrictUtf8 => Flags::UTF8_STRICT,
         SubcommandsNegateReqs => Flags::SC_NEGATE_REQS,
         SubcommandRequired => Flags::SC_REQUIRED,
@@ -113,6 +126,7 @@ impl AppFlags {
         ValidNegNumFound => Flags::VALID_NEG_NUM_FOUND,
         Propagated => Flags::PROPAGATED,
         ValidArgFound => Flags::VALID_ARG_FOUND,
+        InferLongArgs => Flags::INFER_LONG_ARGS,
         InferSubcommands => Flags::INFER_SUBCOMMANDS,
         ContainsLast => Flags::CONTAINS_LAST
     }
@@ -332,6 +346,40 @@ pub enum AppSettings {
     /// [`Arg::default_value`]: ./struct.Arg.html#method.default_value
     ArgRequiredElseHelp,
 
+    /// Allows several sibling [`SubCommand`]s to be used one after the other in a single
+    /// invocation, such as `tool build --release test --quick publish`.
+    ///
+    /// Each subcommand takes the arguments up to the name (or an [alias]) of the next sibling,
+    /// as long as that name isn't the value of an option or a subcommand of its own. Every
+    /// subcommand is then validated on its own, with errors showing its own usage. They are
+    /// accessed in the order they were used with [`ArgMatches::subcommands`], while
+    /// [`ArgMatches::subcommand`] and friends only see the first one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings, SubCommand};
+    /// let m = App::new("tool")
+    ///     .setting(AppSettings::ChainSubcommands)
+    ///     .subcommand(SubCommand::with_name("build")
+    ///         .arg(Arg::with_name("release").long("release")))
+    ///     .subcommand(SubCommand::with_name("test")
+    ///         .arg(Arg::with_name("quick").long("quick")))
+    ///     .subcommand(SubCommand::with_name("publish"))
+    ///     .get_matches_from(vec![
+    ///         "tool", "build", "--release", "test", "--quick", "publish"
+    ///     ]);
+    ///
+    /// let names: Vec<_> = m.subcommands().map(|(name, _)| name).collect();
+    /// assert_eq!(names, ["build", "test", "publish"]);
+    /// assert!(m.subcommand_matches("test").unwrap().is_present("quick"));
+    /// ```
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [alias]: ./struct.App.html#method.alias
+    /// [`ArgMatches::subcommands`]: ./struct.ArgMatches.html#method.subcommands
+    /// [`ArgMatches::subcommand`]: ./struct.ArgMatches.html#method.subcommand
+    ChainSubcommands,
+
     /// Uses colorized help messages.
     ///
     /// **NOTE:** Must be compiled with the `color` cargo feature
@@ -504,6 +552,41 @@ pub enum AppSettings {
     /// [`SubCommand`]: ./struct.SubCommand.html
     DeriveDisplayOrder,
 
+    /// Answers the hidden `--complete` entry point used by the scripts generated with
+    /// [`App::gen_dynamic_completions`] and [`App::gen_dynamic_completions_to`]. Instead of being
+    /// parsed, a command line such as `myprog --complete <CWORD> -- <WORDS>...` prints the
+    /// completion candidates for the word at index `CWORD` of `WORDS` (where `WORDS[0]` is the
+    /// binary name), one per line, with an optional tab separated description.
+    ///
+    /// Candidates are computed from the subcommands, flags, options, [possible values] and
+    /// [`Arg::completer`] functions of the `App`, so completions can depend on runtime state.
+    ///
+    /// **NOTE:** This setting only needs to be set on the top level `App`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings, ErrorKind};
+    /// let res = App::new("myprog")
+    ///     .setting(AppSettings::DynamicCompletion)
+    ///     .arg(Arg::with_name("color")
+    ///         .long("color")
+    ///         .takes_value(true)
+    ///         .possible_values(&["always", "never"]))
+    ///     .get_matches_from_safe(vec![
+    ///         "myprog", "--complete", "2", "--", "myprog", "--color", "al"
+    ///     ]);
+    ///
+    /// let err = res.unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::CompletionsDisplayed);
+    /// assert_eq!(err.message, "always");
+    /// ```
+    /// [`App::gen_dynamic_completions`]: ./struct.App.html#method.gen_dynamic_completions
+    /// [`App::gen_dynamic_completions_to`]: ./struct.App.html#method.gen_dynamic_completions_to
+    /// [possible values]: ./struct.Arg.html#method.possible_values
+    /// [`Arg::completer`]: ./struct.Arg.html#method.completer
+    DynamicCompletion,
+
     /// Specifies to use the version of the current command for all child [`SubCommand`]s.
     /// (Defaults to `false`; subcommands have independent version strings from their parents.)
     ///
@@ -543,6 +626,39 @@ pub enum AppSettings {
     /// This can be useful if there are many values, or they are explained elsewhere.
     HidePossibleValuesInHelp,
 
+    /// Tries to match unknown long arguments to known [`Arg::long`]s or their [aliases] as if
+    /// they were abbreviated, like GNU `getopt_long` does. For example, if `--verbose` and
+    /// `--version` are valid longs, `--verb` is the same as `--verbose`, whereas `--ver` would
+    /// fail with an [`ErrorKind::AmbiguousArgument`] error listing both.
+    ///
+    /// A long which is given in full always wins, even when it is also the beginning of another
+    /// one, i.e. `--color` is never ambiguous with `--colors`.
+    ///
+    /// **NOTE:** The completion scripts generated with [`App::gen_completions`] complete the
+    /// values of options whose long is abbreviated as well, for the shells which support it. The
+    /// abbreviations themselves aren't offered.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings, ErrorKind};
+    /// let app = App::new("prog")
+    ///     .setting(AppSettings::InferLongArgs)
+    ///     .arg(Arg::from_usage("--verbose 'use verbose output'"))
+    ///     .arg(Arg::from_usage("--verify 'verify the results'"));
+    ///
+    /// let m = app.clone().get_matches_from(vec!["prog", "--verb"]);
+    /// assert!(m.is_present("verbose"));
+    ///
+    /// let res = app.get_matches_from_safe(vec!["prog", "--ver"]);
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::AmbiguousArgument);
+    /// ```
+    /// [`Arg::long`]: ./struct.Arg.html#method.long
+    /// [aliases]: ./struct.Arg.html#method.alias
+    /// [`ErrorKind::AmbiguousArgument`]: ./enum.ErrorKind.html#variant.AmbiguousArgument
+    /// [`App::gen_completions`]: ./struct.App.html#method.gen_completions
+    InferLongArgs,
+
     /// Tries to match unknown args to partial [`subcommands`] or their [aliases]. For example to
     /// match a subcommand named `test`, one could use `t`, `te`, `tes`, and `test`.
     ///
@@ -573,6 +689,44 @@ pub enum AppSettings {
     /// [`AppSeettings::ArgsNegateSubcommands`]: ./enum.AppSettings.html#variant.ArgsNegateSubcommands
     InferSubcommands,
 
+    /// Selects the [`SubCommand`] to run from the file name the binary was invoked under, in the
+    /// style of `busybox`. This allows shipping a single binary which is hard linked (or symlinked)
+    /// under the name of each of its subcommands, also called applets.
+    ///
+    /// When the file name of the first argument matches the name or an [alias] of a subcommand, all
+    /// arguments are parsed as if the user had typed that subcommand directly, and the parent's
+    /// arguments and requirements are skipped. Help, usage, and error messages then refer to the
+    /// applet by the name it was invoked under. When invoked under any other name, such as the
+    /// main binary's, subcommands are parsed as usual.
+    ///
+    /// Generating completions with [`App::gen_completions`] writes one extra file for each
+    /// subcommand, completing it as a binary of its own.
+    ///
+    /// **NOTE:** This setting has no effect when combined with [`AppSettings::NoBinaryName`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings, SubCommand};
+    /// let app = App::new("busybox")
+    ///     .setting(AppSettings::Multicall)
+    ///     .subcommand(SubCommand::with_name("true"))
+    ///     .subcommand(SubCommand::with_name("echo")
+    ///         .arg(Arg::with_name("text").multiple(true)));
+    ///
+    /// let m = app.clone().get_matches_from(vec!["/bin/echo", "hi"]);
+    /// assert_eq!(m.subcommand_name(), Some("echo"));
+    /// assert_eq!(m.subcommand_matches("echo").unwrap().value_of("text"), Some("hi"));
+    ///
+    /// let m = app.get_matches_from(vec!["/bin/busybox", "true"]);
+    /// assert_eq!(m.subcommand_name(), Some("true"));
+    /// ```
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [alias]: ./struct.App.html#method.alias
+    /// [`App::gen_completions`]: ./struct.App.html#method.gen_completions
+    /// [`AppSettings::NoBinaryName`]: ./enum.AppSettings.html#variant.NoBinaryName
+    Multicall,
+
     /// Specifies that the parser should not assume the first argument passed is the binary name.
     /// This is normally the case when using a "daemon" style mode, or an interactive CLI where one
     /// one would not normally type the binary or program name for each command.
@@ -603,6 +757,87 @@ pub enum AppSettings {
     /// ```
     NextLineHelp,
 
+    /// Runs executables named `<bin>-<plugin>`, where `<bin>` is the name of the binary being
+    /// run (without `.exe` on Windows), as `git` does with `git-foo`. For a subcommand, `<bin>` is
+    /// the name of the binary and those of the subcommands joined by `-`, like `tool-remote`. Using `<plugin>` like a [`SubCommand`] collects the arguments
+    /// after it the same way as with [`AppSettings::AllowExternalSubcommands`], and
+    /// [`ArgMatches::plugin_path`] returns the executable to run them with.
+    ///
+    /// Plugins are searched for in the directories of the `PATH` environment variable, or those
+    /// given with [`App::plugin_dir`]. They are listed in the help message along with the
+    /// subcommands, and suggested for misspelled subcommands. A plugin with the same name as a
+    /// subcommand is ignored.
+    ///
+    /// **NOTE:** The scripts made by [`App::gen_completions`] only contain the plugins found when
+    /// they are generated, since the shells can't search for them. Plugins installed later are
+    /// only completed with [`AppSettings::DynamicCompletion`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, AppSettings, SubCommand};
+    /// use std::process::Command;
+    ///
+    /// let m = App::new("tool")
+    ///     .setting(AppSettings::PluginSubcommands)
+    ///     .subcommand(SubCommand::with_name("build"))
+    ///     .get_matches();
+    ///
+    /// // With `tool-lint` in the `PATH`, `tool lint --fix` runs `tool-lint --fix`
+    /// if let (plugin, Some(sub_m)) = m.subcommand() {
+    ///     if let Some(path) = m.plugin_path() {
+    ///         let args = sub_m.values_of_os("").into_iter().flat_map(|v| v);
+    ///         Command::new(path).args(args).status().expect(plugin);
+    ///     }
+    /// }
+    /// ```
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [`AppSettings::AllowExternalSubcommands`]: ./enum.AppSettings.html#variant.AllowExternalSubcommands
+    /// [`ArgMatches::plugin_path`]: ./struct.ArgMatches.html#method.plugin_path
+    /// [`App::plugin_dir`]: ./struct.App.html#method.plugin_dir
+    /// [`App::gen_completions`]: ./struct.App.html#method.gen_completions
+    /// [`AppSettings::DynamicCompletion`]: ./enum.AppSettings.html#variant.DynamicCompletion
+    PluginSubcommands,
+
+    /// Keeps parsing and validating past the first problem with the arguments, so that unknown
+    /// arguments, conflicts, missing [required] arguments and invalid values are all reported at
+    /// once. When there is more than one, they result in an [`ErrorKind::MultipleErrors`] error
+    /// whose message lists them above a single usage string, and whose [`Error::errors`] are the
+    /// individual errors in the order they were found.
+    ///
+    /// **NOTE:** This setting only applies to the current [`App`] / [`SubCommand`], use
+    /// [`App::global_setting`] for it to apply to the subcommands too.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, AppSettings, Arg, ErrorKind};
+    /// let res = App::new("myprog")
+    ///     .setting(AppSettings::ReportAllErrors)
+    ///     .arg(Arg::with_name("input").required(true))
+    ///     .arg(Arg::with_name("mode")
+    ///         .long("mode")
+    ///         .takes_value(true)
+    ///         .possible_values(&["fast", "slow"]))
+    ///     .get_matches_from_safe(vec!["myprog", "--mode", "medium", "--colour"]);
+    ///
+    /// let err = res.unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::MultipleErrors);
+    /// let kinds: Vec<_> = err.errors().iter().map(|e| e.kind).collect();
+    /// assert_eq!(kinds, [
+    ///     ErrorKind::UnknownArgument,
+    ///     ErrorKind::MissingRequiredArgument,
+    ///     ErrorKind::InvalidValue,
+    /// ]);
+    /// ```
+    /// [required]: ./struct.Arg.html#method.required
+    /// [`ErrorKind::MultipleErrors`]: ./enum.ErrorKind.html#variant.MultipleErrors
+    /// [`Error::errors`]: ./struct.Error.html#method.errors
+    /// [`App`]: ./struct.App.html
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [`App::global_setting`]: ./struct.App.html#method.global_setting
+    ReportAllErrors,
+
     /// **DEPRECATED**: This setting is no longer required in order to propagate values up or down
     ///
     /// Specifies that the parser should propagate global arg's values down or up through any *used*
@@ -642,6 +877,39 @@ pub enum AppSettings {
     #[deprecated(since = "2.27.0", note = "No longer required to propagate values")]
     PropagateGlobalValuesDown,
 
+    /// Expands arguments of the form `@path` into the arguments read from the file at `path`,
+    /// known as a response file. This lets users get around command line length limits or keep
+    /// long lists of arguments around.
+    ///
+    /// The file is split into arguments the way a POSIX shell would, i.e. arguments are separated
+    /// by whitespace and may be quoted with single or double quotes, but nothing else such as
+    /// variables or globs is expanded. Response files may include other response files, and
+    /// relative paths are always resolved against the current working directory. Arguments after
+    /// a `--`, either on the command line or in a response file, are never expanded.
+    ///
+    /// Indices returned by [`ArgMatches::index_of`] and [`ArgMatches::indices_of`] refer to the
+    /// list of arguments *after* expansion.
+    ///
+    /// **NOTE:** A response file which can't be read, isn't properly quoted, or includes itself
+    /// results in an [`ErrorKind::Io`] error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings, ErrorKind};
+    /// let res = App::new("myprog")
+    ///     .setting(AppSettings::ResponseFiles)
+    ///     .arg(Arg::with_name("file").multiple(true))
+    ///     .get_matches_from_safe(vec!["myprog", "@does/not/exist.txt"]);
+    ///
+    /// assert!(res.is_err());
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::Io);
+    /// ```
+    /// [`ArgMatches::index_of`]: ./struct.ArgMatches.html#method.index_of
+    /// [`ArgMatches::indices_of`]: ./struct.ArgMatches.html#method.indices_of
+    /// [`ErrorKind::Io`]: ./enum.ErrorKind.html#variant.Io
+    ResponseFiles,
+
     /// Allows [`SubCommand`]s to override all requirements of the parent command.
     /// For example if you had a subcommand or top level application with a required argument
     /// that is only required as long as there is no subcommand present,
@@ -875,11 +1143,14 @@ impl FromStr for AppSettings {
     fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
         match &*s.to_ascii_lowercase() {
             "argrequiredelsehelp" => Ok(AppSettings::ArgRequiredElseHelp),
+            "allargsoverrideself" => Ok(AppSettings::AllArgsOverrideSelf),
             "argsnegatesubcommands" => Ok(AppSettings::ArgsNegateSubcommands),
             "allowinvalidutf8" => Ok(AppSettings::AllowInvalidUtf8),
             "allowleadinghyphen" => Ok(AppSettings::AllowLeadingHyphen),
             "allowexternalsubcommands" => Ok(AppSettings::AllowExternalSubcommands),
             "allownegativenumbers" => Ok(AppSettings::AllowNegativeNumbers),
+            "allowmissingpositional" => Ok(AppSettings::AllowMissingPositional),
+            "chainsubcommands" => Ok(AppSettings::ChainSubcommands),
             "colorauto" => Ok(AppSettings::ColorAuto),
             "coloralways" => Ok(AppSettings::ColorAlways),
             "colornever" => Ok(AppSettings::ColorNever),
@@ -889,13 +1160,19 @@ impl FromStr for AppSettings {
             "dontdelimittrailingvalues" => Ok(AppSettings::DontDelimitTrailingValues),
             "disablehelpsubcommand" => Ok(AppSettings::DisableHelpSubcommand),
             "disableversion" => Ok(AppSettings::DisableVersion),
+            "dynamiccompletion" => Ok(AppSettings::DynamicCompletion),
             "globalversion" => Ok(AppSettings::GlobalVersion),
             "hidden" => Ok(AppSettings::Hidden),
             "hidepossiblevaluesinhelp" => Ok(AppSettings::HidePossibleValuesInHelp),
+            "inferlongargs" => Ok(AppSettings::InferLongArgs),
             "infersubcommands" => Ok(AppSettings::InferSubcommands),
             "lowindexmultiplepositional" => Ok(AppSettings::LowIndexMultiplePositional),
+            "multicall" => Ok(AppSettings::Multicall),
             "nobinaryname" => Ok(AppSettings::NoBinaryName),
+            "pluginsubcommands" => Ok(AppSettings::PluginSubcommands),
+            "reportallerrors" => Ok(AppSettings::ReportAllErrors),
             "nextlinehelp" => Ok(AppSettings::NextLineHelp),
+            "responsefiles" => Ok(AppSettings::ResponseFiles),
             "strictutf8" => Ok(AppSettings::StrictUtf8),
             "subcommandsnegatereqs" => Ok(AppSettings::SubcommandsNegateReqs),
             "subcommandrequired" => Ok(AppSettings::SubcommandRequired),
@@ -943,6 +1220,18 @@ mod test {
             "allownegativenumbers".parse::<AppSettings>().unwrap(),
             AppSettings::AllowNegativeNumbers
         );
+        assert_eq!(
+            "allargsoverrideself".parse::<AppSettings>().unwrap(),
+            AppSettings::AllArgsOverrideSelf
+        );
+        assert_eq!(
+            "allowmissingpositional".parse::<AppSettings>().unwrap(),
+            AppSettings::AllowMissingPositional
+        );
+        assert_eq!(
+            "chainsubcommands".parse::<AppSettings>().unwrap(),
+            AppSettings::ChainSubcommands
+        );
         assert_eq!(
             "coloredhelp".parse::<AppSettings>().unwrap(),
             AppSettings::ColoredHelp
@@ -967,6 +1256,10 @@ mod test {
             "disableversion".parse::<AppSettings>().unwrap(),
             AppSettings::DisableVersion
         );
+        assert_eq!(
+            "dynamiccompletion".parse::<AppSettings>().unwrap(),
+            AppSettings::DynamicCompletion
+        );
         assert_eq!(
             "dontcollapseargsinusage".parse::<AppSettings>().unwrap(),
             AppSettings::DontCollapseArgsInUsage
@@ -995,14 +1288,30 @@ mod test {
             "lowindexmultiplePositional".parse::<AppSettings>().unwrap(),
             AppSettings::LowIndexMultiplePositional
         );
+        assert_eq!(
+            "multicall".parse::<AppSettings>().unwrap(),
+            AppSettings::Multicall
+        );
         assert_eq!(
             "nobinaryname".parse::<AppSettings>().unwrap(),
             AppSettings::NoBinaryName
         );
+        assert_eq!(
+            "pluginsubcommands".parse::<AppSettings>().unwrap(),
+            AppSettings::PluginSubcommands
+        );
+        assert_eq!(
+            "reportallerrors".parse::<AppSettings>().unwrap(),
+            AppSettings::ReportAllErrors
+        );
         assert_eq!(
             "nextlinehelp".parse::<AppSettings>().unwrap(),
             AppSettings::NextLineHelp
         );
+        assert_eq!(
+            "responsefiles".parse::<AppSettings>().unwrap(),
+            AppSettings::ResponseFiles
+        );
         assert_eq!(
             "subcommandsnegatereqs".parse::<AppSettings>().unwrap(),
             AppSettings::SubcommandsNegateReqs
@@ -1051,6 +1360,10 @@ mod test {
             "trailingvalues".parse::<AppSettings>().unwrap(),
             AppSettings::TrailingValues
         );
+        assert_eq!(
+            "inferlongargs".parse::<AppSettings>().unwrap(),
+            AppSettings::InferLongArgs
+        );
         assert_eq!(
             "infersubcommands".parse::<AppSettings>().unwrap(),
             AppSettings::InferSubcommands
@@ -96,7 +96,8 @@ pub fn create_help_usage(p: &Parser, incl_reqs: bool) -> String {
         && p.positionals
             .values()
             .any(|p| !p.is_set(ArgSettings::Required))
-        && !(p.has_visible_subcommands() || p.is_set(AS::AllowExternalSubcommands))
+        && !(p.has_visible_subcommands() || p.is_set(AS::AllowExternalSubcommands)
+            || p.is_set(AS::PluginSubcommands))
         && !has_last
     {
         usage.push_str(" [--]");
@@ -139,7 +140,9 @@ pub fn create_help_usage(p: &Parser, incl_reqs: bool) -> String {
     }
 
     // incl_reqs is only false when this function is called recursively
-    if p.has_visible_subcommands() && incl_reqs || p.is_set(AS::AllowExternalSubcommands) {
+    if p.has_visible_subcommands() && incl_reqs || p.is_set(AS::AllowExternalSubcommands)
+        || p.is_set(AS::PluginSubcommands)
+    {
         if p.is_set(AS::SubcommandsNegateReqs) || p.is_set(AS::ArgsNegateSubcommands) {
             if !p.is_set(AS::ArgsNegateSubcommands) {
                 usage.push_str("\n    ");
@@ -155,6 +158,9 @@ pub fn create_help_usage(p: &Parser, incl_reqs: bool) -> String {
         } else {
             usage.push_str(" [SUBCOMMAND]");
         }
+        if p.is_set(AS::ChainSubcommands) {
+            usage.push_str("...");
+        }
     }
     usage.shrink_to_fit();
     debugln!("usage::create_help_usage: usage={}", usage);
@@ -465,8 +471,7 @@ pub fn get_required_usage_from<'a, 'b>(
         .iter()
         .filter(|n| p.groups.iter().any(|g| &&g.name == n))
     {
-        let g_string = p.args_in_group(g).join("|");
-        let elem = format!("<{}>", &g_string[..g_string.len()]);
+        let elem = p.group_usage(g);
         if !g_vec.contains(&elem) {
             g_vec.push(elem);
         }
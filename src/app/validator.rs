@@ -8,12 +8,11 @@ use INTERNAL_ERROR_MSG;
 use INVALID_UTF8;
 use args::{AnyArg, ArgMatcher, MatchedArg};
 use args::settings::ArgSettings;
-use errors::{Error, ErrorKind};
+use errors::{self, Error, ErrorKind};
 use errors::Result as ClapResult;
 use osstringext::OsStrExt2;
 use app::settings::AppSettings as AS;
 use app::parser::{ParseResult, Parser};
-use fmt::{Colorizer, ColorizerOption};
 use app::usage;
 
 pub struct Validator<'a, 'b, 'z>(&'z mut Parser<'a, 'b>)
@@ -29,8 +28,10 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
         needs_val_of: ParseResult<'a>,
         subcmd_name: Option<String>,
         matcher: &mut ArgMatcher<'a>,
+        mut errors: Vec<Error>,
     ) -> ClapResult<()> {
         debugln!("Validator::validate;");
+        let all = self.0.is_set(AS::ReportAllErrors);
         let mut reqs_validated = false;
         self.0.add_env(matcher)?;
         self.0.add_defaults(matcher)?;
@@ -44,7 +45,8 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                 .expect(INTERNAL_ERROR_MSG)
                 .clone()
             };
-            self.validate_required(matcher)?;
+            let res = self.validate_required(matcher);
+            errors::collect(all, res, &mut errors)?;
             reqs_validated = true;
             let should_err = if let Some(v) = matcher.0.args.get(&*o.b.name) {
                 v.vals.is_empty() && !(o.v.min_vals.is_some() && o.v.min_vals.unwrap() == 0)
@@ -52,11 +54,12 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                 true
             };
             if should_err {
-                return Err(Error::empty_value(
+                let e = Error::empty_value(
                     &o,
                     &*usage::create_error_usage(self.0, matcher, None),
                     self.0.color(),
-                ));
+                );
+                errors::collect(all, Err(e), &mut errors)?;
             }
         }
 
@@ -65,18 +68,33 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
         {
             let mut out = vec![];
             self.0.write_help_err(&mut out)?;
-            return Err(Error {
-                message: String::from_utf8_lossy(&*out).into_owned(),
-                kind: ErrorKind::MissingArgumentOrSubcommand,
-                info: None,
-            });
+            return Err(Error::displayed(
+                String::from_utf8_lossy(&out).into_owned(),
+                ErrorKind::MissingArgumentOrSubcommand,
+            ));
         }
-        self.validate_blacklist(matcher)?;
+        errors::collect(all, self.validate_blacklist(matcher), &mut errors)?;
         if !(self.0.is_set(AS::SubcommandsNegateReqs) && subcmd_name.is_some()) && !reqs_validated {
-            self.validate_required(matcher)?;
+            let res = self.validate_required(matcher);
+            errors::collect(all, res, &mut errors)?;
+        }
+        errors::collect(all, self.validate_constraints(matcher), &mut errors)?;
+        let failed = self.validate_matched_args(matcher, all, &mut errors)?;
+        self.parse_values(matcher, &failed, all, &mut errors)?;
+        if !errors.is_empty() {
+            return Err(Error::multiple(
+                errors,
+                &*usage::create_error_usage(self.0, matcher, None),
+                self.0.color(),
+            ));
         }
-        self.validate_matched_args(matcher)?;
+        self.split_key_values(matcher);
         matcher.usage(usage::create_usage_with_title(self.0, &[]));
+        matcher.0.color = self.0.color();
+        if let Some(ref f) = self.0.validator {
+            debugln!("Validator::validate: running the app validator");
+            f(&matcher.0)?;
+        }
 
         Ok(())
     }
@@ -120,6 +138,34 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                     ));
                 }
             }
+            if let Some(d) = arg.key_value_delim() {
+                let val_str = val.to_string_lossy();
+                debugln!("Validator::validate_arg_values: key_value_delim={:?}", d);
+                let key = match split_key_value(&val_str, d) {
+                    Some((k, _)) => k,
+                    None => {
+                        return Err(Error::value_parse(
+                            &*val_str,
+                            arg,
+                            format!("expected a value of the form KEY{}VALUE", d),
+                            &*usage::create_error_usage(self.0, matcher, None),
+                            self.0.color(),
+                        ));
+                    }
+                };
+                if let Some(p_keys) = arg.possible_keys() {
+                    debugln!("Validator::validate_arg_values: possible_keys={:?}", p_keys);
+                    if !p_keys.contains(&key) {
+                        return Err(Error::invalid_key(
+                            key,
+                            p_keys,
+                            arg,
+                            &*usage::create_error_usage(self.0, matcher, None),
+                            self.0.color(),
+                        ));
+                    }
+                }
+            }
             if !arg.is_set(ArgSettings::EmptyValues) && val.is_empty_()
                 && matcher.contains(&*arg.name())
             {
@@ -188,13 +234,39 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
 
     fn validate_blacklist(&self, matcher: &mut ArgMatcher) -> ClapResult<()> {
         debugln!("Validator::validate_blacklist;");
+        for g in &self.0.groups {
+            let max = match g.max_args {
+                Some(max) => max,
+                None => continue,
+            };
+            // Like for conflicts, default values don't count
+            let used = self.0
+                .arg_names_in_group(g.name)
+                .iter()
+                .filter(|&&n| match matcher.get(n) {
+                    Some(ma) => ma.occurs > 0,
+                    None => false,
+                })
+                .count() as u64;
+            debugln!("Validator::validate_blacklist:{}: used={}, max={}", g.name, used, max);
+            if used > max {
+                return Err(Error::too_many_args_in_group(
+                    g.name,
+                    &self.0.group_usage(g.name),
+                    max,
+                    used,
+                    &*usage::create_error_usage(self.0, matcher, None),
+                    self.0.color(),
+                ));
+            }
+        }
         let mut conflicts: Vec<&str> = vec![];
         for (&name, _) in matcher.iter() {
             debugln!("Validator::validate_blacklist:iter:{};", name);
             if let Some(grps) = self.0.groups_for_arg(name) {
                 for grp in &grps {
                     if let Some(g) = self.0.groups.iter().find(|g| &g.name == grp) {
-                        if !g.multiple {
+                        if !g.multiple && g.min_args.is_none() && g.max_args.is_none() {
                             for arg in &g.args {
                                 if arg == &name {
                                     continue;
@@ -272,43 +344,148 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
         Ok(())
     }
 
-    fn validate_matched_args(&self, matcher: &mut ArgMatcher<'a>) -> ClapResult<()> {
+    // Returns the names of the arguments which failed, when the errors are being collected
+    fn validate_matched_args(
+        &self,
+        matcher: &mut ArgMatcher<'a>,
+        all: bool,
+        errors: &mut Vec<Error>,
+    ) -> ClapResult<Vec<&'a str>> {
         debugln!("Validator::validate_matched_args;");
+        let mut failed = vec![];
         for (name, ma) in matcher.iter() {
             debugln!(
                 "Validator::validate_matched_args:iter:{}: vals={:#?}",
                 name,
                 ma.vals
             );
-            if let Some(opt) = find_by_name!(self.0, *name, opts, iter) {
-                self.validate_arg_num_vals(opt, ma, matcher)?;
-                self.validate_arg_values(opt, ma, matcher)?;
-                self.validate_arg_requires(opt, ma, matcher)?;
-                self.validate_arg_num_occurs(opt, ma, matcher)?;
-            } else if let Some(flag) = find_by_name!(self.0, *name, flags, iter) {
-                self.validate_arg_requires(flag, ma, matcher)?;
-                self.validate_arg_num_occurs(flag, ma, matcher)?;
-            } else if let Some(pos) = find_by_name!(self.0, *name, positionals, values) {
-                self.validate_arg_num_vals(pos, ma, matcher)?;
-                self.validate_arg_num_occurs(pos, ma, matcher)?;
-                self.validate_arg_values(pos, ma, matcher)?;
-                self.validate_arg_requires(pos, ma, matcher)?;
-            } else {
-                let grp = self.0
-                    .groups
-                    .iter()
-                    .find(|g| &g.name == name)
-                    .expect(INTERNAL_ERROR_MSG);
-                if let Some(ref g_reqs) = grp.requires {
-                    if g_reqs.iter().any(|&n| !matcher.contains(n)) {
-                        return self.missing_required_error(matcher, None);
-                    }
+            let res = self.validate_matched_arg(name, ma, matcher);
+            if res.is_err() {
+                failed.push(*name);
+            }
+            errors::collect(all, res, errors)?;
+        }
+        Ok(failed)
+    }
+
+    fn validate_matched_arg(
+        &self,
+        name: &str,
+        ma: &MatchedArg,
+        matcher: &ArgMatcher<'a>,
+    ) -> ClapResult<()> {
+        if let Some(opt) = find_by_name!(self.0, name, opts, iter) {
+            self.validate_arg_num_vals(opt, ma, matcher)?;
+            self.validate_arg_values(opt, ma, matcher)?;
+            self.validate_arg_requires(opt, ma, matcher)?;
+            self.validate_arg_num_occurs(opt, ma, matcher)?;
+        } else if let Some(flag) = find_by_name!(self.0, name, flags, iter) {
+            self.validate_arg_requires(flag, ma, matcher)?;
+            self.validate_arg_num_occurs(flag, ma, matcher)?;
+        } else if let Some(pos) = find_by_name!(self.0, name, positionals, values) {
+            self.validate_arg_num_vals(pos, ma, matcher)?;
+            self.validate_arg_num_occurs(pos, ma, matcher)?;
+            self.validate_arg_values(pos, ma, matcher)?;
+            self.validate_arg_requires(pos, ma, matcher)?;
+        } else {
+            let grp = self.0
+                .groups
+                .iter()
+                .find(|g| g.name == name)
+                .expect(INTERNAL_ERROR_MSG);
+            if let Some(ref g_reqs) = grp.requires {
+                if g_reqs.iter().any(|&n| !matcher.contains(n)) {
+                    return self.missing_required_error(matcher, None);
                 }
             }
         }
         Ok(())
     }
 
+    // Runs each `Arg::value_parser` over the (already validated) values, storing the results
+    // next to the raw values so `ArgMatches::get` doesn't have to parse them again
+    fn parse_values(
+        &self,
+        matcher: &mut ArgMatcher<'a>,
+        failed: &[&str],
+        all: bool,
+        errors: &mut Vec<Error>,
+    ) -> ClapResult<()> {
+        debugln!("Validator::parse_values;");
+        macro_rules! parse_vals {
+            ($a:ident) => {{
+                let mut res = Ok(());
+                if let Some(ref vp) = $a.v.value_parser {
+                    let mut parsed = vec![];
+                    if let Some(ma) = matcher.get($a.b.name) {
+                        debugln!("Validator::parse_values:iter:{}: vals={:?}", $a.b.name, ma.vals);
+                        for val in &ma.vals {
+                            let r = val.to_str()
+                                .ok_or_else(|| String::from(INVALID_UTF8))
+                                .and_then(|s| vp(s));
+                            match r {
+                                Ok(v) => parsed.push(v),
+                                Err(e) => {
+                                    res = Err(Error::value_parse(
+                                        val.to_string_lossy(),
+                                        $a,
+                                        e,
+                                        &*usage::create_error_usage(self.0, matcher, None),
+                                        self.0.color(),
+                                    ));
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    if let Some(ma) = matcher.get_mut($a.b.name) {
+                        ma.parsed = parsed;
+                    }
+                }
+                res
+            }};
+        }
+        for o in self.0.opts.iter().filter(|o| !failed.contains(&o.b.name)) {
+            errors::collect(all, parse_vals!(o), errors)?;
+        }
+        for p in self.0
+            .positionals
+            .values()
+            .filter(|p| !failed.contains(&p.b.name))
+        {
+            errors::collect(all, parse_vals!(p), errors)?;
+        }
+        Ok(())
+    }
+
+    // Splits the (already validated) values of each `Arg::key_value_delimiter` argument into
+    // their keys and values, for `ArgMatches::key_values_of`
+    fn split_key_values(&self, matcher: &mut ArgMatcher<'a>) {
+        debugln!("Validator::split_key_values;");
+        macro_rules! split_vals {
+            ($a:ident) => {{
+                if let Some(d) = $a.v.kv_delim {
+                    if let Some(ma) = matcher.get_mut($a.b.name) {
+                        let mut kvs = vec![];
+                        for val in &ma.vals {
+                            let val_str = val.to_string_lossy();
+                            if let Some((k, v)) = split_key_value(&val_str, d) {
+                                kvs.push((k.to_owned(), v.to_owned()));
+                            }
+                        }
+                        ma.key_values = Some(kvs);
+                    }
+                }
+            }};
+        }
+        for o in &self.0.opts {
+            split_vals!(o);
+        }
+        for p in self.0.positionals.values() {
+            split_vals!(p);
+        }
+    }
+
     fn validate_arg_num_occurs<A>(
         &self,
         a: &A,
@@ -494,6 +671,34 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                 }
             }
         }
+
+        // Groups with a minimum only need it once any of their args is used, a required one
+        // without any was handled above. As for the maximum, default values don't count
+        for g in &self.0.groups {
+            let min = match g.min_args {
+                Some(min) => min,
+                None => continue,
+            };
+            let used = self.0
+                .arg_names_in_group(g.name)
+                .iter()
+                .filter(|&&n| match matcher.get(n) {
+                    Some(ma) => ma.occurs > 0,
+                    None => false,
+                })
+                .count() as u64;
+            debugln!("Validator::validate_required:{}: used={}, min={}", g.name, used, min);
+            if used > 0 && used < min {
+                return Err(Error::too_few_args_in_group(
+                    g.name,
+                    &self.0.group_usage(g.name),
+                    min,
+                    used,
+                    &*usage::create_error_usage(self.0, matcher, None),
+                    self.0.color(),
+                ));
+            }
+        }
         Ok(())
     }
 
@@ -537,10 +742,6 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
 
     fn missing_required_error(&self, matcher: &ArgMatcher, extra: Option<&str>) -> ClapResult<()> {
         debugln!("Validator::missing_required_error: extra={:?}", extra);
-        let c = Colorizer::new(ColorizerOption {
-            use_stderr: true,
-            when: self.0.color(),
-        });
         let mut reqs = self.0.required.iter().map(|&r| &*r).collect::<Vec<_>>();
         if let Some(r) = extra {
             reqs.push(r);
@@ -549,22 +750,52 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
         reqs.dedup();
         debugln!("Validator::missing_required_error: reqs={:#?}", reqs);
         let req_args =
-            usage::get_required_usage_from(self.0, &reqs[..], Some(matcher), extra, true)
-                .iter()
-                .fold(String::new(), |acc, s| {
-                    acc + &format!("\n    {}", c.error(s))[..]
-                });
+            usage::get_required_usage_from(self.0, &reqs[..], Some(matcher), extra, true);
         debugln!(
             "Validator::missing_required_error: req_args={:#?}",
             req_args
         );
         Err(Error::missing_required_argument(
-            &*req_args,
+            req_args.into_iter().collect(),
             &*usage::create_error_usage(self.0, matcher, extra),
             self.0.color(),
         ))
     }
 
+    fn validate_constraints(&self, matcher: &ArgMatcher<'a>) -> ClapResult<()> {
+        debugln!("Validator::validate_constraints;");
+        for c in &self.0.constraints {
+            let v = match c.violated(matcher) {
+                Some(v) => v,
+                None => continue,
+            };
+            debugln!("Validator::validate_constraints: violated={:?}", v);
+            let names = v.arg_names();
+            let args = usage::get_required_usage_from(self.0, &names[..], None, None, true);
+            let rule = v.describe(&|n| self.constraint_arg(n));
+            return Err(Error::constraint_violation(
+                &rule,
+                v.custom_message(),
+                args.into_iter().collect(),
+                names.iter().map(|&n| n.to_owned()).collect(),
+                &*usage::create_error_usage(self.0, matcher, None),
+                self.0.color(),
+            ));
+        }
+        Ok(())
+    }
+
+    // How a constraint refers to an argument or group
+    fn constraint_arg(&self, name: &str) -> String {
+        if let Some(a) = find_any_by_name!(self.0, name) {
+            a.to_string()
+        } else if self.0.groups.iter().any(|g| g.name == name) {
+            self.0.group_usage(name)
+        } else {
+            name.to_owned()
+        }
+    }
+
     #[inline]
     fn is_missing_required_ok(&self, a: &AnyArg, matcher: &ArgMatcher) -> bool {
         debugln!("Validator::is_missing_required_ok: a={}", a.name());
@@ -572,3 +803,11 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
             || self.validate_required_unless(a, matcher).unwrap_or(false)
     }
 }
+
+// Splits `val` on the first `d`, as long as there is a key before it
+fn split_key_value(val: &str, d: char) -> Option<(&str, &str)> {
+    match val.find(d) {
+        Some(0) | None => None,
+        Some(i) => Some((&val[..i], &val[i + d.len_utf8()..])),
+    }
+}
@@ -20,6 +20,7 @@ pub trait AnyArg<'n, 'e>: std_fmt::Display {
     fn set(&mut self, ArgSettings);
     fn has_switch(&self) -> bool;
     fn max_vals(&self) -> Option<u64>;
+    fn max_occurs(&self) -> Option<u64>;
     fn min_vals(&self) -> Option<u64>;
     fn num_vals(&self) -> Option<u64>;
     fn possible_vals(&self) -> Option<&[&'e str]>;
@@ -28,6 +29,8 @@ pub trait AnyArg<'n, 'e>: std_fmt::Display {
     fn short(&self) -> Option<char>;
     fn long(&self) -> Option<&'e str>;
     fn val_delim(&self) -> Option<char>;
+    fn key_value_delim(&self) -> Option<char>;
+    fn possible_keys(&self) -> Option<&[&'e str]>;
     fn takes_value(&self) -> bool;
     fn val_names(&self) -> Option<&VecMap<&'e str>>;
     fn help(&self) -> Option<&'e str>;
@@ -54,6 +57,7 @@ impl<'n, 'e, 'z, T: ?Sized> AnyArg<'n, 'e> for &'z T where T: AnyArg<'n, 'e> + '
     fn set(&mut self, _: ArgSettings) { panic!(INTERNAL_ERROR_MSG) }
     fn has_switch(&self) -> bool { (*self).has_switch() }
     fn max_vals(&self) -> Option<u64> { (*self).max_vals() }
+    fn max_occurs(&self) -> Option<u64> { (*self).max_occurs() }
     fn min_vals(&self) -> Option<u64> { (*self).min_vals() }
     fn num_vals(&self) -> Option<u64> { (*self).num_vals() }
     fn possible_vals(&self) -> Option<&[&'e str]> { (*self).possible_vals() }
@@ -62,6 +66,8 @@ impl<'n, 'e, 'z, T: ?Sized> AnyArg<'n, 'e> for &'z T where T: AnyArg<'n, 'e> + '
     fn short(&self) -> Option<char> { (*self).short() }
     fn long(&self) -> Option<&'e str> { (*self).long() }
     fn val_delim(&self) -> Option<char> { (*self).val_delim() }
+    fn key_value_delim(&self) -> Option<char> { (*self).key_value_delim() }
+    fn possible_keys(&self) -> Option<&[&'e str]> { (*self).possible_keys() }
     fn takes_value(&self) -> bool { (*self).takes_value() }
     fn val_names(&self) -> Option<&VecMap<&'e str>> { (*self).val_names() }
     fn help(&self) -> Option<&'e str> { (*self).help() }
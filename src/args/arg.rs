@@ -1,5 +1,6 @@
 #[cfg(feature = "yaml")]
 use std::collections::BTreeMap;
+use std::any::Any;
 use std::rc::Rc;
 use std::ffi::{OsStr, OsString};
 #[cfg(any(target_os = "windows", target_arch = "wasm32"))]
@@ -15,6 +16,7 @@ use map::VecMap;
 use usage_parser::UsageParser;
 use args::settings::ArgSettings;
 use args::arg_builder::{Base, Switched, Valued};
+use args::AnyValue;
 
 /// The abstract representation of a command line argument. Used to set all the options and
 /// relationships that define a valid argument for the program.
@@ -112,7 +114,10 @@ impl<'a, 'b> Arg<'a, 'b> {
                 "index" => yaml_to_u64!(a, v, index),
                 "global" => yaml_to_bool!(a, v, global),
                 "multiple" => yaml_to_bool!(a, v, multiple),
+                "max_occurrences" => yaml_to_u64!(a, v, max_occurrences),
+                "decrements" => yaml_to_str!(a, v, decrements),
                 "hidden" => yaml_to_bool!(a, v, hidden),
+                "negatable" => yaml_to_bool!(a, v, negatable),
                 "next_line_help" => yaml_to_bool!(a, v, next_line_help),
                 "empty_values" => yaml_to_bool!(a, v, empty_values),
                 "group" => yaml_to_str!(a, v, group),
@@ -125,6 +130,7 @@ impl<'a, 'b> Arg<'a, 'b> {
                 "last" => yaml_to_bool!(a, v, last),
                 "require_delimiter" => yaml_to_bool!(a, v, require_delimiter),
                 "value_delimiter" => yaml_to_str!(a, v, value_delimiter),
+                "key_value_delimiter" => yaml_to_str!(a, v, key_value_delimiter),
                 "required_unless" => yaml_to_str!(a, v, required_unless),
                 "display_order" => yaml_to_usize!(a, v, display_order),
                 "default_value" => yaml_to_str!(a, v, default_value),
@@ -139,6 +145,7 @@ impl<'a, 'b> Arg<'a, 'b> {
                 "conflicts_with" => yaml_vec_or_str!(v, a, conflicts_with),
                 "overrides_with" => yaml_vec_or_str!(v, a, overrides_with),
                 "possible_values" => yaml_vec_or_str!(v, a, possible_value),
+                "possible_keys" => yaml_vec_or_str!(v, a, possible_key),
                 "required_unless_one" => yaml_vec_or_str!(v, a, required_unless),
                 "required_unless_all" => {
                     a = yaml_vec_or_str!(v, a, required_unless);
@@ -2152,6 +2159,69 @@ impl<'a, 'b> Arg<'a, 'b> {
         }
     }
 
+    /// Allows a [flag] to be used up to `max` times, i.e. `-vvv` for the third level of verbosity.
+    /// Any further occurrences are accepted, but not counted by [`ArgMatches::occurrences_of`].
+    ///
+    /// The help message lists the maximum next to the flag, i.e. `[max: 3]`.
+    ///
+    /// **NOTE:** This setting implies [`Arg::multiple(true)`], and only applies to flags. Using it
+    /// on an argument which takes values panics in debug builds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("verbose")
+    ///         .short("v")
+    ///         .max_occurrences(3))
+    ///     .get_matches_from(vec![
+    ///         "prog", "-vvvv", "-v"
+    ///     ]);
+    ///
+    /// assert_eq!(m.occurrences_of("verbose"), 3);
+    /// ```
+    /// [flag]: ./struct.Arg.html#method.takes_value
+    /// [`ArgMatches::occurrences_of`]: ./struct.ArgMatches.html#method.occurrences_of
+    /// [`Arg::multiple(true)`]: ./struct.Arg.html#method.multiple
+    pub fn max_occurrences(mut self, max: u64) -> Self {
+        self.b.max_occurs = Some(max);
+        self.set(ArgSettings::Multiple)
+    }
+
+    /// Makes each occurrence of this [flag] lower the level of another flag by one, for use with
+    /// [`ArgMatches::verbosity`]. This is typically a `--quiet` flag for a `--verbose` one, so
+    /// that `-vv -q` has the same meaning as `-v`.
+    ///
+    /// The flags are still counted separately by [`ArgMatches::occurrences_of`], and this one
+    /// may have its own [`Arg::max_occurrences`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let app = App::new("prog")
+    ///     .arg(Arg::from_usage("-v, --verbose... 'more output'"))
+    ///     .arg(Arg::from_usage("-q, --quiet 'less output'")
+    ///         .max_occurrences(2)
+    ///         .decrements("verbose"));
+    ///
+    /// let m = app.clone().get_matches_from(vec!["prog", "-vvv", "-q"]);
+    /// assert_eq!(m.verbosity("verbose"), 2);
+    ///
+    /// let m = app.get_matches_from(vec!["prog", "-qqq"]);
+    /// assert_eq!(m.verbosity("verbose"), -2);
+    /// assert_eq!(m.occurrences_of("quiet"), 2);
+    /// ```
+    /// [flag]: ./struct.Arg.html#method.takes_value
+    /// [`ArgMatches::verbosity`]: ./struct.ArgMatches.html#method.verbosity
+    /// [`ArgMatches::occurrences_of`]: ./struct.ArgMatches.html#method.occurrences_of
+    /// [`Arg::max_occurrences`]: ./struct.Arg.html#method.max_occurrences
+    pub fn decrements(mut self, name: &'a str) -> Self {
+        self.b.decrements = Some(name);
+        self
+    }
+
     /// Specifies a value that *stops* parsing multiple values of a give argument. By default when
     /// one sets [`multiple(true)`] on an argument, clap will continue parsing values for that
     /// argument until it reaches another valid argument, or one of the other more specific settings
@@ -2343,6 +2413,46 @@ impl<'a, 'b> Arg<'a, 'b> {
         }
     }
 
+    /// Allows a [flag] with a [`Arg::long`] to be turned off again with `--no-<long>`, e.g.
+    /// `--no-color` for `--color`. Whichever of the two is used last wins, and the flag is only
+    /// [present] if that is the flag itself. Use [`ArgMatches::flag_state`] to also tell whether
+    /// it was explicitly turned off.
+    ///
+    /// The help message lists both forms as one, i.e. `--[no-]color`. With
+    /// [`AppSettings::InferLongArgs`] the long may be abbreviated after the `no-` too, i.e.
+    /// `--no-col`.
+    ///
+    /// **NOTE:** This setting only applies to flags, and does nothing for arguments without a
+    /// long.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("color")
+    ///         .long("color")
+    ///         .negatable(true))
+    ///     .get_matches_from(vec![
+    ///         "prog", "--color", "--no-color"
+    ///     ]);
+    ///
+    /// assert!(!m.is_present("color"));
+    /// assert_eq!(m.flag_state("color"), Some(false));
+    /// ```
+    /// [flag]: ./struct.Arg.html#method.takes_value
+    /// [`Arg::long`]: ./struct.Arg.html#method.long
+    /// [present]: ./struct.ArgMatches.html#method.is_present
+    /// [`ArgMatches::flag_state`]: ./struct.ArgMatches.html#method.flag_state
+    /// [`AppSettings::InferLongArgs`]: ./enum.AppSettings.html#variant.InferLongArgs
+    pub fn negatable(self, n: bool) -> Self {
+        if n {
+            self.set(ArgSettings::Negatable)
+        } else {
+            self.unset(ArgSettings::Negatable)
+        }
+    }
+
     /// Specifies a list of possible values for this argument. At runtime, `clap` verifies that
     /// only one of the specified values was used, or fails with an error message.
     ///
@@ -2463,6 +2573,65 @@ impl<'a, 'b> Arg<'a, 'b> {
         self
     }
 
+    /// Specifies a list of the keys allowed in the values of an argument using
+    /// [`Arg::key_value_delimiter`]. At runtime, `clap` verifies that every key is one of them, or
+    /// fails with an error message suggesting the closest allowed key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let app = App::new("prog")
+    ///     .arg(Arg::with_name("set")
+    ///         .long("set")
+    ///         .multiple(true)
+    ///         .key_value_delimiter("=")
+    ///         .possible_keys(&["width", "height"]));
+    ///
+    /// let m = app.clone().get_matches_from(vec!["prog", "--set", "width=80"]);
+    /// assert_eq!(m.key_values_of("set").unwrap().collect::<Vec<_>>(), [("width", "80")]);
+    ///
+    /// let res = app.get_matches_from_safe(vec!["prog", "--set", "widht=80"]);
+    /// assert!(res.is_err());
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::InvalidValue);
+    /// ```
+    /// [`Arg::key_value_delimiter`]: ./struct.Arg.html#method.key_value_delimiter
+    pub fn possible_keys(mut self, names: &[&'b str]) -> Self {
+        if let Some(ref mut vec) = self.v.possible_keys {
+            for s in names {
+                vec.push(s);
+            }
+        } else {
+            self.v.possible_keys = Some(names.to_vec());
+        }
+        self
+    }
+
+    /// Specifies a key allowed in the values of an argument using [`Arg::key_value_delimiter`],
+    /// one at a time. See [`Arg::possible_keys`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// Arg::with_name("set")
+    ///     .long("set")
+    ///     .key_value_delimiter("=")
+    ///     .possible_key("width")
+    ///     .possible_key("height")
+    /// # ;
+    /// ```
+    /// [`Arg::key_value_delimiter`]: ./struct.Arg.html#method.key_value_delimiter
+    /// [`Arg::possible_keys`]: ./struct.Arg.html#method.possible_keys
+    pub fn possible_key(mut self, name: &'b str) -> Self {
+        if let Some(ref mut vec) = self.v.possible_keys {
+            vec.push(name);
+        } else {
+            self.v.possible_keys = Some(vec![name]);
+        }
+        self
+    }
+
     /// When used with [`Arg::possible_values`] it allows the argument value to pass validation even if
     /// the case differs from that of the specified `possible_value`.
     ///
@@ -2721,6 +2890,109 @@ impl<'a, 'b> Arg<'a, 'b> {
         self
     }
 
+    /// Parses each value of this argument into a `T` once, while the arguments are being
+    /// validated, so that it can later be retrieved already typed with [`ArgMatches::get`] and
+    /// [`ArgMatches::get_many`]. Values coming from [`Arg::default_value`] or [`Arg::env`] are
+    /// parsed too.
+    ///
+    /// The function receives each value as a `&str` and returns either the parsed value or an
+    /// error message, which is reported as an [`ErrorKind::ValueValidation`] naming the argument
+    /// and the offending value, followed by the usage string.
+    ///
+    /// **NOTE:** Any type implementing [`FromStr`] can be parsed by passing a closure calling
+    /// [`str::parse`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("port")
+    ///         .long("port")
+    ///         .takes_value(true)
+    ///         .value_parser(|s: &str| s.parse::<u16>().map_err(|e| e.to_string())))
+    ///     .get_matches_from(vec![
+    ///         "prog", "--port", "8080"
+    ///     ]);
+    ///
+    /// assert_eq!(m.get::<u16>("port"), Some(&8080));
+    /// ```
+    ///
+    /// Values which can't be parsed are rejected along with the usage string
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let res = App::new("prog")
+    ///     .arg(Arg::with_name("port")
+    ///         .long("port")
+    ///         .takes_value(true)
+    ///         .value_parser(|s: &str| s.parse::<u16>().map_err(|e| e.to_string())))
+    ///     .get_matches_from_safe(vec![
+    ///         "prog", "--port", "http"
+    ///     ]);
+    ///
+    /// assert!(res.is_err());
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::ValueValidation);
+    /// ```
+    /// [`ArgMatches::get`]: ./struct.ArgMatches.html#method.get
+    /// [`ArgMatches::get_many`]: ./struct.ArgMatches.html#method.get_many
+    /// [`Arg::default_value`]: ./struct.Arg.html#method.default_value
+    /// [`Arg::env`]: ./struct.Arg.html#method.env
+    /// [`ErrorKind::ValueValidation`]: ./enum.ErrorKind.html#variant.ValueValidation
+    /// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+    /// [`str::parse`]: https://doc.rust-lang.org/std/primitive.str.html#method.parse
+    pub fn value_parser<F, T>(mut self, f: F) -> Self
+    where
+        F: Fn(&str) -> Result<T, String> + 'static,
+        T: Any + Send + Sync,
+    {
+        self.v.value_parser = Some(Rc::new(move |s| f(s).map(AnyValue::new)));
+        self
+    }
+
+    /// Computes the completion candidates for the values of this argument at the time the user
+    /// presses `<TAB>`, when the shell completion script was generated with
+    /// [`App::gen_dynamic_completions`] and the [`AppSettings::DynamicCompletion`] setting is
+    /// used. This allows completing values which depend on the state of the system, such as
+    /// branch names, remote hosts or files of a given extension.
+    ///
+    /// The function receives the (possibly empty) partial value being completed and returns the
+    /// candidates, which are offered as is (i.e. they are *not* filtered by the partial value).
+    /// Without a completer, the [possible values] of the argument are offered instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings};
+    /// fn branches(cur: &str) -> Vec<String> {
+    ///     ["master", "maint", "next"]
+    ///         .iter()
+    ///         .filter(|b| b.starts_with(cur))
+    ///         .map(|b| b.to_string())
+    ///         .collect()
+    /// }
+    /// let res = App::new("prog")
+    ///     .setting(AppSettings::DynamicCompletion)
+    ///     .arg(Arg::with_name("branch")
+    ///         .index(1)
+    ///         .completer(branches))
+    ///     .get_matches_from_safe(vec![
+    ///         "prog", "--complete", "1", "--", "prog", "ma"
+    ///     ]);
+    ///
+    /// assert_eq!(res.unwrap_err().message, "master\nmaint");
+    /// ```
+    /// [`App::gen_dynamic_completions`]: ./struct.App.html#method.gen_dynamic_completions
+    /// [`AppSettings::DynamicCompletion`]: ./enum.AppSettings.html#variant.DynamicCompletion
+    /// [possible values]: ./struct.Arg.html#method.possible_values
+    pub fn completer<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str) -> Vec<String> + 'static,
+    {
+        self.v.completer = Some(Rc::new(f));
+        self
+    }
+
     /// Specifies the *maximum* number of values are for this argument. For example, if you had a
     /// `-f <file>` argument where you wanted up to 3 'files' you would set `.max_values(3)`, and
     /// this argument would be satisfied if the user provided, 1, 2, or 3 values.
@@ -3026,6 +3298,57 @@ impl<'a, 'b> Arg<'a, 'b> {
         self
     }
 
+    /// Makes every value of this argument a `key=value` pair, such as the defines of
+    /// `cc -D NAME=VALUE`, split on the first occurrence of the given separator. A value without
+    /// the separator, or with nothing before it, fails with an [`ErrorKind::ValueValidation`]
+    /// error. The pairs are accessed with [`ArgMatches::key_values_of`].
+    ///
+    /// **NOTE:** implicitly sets [`Arg::takes_value(true)`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("define")
+    ///         .short("D")
+    ///         .multiple(true)
+    ///         .number_of_values(1)
+    ///         .key_value_delimiter("="))
+    ///     .get_matches_from(vec![
+    ///         "prog", "-D", "NAME=clap", "-D", "EMPTY=", "-D", "EQ=a=b"
+    ///     ]);
+    ///
+    /// let defines: Vec<_> = m.key_values_of("define").unwrap().collect();
+    /// assert_eq!(defines, [("NAME", "clap"), ("EMPTY", ""), ("EQ", "a=b")]);
+    /// ```
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let res = App::new("prog")
+    ///     .arg(Arg::with_name("define")
+    ///         .short("D")
+    ///         .key_value_delimiter("="))
+    ///     .get_matches_from_safe(vec![
+    ///         "prog", "-D", "NAME"
+    ///     ]);
+    ///
+    /// assert!(res.is_err());
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::ValueValidation);
+    /// ```
+    /// [`ErrorKind::ValueValidation`]: ./enum.ErrorKind.html#variant.ValueValidation
+    /// [`ArgMatches::key_values_of`]: ./struct.ArgMatches.html#method.key_values_of
+    /// [`Arg::takes_value(true)`]: ./struct.Arg.html#method.takes_value
+    pub fn key_value_delimiter(mut self, d: &str) -> Self {
+        self.setb(ArgSettings::TakesValue);
+        self.v.kv_delim = Some(
+            d.chars()
+                .nth(0)
+                .expect("Failed to get key_value_delimiter from arg"),
+        );
+        self
+    }
+
     /// Specify multiple names for values of option arguments. These names are cosmetic only, used
     /// for help and usage strings only. The names are **not** used to access arguments. The values
     /// of the arguments are accessed in numeric order (i.e. if you specify two names `one` and
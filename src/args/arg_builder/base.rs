@@ -14,6 +14,8 @@ where
     pub overrides: Option<Vec<&'a str>>,
     pub groups: Option<Vec<&'a str>>,
     pub requires: Option<Vec<(Option<&'b str>, &'a str)>>,
+    pub max_occurs: Option<u64>,
+    pub decrements: Option<&'a str>,
 }
 
 impl<'n, 'e> Base<'n, 'e> {
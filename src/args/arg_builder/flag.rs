@@ -28,6 +28,14 @@ impl<'n, 'e> FlagBuilder<'n, 'e> {
             ..Default::default()
         }
     }
+
+    // The long turning this flag off, i.e. `no-<long>`, if it is negatable
+    pub fn negated_long(&self) -> Option<String> {
+        match self.s.long {
+            Some(l) if self.b.is_set(ArgSettings::Negatable) => Some(format!("no-{}", l)),
+            _ => None,
+        }
+    }
 }
 
 impl<'a, 'b, 'z> From<&'z Arg<'a, 'b>> for FlagBuilder<'a, 'b> {
@@ -73,6 +81,7 @@ impl<'n, 'e> AnyArg<'n, 'e> for FlagBuilder<'n, 'e> {
     fn takes_value(&self) -> bool { false }
     fn set(&mut self, s: ArgSettings) { self.b.settings.set(s) }
     fn max_vals(&self) -> Option<u64> { None }
+    fn max_occurs(&self) -> Option<u64> { self.b.max_occurs }
     fn val_names(&self) -> Option<&VecMap<&'e str>> { None }
     fn num_vals(&self) -> Option<u64> { None }
     fn possible_vals(&self) -> Option<&[&'e str]> { None }
@@ -82,6 +91,8 @@ impl<'n, 'e> AnyArg<'n, 'e> for FlagBuilder<'n, 'e> {
     fn short(&self) -> Option<char> { self.s.short }
     fn long(&self) -> Option<&'e str> { self.s.long }
     fn val_delim(&self) -> Option<char> { None }
+    fn key_value_delim(&self) -> Option<char> { None }
+    fn possible_keys(&self) -> Option<&[&'e str]> { None }
     fn help(&self) -> Option<&'e str> { self.b.help }
     fn long_help(&self) -> Option<&'e str> { self.b.long_help }
     fn val_terminator(&self) -> Option<&'e str> { None }
@@ -144,6 +144,7 @@ impl<'n, 'e> AnyArg<'n, 'e> for PosBuilder<'n, 'e> {
     fn set(&mut self, s: ArgSettings) { self.b.settings.set(s) }
     fn has_switch(&self) -> bool { false }
     fn max_vals(&self) -> Option<u64> { self.v.max_vals }
+    fn max_occurs(&self) -> Option<u64> { self.b.max_occurs }
     fn val_terminator(&self) -> Option<&'e str> { self.v.terminator }
     fn num_vals(&self) -> Option<u64> { self.v.num_vals }
     fn possible_vals(&self) -> Option<&[&'e str]> { self.v.possible_vals.as_ref().map(|o| &o[..]) }
@@ -157,6 +158,8 @@ impl<'n, 'e> AnyArg<'n, 'e> for PosBuilder<'n, 'e> {
     fn short(&self) -> Option<char> { None }
     fn long(&self) -> Option<&'e str> { None }
     fn val_delim(&self) -> Option<char> { self.v.val_delim }
+    fn key_value_delim(&self) -> Option<char> { self.v.kv_delim }
+    fn possible_keys(&self) -> Option<&[&'e str]> { self.v.possible_keys.as_ref().map(|o| &o[..]) }
     fn takes_value(&self) -> bool { true }
     fn help(&self) -> Option<&'e str> { self.b.help }
     fn long_help(&self) -> Option<&'e str> { self.b.long_help }
@@ -4,6 +4,7 @@ use std::ffi::{OsStr, OsString};
 use map::VecMap;
 
 use Arg;
+use args::AnyValue;
 
 #[allow(missing_debug_implementations)]
 #[derive(Clone)]
@@ -18,7 +19,11 @@ where
     pub min_vals: Option<u64>,
     pub validator: Option<Rc<Fn(String) -> Result<(), String>>>,
     pub validator_os: Option<Rc<Fn(&OsStr) -> Result<(), OsString>>>,
+    pub value_parser: Option<Rc<Fn(&str) -> Result<AnyValue, String>>>,
+    pub completer: Option<Rc<Fn(&str) -> Vec<String>>>,
     pub val_delim: Option<char>,
+    pub kv_delim: Option<char>,
+    pub possible_keys: Option<Vec<&'b str>>,
     pub default_val: Option<&'b OsStr>,
     pub default_vals_ifs: Option<VecMap<(&'a str, Option<&'b OsStr>, &'b OsStr)>>,
     pub env: Option<(&'a OsStr, Option<OsString>)>,
@@ -35,7 +40,11 @@ impl<'n, 'e> Default for Valued<'n, 'e> {
             val_names: None,
             validator: None,
             validator_os: None,
+            value_parser: None,
+            completer: None,
             val_delim: None,
+            kv_delim: None,
+            possible_keys: None,
             default_val: None,
             default_vals_ifs: None,
             env: None,
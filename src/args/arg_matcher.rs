@@ -6,7 +6,7 @@ use std::ops::Deref;
 use std::mem;
 
 // Internal
-use args::{ArgMatches, MatchedArg, SubCommand};
+use args::{ArgMatches, MatchedArg, SubCommand, ValueSource};
 use args::AnyArg;
 use args::settings::ArgSettings;
 
@@ -68,6 +68,7 @@ impl<'a> ArgMatcher<'a> {
                     // swap_remove(0) would be O(1) but does not preserve order, which
                     // we need
                     ma.vals.remove(0);
+                    ma.sources.remove(0);
                     ma.occurs = 1;
                 } else if !aa.takes_value() && ma.occurs > 1 {
                     ma.occurs = 1;
@@ -86,10 +87,12 @@ impl<'a> ArgMatcher<'a> {
         self.fill_in_global_values(global_arg_vec, &mut vals_map);
     }
 
+    // A `None` value in `vals_map` stands for a negated flag, so that it's turned off at every
+    // level, just like a value is set at every level
     fn fill_in_global_values(
         &mut self,
         global_arg_vec: &[&'a str],
-        vals_map: &mut HashMap<&'a str, MatchedArg>,
+        vals_map: &mut HashMap<&'a str, Option<MatchedArg>>,
     ) {
         for global_arg in global_arg_vec {
             if let Some(ma) = self.get(global_arg) {
@@ -99,26 +102,45 @@ impl<'a> ArgMatcher<'a> {
                 // For example, `myprog subcommand --global-arg=value` where --global-arg defines
                 // a default value of `other` myprog would have an existing MatchedArg for
                 // --global-arg where the value is `other`, however the occurs will be 0.
-                let to_update = if let Some(parent_ma) = vals_map.get(global_arg) {
-                    if parent_ma.occurs > 0 && ma.occurs == 0 {
-                        parent_ma.clone()
-                    } else {
-                        ma.clone()
+                let to_update = match vals_map.get(global_arg) {
+                    Some(&Some(ref parent_ma)) if parent_ma.occurs > 0 && ma.occurs == 0 => {
+                        Some(parent_ma.clone())
                     }
-                } else {
-                    ma.clone()
+                    Some(&None) if ma.occurs == 0 => None,
+                    _ => Some(ma.clone()),
                 };
                 vals_map.insert(global_arg, to_update);
+            } else if self.0.negated.contains(global_arg) {
+                vals_map.insert(global_arg, None);
             }
         }
+        // Chained subcommands only get the values of their parent, not those of their siblings
+        let parent_vals_map = vals_map.clone();
         if let Some(ref mut sc) = self.0.subcommand {
             let mut am = ArgMatcher(mem::replace(&mut sc.matches, ArgMatches::new()));
             am.fill_in_global_values(global_arg_vec, vals_map);
             mem::swap(&mut am.0, &mut sc.matches);
         }
+        for sc in &mut self.0.chained {
+            let mut sc_vals_map = parent_vals_map.clone();
+            let mut am = ArgMatcher(mem::replace(&mut sc.matches, ArgMatches::new()));
+            am.fill_in_global_values(global_arg_vec, &mut sc_vals_map);
+            mem::swap(&mut am.0, &mut sc.matches);
+        }
 
-        for (name, matched_arg) in vals_map.into_iter() {
-            self.0.args.insert(name, matched_arg.clone());
+        for (&name, matched_arg) in vals_map.iter() {
+            match *matched_arg {
+                Some(ref ma) => {
+                    self.0.negated.retain(|&n| n != name);
+                    self.0.args.insert(name, ma.clone());
+                }
+                None => {
+                    self.remove(name);
+                    if !self.0.negated.contains(&name) {
+                        self.0.negated.push(name);
+                    }
+                }
+            }
         }
     }
 
@@ -146,36 +168,74 @@ impl<'a> ArgMatcher<'a> {
 
     pub fn entry(&mut self, arg: &'a str) -> Entry<&'a str, MatchedArg> { self.0.args.entry(arg) }
 
-    pub fn subcommand(&mut self, sc: SubCommand<'a>) { self.0.subcommand = Some(Box::new(sc)); }
+    // Any subcommand after the first was chained to it
+    pub fn subcommand(&mut self, sc: SubCommand<'a>) {
+        if self.0.subcommand.is_some() {
+            self.0.chained.push(sc);
+        } else {
+            self.0.subcommand = Some(Box::new(sc));
+        }
+    }
 
     pub fn subcommand_name(&self) -> Option<&str> { self.0.subcommand_name() }
 
-    pub fn iter(&self) -> Iter<&str, MatchedArg> { self.0.args.iter() }
+    pub fn iter<'z>(&'z self) -> Iter<'z, &'a str, MatchedArg> { self.0.args.iter() }
 
-    pub fn inc_occurrence_of(&mut self, arg: &'a str) {
-        debugln!("ArgMatcher::inc_occurrence_of: arg={}", arg);
+    // Occurrences past `max` are accepted, but not counted
+    pub fn inc_occurrence_of(&mut self, arg: &'a str, max: Option<u64>) {
+        debugln!("ArgMatcher::inc_occurrence_of: arg={}, max={:?}", arg, max);
+        self.0.negated.retain(|&n| n != arg);
         if let Some(a) = self.get_mut(arg) {
-            a.occurs += 1;
+            match max {
+                Some(max) if a.occurs >= max => (),
+                _ => a.occurs += 1,
+            }
             return;
         }
         debugln!("ArgMatcher::inc_occurrence_of: first instance");
         self.insert(arg);
     }
 
+    // The occurrences of `arg` are taken back from its `groups` too
+    pub fn negate(&mut self, arg: &'a str, groups: &[&'a str]) {
+        debugln!("ArgMatcher::negate: arg={}, groups={:?}", arg, groups);
+        let occurs = self.get(arg).map_or(0, |a| a.occurs);
+        self.remove(arg);
+        for grp in groups {
+            let unused = match self.get_mut(grp) {
+                Some(g) => {
+                    g.occurs = g.occurs.saturating_sub(occurs);
+                    g.occurs == 0
+                }
+                None => false,
+            };
+            if unused {
+                self.remove(grp);
+            }
+        }
+        if !self.0.negated.contains(&arg) {
+            self.0.negated.push(arg);
+        }
+    }
+
     pub fn inc_occurrences_of(&mut self, args: &[&'a str]) {
         debugln!("ArgMatcher::inc_occurrences_of: args={:?}", args);
         for arg in args {
-            self.inc_occurrence_of(arg);
+            self.inc_occurrence_of(arg, None);
         }
     }
 
-    pub fn add_val_to(&mut self, arg: &'a str, val: &OsStr) {
+    pub fn add_val_to(&mut self, arg: &'a str, val: &OsStr, source: &ValueSource) {
         let ma = self.entry(arg).or_insert(MatchedArg {
             occurs: 0,
             indices: Vec::with_capacity(1),
             vals: Vec::with_capacity(1),
+            parsed: Vec::new(),
+            sources: Vec::with_capacity(1),
+            key_values: None,
         });
         ma.vals.push(val.to_owned());
+        ma.sources.push(source.clone());
     }
 
     pub fn add_index_to(&mut self, arg: &'a str, idx: usize) {
@@ -183,6 +243,9 @@ impl<'a> ArgMatcher<'a> {
             occurs: 0,
             indices: Vec::with_capacity(1),
             vals: Vec::new(),
+            parsed: Vec::new(),
+            sources: Vec::new(),
+            key_values: None,
         });
         ma.indices.push(idx);
     }
@@ -1,14 +1,21 @@
 // Std
+use std::any::Any;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::ffi::{OsStr, OsString};
-use std::iter::Map;
+use std::fmt::Display;
+use std::iter::{Chain, Map};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::option;
 use std::slice::Iter;
 
 // Internal
 use INVALID_UTF8;
-use args::MatchedArg;
+use args::{AnyValue, MatchedArg, ValueSource};
 use args::SubCommand;
+use errors::{Error, ErrorKind};
+use fmt::ColorWhen;
 
 /// Used to get information about the arguments that where supplied to the program at runtime by
 /// the user. New instances of this struct are obtained by using the [`App::get_matches`] family of
@@ -61,7 +68,12 @@ use args::SubCommand;
 pub struct ArgMatches<'a> {
     #[doc(hidden)] pub args: HashMap<&'a str, MatchedArg>,
     #[doc(hidden)] pub subcommand: Option<Box<SubCommand<'a>>>,
+    #[doc(hidden)] pub chained: Vec<SubCommand<'a>>,
+    #[doc(hidden)] pub plugin: Option<PathBuf>,
     #[doc(hidden)] pub usage: Option<String>,
+    #[doc(hidden)] pub color: ColorWhen,
+    #[doc(hidden)] pub negated: Vec<&'a str>,
+    #[doc(hidden)] pub decrements: Vec<(&'a str, &'a str)>,
 }
 
 impl<'a> Default for ArgMatches<'a> {
@@ -69,7 +81,12 @@ impl<'a> Default for ArgMatches<'a> {
         ArgMatches {
             args: HashMap::new(),
             subcommand: None,
+            chained: Vec::new(),
+            plugin: None,
             usage: None,
+            color: ColorWhen::Auto,
+            negated: Vec::new(),
+            decrements: Vec::new(),
         }
     }
 }
@@ -297,8 +314,145 @@ impl<'a> ArgMatches<'a> {
         None
     }
 
+    /// Gets a [`KeyValues`] struct which implements [`Iterator`] for the `key=value` pairs of an
+    /// argument using [`Arg::key_value_delimiter`], in the order they were given. If the argument
+    /// wasn't present at runtime it returns `None`.
+    ///
+    /// A key may be given more than once, so it's up to the caller to decide which one wins.
+    /// Collecting the pairs into a [`BTreeMap`] or [`HashMap`] keeps the last one.
+    ///
+    /// # Panics
+    ///
+    /// This method will [`panic!`] if the argument has no [`Arg::key_value_delimiter`], even when it
+    /// has no values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// use std::collections::BTreeMap;
+    ///
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::with_name("define")
+    ///         .short("D")
+    ///         .multiple(true)
+    ///         .number_of_values(1)
+    ///         .key_value_delimiter("="))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "-D", "DEBUG=1", "-D", "NAME=clap", "-D", "DEBUG=0"
+    ///     ]);
+    ///
+    /// let defines: BTreeMap<_, _> = m.key_values_of("define").unwrap().collect();
+    /// assert_eq!(defines["DEBUG"], "0");
+    /// assert_eq!(defines["NAME"], "clap");
+    /// ```
+    /// [`KeyValues`]: ./struct.KeyValues.html
+    /// [`Iterator`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html
+    /// [`Arg::key_value_delimiter`]: ./struct.Arg.html#method.key_value_delimiter
+    /// [`BTreeMap`]: https://doc.rust-lang.org/std/collections/struct.BTreeMap.html
+    /// [`HashMap`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html
+    /// [`panic!`]: https://doc.rust-lang.org/std/macro.panic!.html
+    pub fn key_values_of<S: AsRef<str>>(&'a self, name: S) -> Option<KeyValues<'a>> {
+        if let Some(arg) = self.args.get(name.as_ref()) {
+            // Only the values of arguments with a delimiter are split, even when there are none
+            let kvs = match arg.key_values {
+                Some(ref kvs) => kvs,
+                None => panic!(
+                    "The argument '{}' has no key_value_delimiter, use values_of instead",
+                    name.as_ref()
+                ),
+            };
+            fn to_str_pair(kv: &(String, String)) -> (&str, &str) { (&kv.0, &kv.1) }
+            let to_str_pair: ToKeyValue<'a> = to_str_pair; // coerce to fn pointer
+            return Some(KeyValues {
+                iter: kvs.iter().map(to_str_pair),
+            });
+        }
+        None
+    }
+
+    /// Gets the value of a specific argument, as parsed by its [`Arg::value_parser`]. If the
+    /// argument wasn't present at runtime it returns `None`.
+    ///
+    /// *NOTE:* If getting a value for an option or positional argument that allows multiples,
+    /// prefer [`ArgMatches::get_many`] as `ArgMatches::get` will only return the *first* value.
+    ///
+    /// # Panics
+    ///
+    /// This method will [`panic!`] if the argument has values but no [`Arg::value_parser`], or
+    /// if `T` isn't the type its value parser returns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myapp")
+    ///     .arg(Arg::with_name("jobs")
+    ///         .short("j")
+    ///         .takes_value(true)
+    ///         .default_value("1")
+    ///         .value_parser(|s: &str| s.parse::<usize>().map_err(|e| e.to_string())))
+    ///     .get_matches_from(vec!["myapp"]);
+    ///
+    /// assert_eq!(m.get::<usize>("jobs"), Some(&1));
+    /// ```
+    /// [`Arg::value_parser`]: ./struct.Arg.html#method.value_parser
+    /// [`ArgMatches::get_many`]: ./struct.ArgMatches.html#method.get_many
+    /// [`panic!`]: https://doc.rust-lang.org/std/macro.panic!.html
+    pub fn get<T: Any>(&self, name: &str) -> Option<&T> {
+        self.get_many(name).and_then(|mut vals| vals.next())
+    }
+
+    /// Gets a [`TypedValues`] struct which implements [`Iterator`] for the values of a specific
+    /// argument, as parsed by its [`Arg::value_parser`]. If the argument wasn't present at
+    /// runtime it returns `None`.
+    ///
+    /// # Panics
+    ///
+    /// This method will [`panic!`] if the argument has values but no [`Arg::value_parser`], or
+    /// if `T` isn't the type its value parser returns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::with_name("port")
+    ///         .multiple(true)
+    ///         .value_parser(|s: &str| s.parse::<u16>().map_err(|e| e.to_string())))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "80", "443"
+    ///     ]);
+    /// let ports: Vec<u16> = m.get_many::<u16>("port").unwrap().cloned().collect();
+    /// assert_eq!(ports, [80, 443]);
+    /// ```
+    /// [`TypedValues`]: ./struct.TypedValues.html
+    /// [`Iterator`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html
+    /// [`Arg::value_parser`]: ./struct.Arg.html#method.value_parser
+    /// [`panic!`]: https://doc.rust-lang.org/std/macro.panic!.html
+    pub fn get_many<T: Any>(&self, name: &str) -> Option<TypedValues<T>> {
+        if let Some(arg) = self.args.get(name) {
+            if arg.parsed.len() != arg.vals.len() {
+                panic!(
+                    "The argument '{}' has no value_parser, use value_of or values_of instead",
+                    name
+                );
+            }
+            return Some(TypedValues {
+                name: name.to_owned(),
+                iter: arg.parsed.iter(),
+                _ty: PhantomData,
+            });
+        }
+        None
+    }
+
     /// Returns `true` if an argument was present at runtime, otherwise `false`.
     ///
+    /// **NOTE:** Arguments which got their values from their environment variable, a
+    /// [`ValueProvider`] or their default value are present too, use
+    /// [`ArgMatches::value_source`] to know whether the user passed them explicitly.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -312,11 +466,11 @@ impl<'a> ArgMatches<'a> {
     ///
     /// assert!(m.is_present("debug"));
     /// ```
+    /// [`ValueProvider`]: ./trait.ValueProvider.html
+    /// [`ArgMatches::value_source`]: ./struct.ArgMatches.html#method.value_source
     pub fn is_present<S: AsRef<str>>(&self, name: S) -> bool {
-        if let Some(ref sc) = self.subcommand {
-            if sc.name == name.as_ref() {
-                return true;
-            }
+        if self.subcommands().any(|(sc_name, _)| sc_name == name.as_ref()) {
+            return true;
         }
         self.args.contains_key(name.as_ref())
     }
@@ -364,6 +518,154 @@ impl<'a> ArgMatches<'a> {
         self.args.get(name.as_ref()).map_or(0, |a| a.occurs)
     }
 
+    /// Returns the level of a counting flag such as `--verbose`, i.e. the number of times it was
+    /// used minus the number of times the flags which [`Arg::decrements`] it were used. The
+    /// result is negative when the latter were used more often, and `0` if none of them were
+    /// used at all.
+    ///
+    /// Each flag is only counted up to its [`Arg::max_occurrences`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::from_usage("-v, --verbose 'more output'").max_occurrences(3))
+    ///     .arg(Arg::from_usage("-q, --quiet... 'less output'").decrements("verbose"))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "-vvvvv", "-q"
+    ///     ]);
+    ///
+    /// assert_eq!(m.verbosity("verbose"), 2);
+    /// ```
+    /// [`Arg::decrements`]: ./struct.Arg.html#method.decrements
+    /// [`Arg::max_occurrences`]: ./struct.Arg.html#method.max_occurrences
+    pub fn verbosity<S: AsRef<str>>(&self, name: S) -> i64 {
+        let name = name.as_ref();
+        self.decrements
+            .iter()
+            .filter(|&&(_, target)| target == name)
+            .fold(self.occurrences_of(name) as i64, |level, &(dec, _)| {
+                level - self.occurrences_of(dec) as i64
+            })
+    }
+
+    /// Returns whether a flag is turned on or off: `Some(true)` if it was present at runtime,
+    /// `Some(false)` if a [negatable] flag was turned off with `--no-<long>` last, and `None` if
+    /// neither was used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let app = App::new("myprog")
+    ///     .arg(Arg::with_name("color")
+    ///         .long("color")
+    ///         .negatable(true));
+    ///
+    /// let m = app.clone().get_matches_from(vec!["myprog", "--no-color", "--color"]);
+    /// assert_eq!(m.flag_state("color"), Some(true));
+    ///
+    /// let m = app.clone().get_matches_from(vec!["myprog", "--color", "--no-color"]);
+    /// assert_eq!(m.flag_state("color"), Some(false));
+    ///
+    /// let m = app.get_matches_from(vec!["myprog"]);
+    /// assert_eq!(m.flag_state("color"), None);
+    /// ```
+    /// [negatable]: ./struct.Arg.html#method.negatable
+    pub fn flag_state<S: AsRef<str>>(&self, name: S) -> Option<bool> {
+        if self.args.contains_key(name.as_ref()) {
+            Some(true)
+        } else if self.negated.contains(&name.as_ref()) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Gets where an argument came from, i.e. the command line, its environment variable, a
+    /// [`ValueProvider`] or its default value. Returns `None` if the argument wasn't present at
+    /// runtime.
+    ///
+    /// Unlike [`ArgMatches::is_present`], this tells arguments the user passed explicitly apart
+    /// from the ones filled in afterwards. An argument which was used on the command line is
+    /// reported as such even if its values came from elsewhere, e.g. an option with
+    /// [`Arg::min_values(0)`] used without any value, which then gets its default value. Use
+    /// [`ArgMatches::value_sources_of`] for the source of each value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ValueSource};
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::with_name("color")
+    ///         .long("color")
+    ///         .takes_value(true)
+    ///         .default_value("auto"))
+    ///     .arg(Arg::with_name("output")
+    ///         .long("output")
+    ///         .takes_value(true))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "--output", "out.txt"
+    ///     ]);
+    ///
+    /// assert!(m.is_present("color"));
+    /// assert_eq!(m.value_source("color"), Some(ValueSource::DefaultValue));
+    /// assert_eq!(m.value_source("output"), Some(ValueSource::CommandLine));
+    /// assert_eq!(m.value_source("input"), None);
+    /// ```
+    /// [`ValueProvider`]: ./trait.ValueProvider.html
+    /// [`ArgMatches::is_present`]: ./struct.ArgMatches.html#method.is_present
+    /// [`Arg::min_values(0)`]: ./struct.Arg.html#method.min_values
+    /// [`ArgMatches::value_sources_of`]: ./struct.ArgMatches.html#method.value_sources_of
+    pub fn value_source<S: AsRef<str>>(&self, name: S) -> Option<ValueSource> {
+        self.args.get(name.as_ref()).map(|a| {
+            if a.occurs > 0 {
+                ValueSource::CommandLine
+            } else {
+                a.sources.first().cloned().unwrap_or(ValueSource::CommandLine)
+            }
+        })
+    }
+
+    /// Gets an [`Iterator`] over where each value of an argument came from, in the same order as
+    /// [`ArgMatches::values_of`]. This is mostly useful for [`ArgGroup`]s, whose values may come
+    /// from different arguments, and thus from different sources.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ArgGroup, ValueSource};
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::with_name("input")
+    ///         .long("input")
+    ///         .takes_value(true))
+    ///     .arg(Arg::with_name("stdin")
+    ///         .long("stdin")
+    ///         .takes_value(true)
+    ///         .default_value("-"))
+    ///     .group(ArgGroup::with_name("sources")
+    ///         .args(&["input", "stdin"])
+    ///         .multiple(true))
+    ///     .get_matches_from(vec![
+    ///         "myprog", "--input", "in.txt"
+    ///     ]);
+    ///
+    /// assert_eq!(m.values_of("sources").unwrap().collect::<Vec<_>>(), ["in.txt", "-"]);
+    /// assert_eq!(
+    ///     m.value_sources_of("sources").unwrap().collect::<Vec<_>>(),
+    ///     [&ValueSource::CommandLine, &ValueSource::DefaultValue]
+    /// );
+    /// ```
+    /// [`Iterator`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html
+    /// [`ArgMatches::values_of`]: ./struct.ArgMatches.html#method.values_of
+    /// [`ArgGroup`]: ./struct.ArgGroup.html
+    pub fn value_sources_of<S: AsRef<str>>(&'a self, name: S) -> Option<ValueSources<'a>> {
+        self.args.get(name.as_ref()).map(|arg| ValueSources {
+            iter: arg.sources.iter(),
+        })
+    }
+
     /// Gets the starting index of the argument in respect to all other arguments. Indices are
     /// similar to argv indices, but are not exactly 1:1.
     ///
@@ -615,16 +917,55 @@ impl<'a> ArgMatches<'a> {
     ///     assert_eq!(sub_m.value_of("opt"), Some("val"));
     /// }
     /// ```
+    ///
+    /// With [`AppSettings::ChainSubcommands`], this returns the [`ArgMatches`] of the first use
+    /// of the subcommand.
     /// [`Subcommand`]: ./struct.SubCommand.html
     /// [`App`]: ./struct.App.html
     /// [`ArgMatches`]: ./struct.ArgMatches.html
+    /// [`AppSettings::ChainSubcommands`]: ./enum.AppSettings.html#variant.ChainSubcommands
     pub fn subcommand_matches<S: AsRef<str>>(&self, name: S) -> Option<&ArgMatches<'a>> {
-        if let Some(ref s) = self.subcommand {
-            if s.name == name.as_ref() {
-                return Some(&s.matches);
-            }
+        self.subcommands()
+            .find(|&(sc_name, _)| sc_name == name.as_ref())
+            .map(|(_, m)| m)
+    }
+
+    /// Gets a [`SubCommands`] struct which implements [`Iterator`] for the names and
+    /// [`ArgMatches`] of the subcommands used at runtime, in the order they were used. There is
+    /// more than one only with [`AppSettings::ChainSubcommands`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, AppSettings, SubCommand};
+    /// let app_m = App::new("tool")
+    ///     .setting(AppSettings::ChainSubcommands)
+    ///     .subcommand(SubCommand::with_name("build"))
+    ///     .subcommand(SubCommand::with_name("test"))
+    ///     .get_matches_from(vec![
+    ///         "tool", "test", "build", "test"
+    ///     ]);
+    ///
+    /// let names: Vec<_> = app_m.subcommands().map(|(name, _)| name).collect();
+    /// assert_eq!(names, ["test", "build", "test"]);
+    /// ```
+    /// [`SubCommands`]: ./struct.SubCommands.html
+    /// [`Iterator`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html
+    /// [`ArgMatches`]: ./struct.ArgMatches.html
+    /// [`AppSettings::ChainSubcommands`]: ./enum.AppSettings.html#variant.ChainSubcommands
+    pub fn subcommands<'z>(&'z self) -> SubCommands<'a, 'z> {
+        fn to_pair<'a, 'z>(sc: &'z SubCommand<'a>) -> (&'z str, &'z ArgMatches<'a>) {
+            (&sc.name, &sc.matches)
+        }
+        let to_pair: ToPair<'a, 'z> = to_pair; // coerce to fn pointer
+        SubCommands {
+            iter: self.subcommand
+                .as_ref()
+                .map(|b| &**b)
+                .into_iter()
+                .chain(self.chained.iter())
+                .map(to_pair),
         }
-        None
     }
 
     /// Because [`Subcommand`]s are essentially "sub-[`App`]s" they have their own [`ArgMatches`]
@@ -663,7 +1004,8 @@ impl<'a> ArgMatches<'a> {
     /// ```
     ///
     /// Notice only one command per "level" may be used. You could not, for example, do `$ git
-    /// clone url push origin path`
+    /// clone url push origin path`, unless [`AppSettings::ChainSubcommands`] is set, in which
+    /// case this returns the name of the first one.
     ///
     /// # Examples
     ///
@@ -685,6 +1027,7 @@ impl<'a> ArgMatches<'a> {
     /// [`Subcommand`]: ./struct.SubCommand.html
     /// [`App`]: ./struct.App.html
     /// [`ArgMatches`]: ./struct.ArgMatches.html
+    /// [`AppSettings::ChainSubcommands`]: ./enum.AppSettings.html#variant.ChainSubcommands
     pub fn subcommand_name(&self) -> Option<&str> {
         self.subcommand.as_ref().map(|sc| &sc.name[..])
     }
@@ -742,6 +1085,23 @@ impl<'a> ArgMatches<'a> {
             .map_or(("", None), |sc| (&sc.name[..], Some(&sc.matches)))
     }
 
+    /// Returns the path of the executable to run when the subcommand used at runtime is a
+    /// plugin found with [`AppSettings::PluginSubcommands`], or `None` if it isn't.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, AppSettings, SubCommand};
+    /// let m = App::new("tool")
+    ///     .setting(AppSettings::PluginSubcommands)
+    ///     .subcommand(SubCommand::with_name("build"))
+    ///     .get_matches_from(vec!["tool", "build"]);
+    ///
+    /// assert!(m.plugin_path().is_none());
+    /// ```
+    /// [`AppSettings::PluginSubcommands`]: ./enum.AppSettings.html#variant.PluginSubcommands
+    pub fn plugin_path(&self) -> Option<&Path> { self.plugin.as_ref().map(|p| p.as_path()) }
+
     /// Returns a string slice of the usage statement for the [`App`] or [`SubCommand`]
     ///
     /// # Examples
@@ -757,6 +1117,46 @@ impl<'a> ArgMatches<'a> {
     /// [`Subcommand`]: ./struct.SubCommand.html
     /// [`App`]: ./struct.App.html
     pub fn usage(&self) -> &str { self.usage.as_ref().map_or("", |u| &u[..]) }
+
+    /// Creates an [`Error`] of the given [`ErrorKind`] which is formatted like the ones of clap
+    /// itself: the message is followed by the [usage] of the [`App`] or [`SubCommand`] and uses
+    /// its color settings. This is meant for the validators added with [`App::validator`]. The
+    /// names of the arguments which were used are kept in the [`ErrorContext::args`] of the error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let res = App::new("myprog")
+    ///     .arg(Arg::with_name("start").long("start").takes_value(true))
+    ///     .arg(Arg::with_name("end").long("end").takes_value(true))
+    ///     .validator(|m| match (m.value_of("start"), m.value_of("end")) {
+    ///         (Some(s), Some(e)) if s > e => Err(m.error(
+    ///             ErrorKind::ValueValidation,
+    ///             "'--start' must be before '--end'",
+    ///         )),
+    ///         _ => Ok(()),
+    ///     })
+    ///     .get_matches_from_safe(vec!["myprog", "--start", "b", "--end", "a"]);
+    ///
+    /// let err = res.unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::ValueValidation);
+    /// assert!(err.message.contains("'--start' must be before '--end'"));
+    /// assert!(err.message.contains("USAGE:"));
+    /// assert_eq!(err.context().args, ["end", "start"]);
+    /// ```
+    /// [`Error`]: ./struct.Error.html
+    /// [`ErrorKind`]: ./enum.ErrorKind.html
+    /// [`ErrorContext::args`]: ./struct.ErrorContext.html#structfield.args
+    /// [usage]: ./struct.ArgMatches.html#method.usage
+    /// [`App`]: ./struct.App.html
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [`App::validator`]: ./struct.App.html#method.validator
+    pub fn error<M: Display>(&self, kind: ErrorKind, message: M) -> Error {
+        let mut args: Vec<_> = self.args.keys().map(|&a| a.to_owned()).collect();
+        args.sort();
+        Error::app_validation(message, kind, args, self.usage(), self.color)
+    }
 }
 
 
@@ -817,6 +1217,142 @@ impl<'a> Default for Values<'a> {
     }
 }
 
+/// An iterator over the names and [`ArgMatches`] of the subcommands used at runtime, as
+/// returned by [`ArgMatches::subcommands`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, AppSettings, SubCommand};
+/// let m = App::new("myapp")
+///     .setting(AppSettings::ChainSubcommands)
+///     .subcommand(SubCommand::with_name("fetch"))
+///     .subcommand(SubCommand::with_name("merge"))
+///     .get_matches_from(vec!["myapp", "fetch", "merge"]);
+///
+/// let mut scs = m.subcommands();
+///
+/// assert_eq!(scs.next().map(|(name, _)| name), Some("fetch"));
+/// assert_eq!(scs.next().map(|(name, _)| name), Some("merge"));
+/// assert!(scs.next().is_none());
+/// ```
+/// [`ArgMatches`]: ./struct.ArgMatches.html
+/// [`ArgMatches::subcommands`]: ./struct.ArgMatches.html#method.subcommands
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct SubCommands<'a, 'z> {
+    iter: Map<Chain<option::IntoIter<&'z SubCommand<'a>>, Iter<'z, SubCommand<'a>>>, ToPair<'a, 'z>>,
+}
+
+type ToPair<'a, 'z> = fn(&'z SubCommand<'a>) -> (&'z str, &'z ArgMatches<'a>);
+
+impl<'a, 'z> Iterator for SubCommands<'a, 'z> {
+    type Item = (&'z str, &'z ArgMatches<'a>);
+
+    fn next(&mut self) -> Option<(&'z str, &'z ArgMatches<'a>)> { self.iter.next() }
+    fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
+}
+
+impl<'a, 'z> DoubleEndedIterator for SubCommands<'a, 'z> {
+    fn next_back(&mut self) -> Option<(&'z str, &'z ArgMatches<'a>)> { self.iter.next_back() }
+}
+
+/// An iterator for getting the `key=value` pairs out of an argument via the
+/// [`ArgMatches::key_values_of`] method.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg};
+/// let m = App::new("myapp")
+///     .arg(Arg::with_name("set")
+///         .long("set")
+///         .multiple(true)
+///         .key_value_delimiter(":"))
+///     .get_matches_from(vec!["myapp", "--set", "width:80", "height:24"]);
+///
+/// let mut pairs = m.key_values_of("set").unwrap();
+///
+/// assert_eq!(pairs.next(), Some(("width", "80")));
+/// assert_eq!(pairs.next(), Some(("height", "24")));
+/// assert_eq!(pairs.next(), None);
+/// ```
+/// [`ArgMatches::key_values_of`]: ./struct.ArgMatches.html#method.key_values_of
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct KeyValues<'a> {
+    iter: Map<Iter<'a, (String, String)>, ToKeyValue<'a>>,
+}
+
+type ToKeyValue<'a> = fn(&'a (String, String)) -> (&'a str, &'a str);
+
+impl<'a> Iterator for KeyValues<'a> {
+    type Item = (&'a str, &'a str);
+
+    fn next(&mut self) -> Option<(&'a str, &'a str)> { self.iter.next() }
+    fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
+}
+
+impl<'a> DoubleEndedIterator for KeyValues<'a> {
+    fn next_back(&mut self) -> Option<(&'a str, &'a str)> { self.iter.next_back() }
+}
+
+impl<'a> ExactSizeIterator for KeyValues<'a> {}
+
+/// An iterator for getting multiple typed values out of an argument via the
+/// [`ArgMatches::get_many`] method.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg};
+/// let m = App::new("myapp")
+///     .arg(Arg::with_name("num")
+///         .short("n")
+///         .multiple(true)
+///         .takes_value(true)
+///         .value_parser(|s: &str| s.parse::<i32>().map_err(|e| e.to_string())))
+///     .get_matches_from(vec!["myapp", "-n", "1", "-n", "2"]);
+///
+/// let mut nums = m.get_many::<i32>("num").unwrap();
+/// assert_eq!(nums.next(), Some(&1));
+/// assert_eq!(nums.next(), Some(&2));
+/// assert_eq!(nums.next(), None);
+/// ```
+/// [`ArgMatches::get_many`]: ./struct.ArgMatches.html#method.get_many
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct TypedValues<'a, T: 'a> {
+    name: String,
+    iter: Iter<'a, AnyValue>,
+    _ty: PhantomData<&'a T>,
+}
+
+impl<'a, T: Any> TypedValues<'a, T> {
+    fn downcast(&self, v: &'a AnyValue) -> &'a T {
+        match v.downcast_ref::<T>() {
+            Some(v) => v,
+            None => panic!(
+                "The value_parser of '{}' doesn't produce values of the requested type",
+                self.name
+            ),
+        }
+    }
+}
+
+impl<'a, T: Any> Iterator for TypedValues<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> { self.iter.next().map(|v| self.downcast(v)) }
+    fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
+}
+
+impl<'a, T: Any> DoubleEndedIterator for TypedValues<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> { self.iter.next_back().map(|v| self.downcast(v)) }
+}
+
+impl<'a, T: Any> ExactSizeIterator for TypedValues<'a, T> {}
+
 /// An iterator for getting multiple values out of an argument via the [`ArgMatches::values_of_os`]
 /// method. Usage of this iterator allows values which contain invalid UTF-8 code points unlike
 /// [`Values`].
@@ -919,6 +1455,56 @@ impl<'a> Default for Indices<'a> {
     }
 }
 
+/// An iterator over where the values of an argument came from, in the same order as its values,
+/// as returned by [`ArgMatches::value_sources_of`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, ValueSource};
+/// let m = App::new("myapp")
+///     .arg(Arg::with_name("output")
+///         .short("o")
+///         .multiple(true)
+///         .takes_value(true))
+///     .get_matches_from(vec!["myapp", "-o", "val1", "val2"]);
+///
+/// let mut sources = m.value_sources_of("output").unwrap();
+///
+/// assert_eq!(sources.next(), Some(&ValueSource::CommandLine));
+/// assert_eq!(sources.next(), Some(&ValueSource::CommandLine));
+/// assert_eq!(sources.next(), None);
+/// ```
+/// [`ArgMatches::value_sources_of`]: ./struct.ArgMatches.html#method.value_sources_of
+#[derive(Clone)]
+#[allow(missing_debug_implementations)]
+pub struct ValueSources<'a> {
+    iter: Iter<'a, ValueSource>,
+}
+
+impl<'a> Iterator for ValueSources<'a> {
+    type Item = &'a ValueSource;
+
+    fn next(&mut self) -> Option<&'a ValueSource> { self.iter.next() }
+    fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
+}
+
+impl<'a> DoubleEndedIterator for ValueSources<'a> {
+    fn next_back(&mut self) -> Option<&'a ValueSource> { self.iter.next_back() }
+}
+
+impl<'a> ExactSizeIterator for ValueSources<'a> {}
+
+/// Creates an empty iterator.
+impl<'a> Default for ValueSources<'a> {
+    fn default() -> Self {
+        static EMPTY: [ValueSource; 0] = [];
+        ValueSources {
+            iter: EMPTY[..].iter(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,424 @@
+// Std
+use std::ffi::OsStr;
+
+// Internal
+use args::ArgMatcher;
+
+/// A rule about which [arguments] (or [`ArgGroup`]s) may be used together, for the relationships
+/// which [`Arg::requires`], [`Arg::required_unless_one`], [`Arg::conflicts_with`] and friends
+/// can't express. A `Constraint` is built from the presence or values of arguments, combined with
+/// `and`, `or`, `not`, "exactly one of", `implies` and `unless`, then added to an [`App`] with
+/// [`App::constraint`].
+///
+/// The constraints of an [`App`] are checked once its required arguments are, and the first one
+/// which isn't satisfied results in an [`ErrorKind::ConstraintViolation`] error describing it,
+/// or with its own [message].
+///
+/// **NOTE:** Like for [`ArgMatches::is_present`], arguments which got their values from their
+/// environment variable, a [`ValueProvider`] or their default value are present.
+///
+/// # Examples
+///
+/// Exactly one of `--a` and `--b` must be used unless `--c` is, and `--d` requires `--e` or
+/// `--f`:
+///
+/// ```rust
+/// # use clap::{App, Arg, Constraint, ErrorKind};
+/// let app = App::new("prog")
+///     .args_from_usage(
+///         "-a 'a'
+///          -b 'b'
+///          -c 'c'
+///          -d 'd'
+///          -e 'e'
+///          -f 'f'")
+///     .constraint(Constraint::exactly_one(vec!["a", "b"]).unless("c"))
+///     .constraint(Constraint::present("d").implies(Constraint::any(vec!["e", "f"])));
+///
+/// assert!(app.clone().get_matches_from_safe(vec!["prog", "-a", "-d", "-f"]).is_ok());
+/// assert!(app.clone().get_matches_from_safe(vec!["prog", "-c"]).is_ok());
+///
+/// let res = app.get_matches_from_safe(vec!["prog", "-b", "-d"]);
+/// let err = res.unwrap_err();
+/// assert_eq!(err.kind, ErrorKind::ConstraintViolation);
+/// assert!(err.message.contains("-d requires any of (-e, -f)"));
+/// ```
+/// [arguments]: ./struct.Arg.html
+/// [`ArgGroup`]: ./struct.ArgGroup.html
+/// [`Arg::requires`]: ./struct.Arg.html#method.requires
+/// [`Arg::required_unless_one`]: ./struct.Arg.html#method.required_unless_one
+/// [`Arg::conflicts_with`]: ./struct.Arg.html#method.conflicts_with
+/// [`App`]: ./struct.App.html
+/// [`App::constraint`]: ./struct.App.html#method.constraint
+/// [`ErrorKind::ConstraintViolation`]: ./enum.ErrorKind.html#variant.ConstraintViolation
+/// [message]: ./struct.Constraint.html#method.message
+/// [`ArgMatches::is_present`]: ./struct.ArgMatches.html#method.is_present
+/// [`ValueProvider`]: ./trait.ValueProvider.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraint<'a> {
+    kind: Kind<'a>,
+    message: Option<&'a str>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind<'a> {
+    Present(&'a str),
+    Value(&'a str, &'a str),
+    Not(Box<Constraint<'a>>),
+    All(Vec<Constraint<'a>>),
+    Any(Vec<Constraint<'a>>),
+    ExactlyOne(Vec<Constraint<'a>>),
+    Implies(Box<Constraint<'a>>, Box<Constraint<'a>>),
+    Unless(Box<Constraint<'a>>, Box<Constraint<'a>>),
+}
+
+impl<'a> Constraint<'a> {
+    fn new(kind: Kind<'a>) -> Self {
+        Constraint {
+            kind: kind,
+            message: None,
+        }
+    }
+
+    /// Satisfied when the argument or [`ArgGroup`] with the given name is present. A name can
+    /// also be used where a `Constraint` is expected for the same effect.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, Constraint};
+    /// let res = App::new("prog")
+    ///     .arg(Arg::with_name("config").long("config").takes_value(true))
+    ///     .constraint(Constraint::present("config"))
+    ///     .get_matches_from_safe(vec!["prog"]);
+    ///
+    /// assert!(res.is_err());
+    /// ```
+    /// [`ArgGroup`]: ./struct.ArgGroup.html
+    pub fn present(name: &'a str) -> Self { Constraint::new(Kind::Present(name)) }
+
+    /// Satisfied when one of the values of the argument with the given name is `val`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, Constraint};
+    /// let res = App::new("prog")
+    ///     .arg(Arg::with_name("format").long("format").takes_value(true))
+    ///     .arg(Arg::with_name("template").long("template").takes_value(true))
+    ///     .constraint(Constraint::value("format", "custom").implies("template"))
+    ///     .get_matches_from_safe(vec!["prog", "--format", "custom"]);
+    ///
+    /// assert!(res.is_err());
+    /// ```
+    pub fn value(name: &'a str, val: &'a str) -> Self { Constraint::new(Kind::Value(name, val)) }
+
+    /// Satisfied when `c` isn't.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, Constraint};
+    /// let res = App::new("prog")
+    ///     .arg(Arg::with_name("mode").long("mode").takes_value(true))
+    ///     .constraint(Constraint::not(Constraint::value("mode", "legacy")))
+    ///     .get_matches_from_safe(vec!["prog", "--mode", "legacy"]);
+    ///
+    /// assert!(res.is_err());
+    /// ```
+    pub fn not<C: Into<Constraint<'a>>>(c: C) -> Self {
+        Constraint::new(Kind::Not(Box::new(c.into())))
+    }
+
+    /// Satisfied when all the given constraints are.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, Constraint};
+    /// let res = App::new("prog")
+    ///     .args_from_usage(
+    ///         "--user [user] 'user'
+    ///          --host [host] 'host'
+    ///          --password [pw] 'pw'")
+    ///     .constraint(Constraint::present("password")
+    ///         .implies(Constraint::all(vec!["user", "host"])))
+    ///     .get_matches_from_safe(vec!["prog", "--password", "hunter2", "--user", "me"]);
+    ///
+    /// assert!(res.is_err());
+    /// ```
+    pub fn all<I>(cs: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Constraint<'a>>,
+    {
+        Constraint::new(Kind::All(cs.into_iter().map(Into::into).collect()))
+    }
+
+    /// Satisfied when at least one of the given constraints is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, Constraint};
+    /// let res = App::new("prog")
+    ///     .args_from_usage(
+    ///         "--all 'all'
+    ///          --package [package] 'package'")
+    ///     .constraint(Constraint::any(vec!["all", "package"]))
+    ///     .get_matches_from_safe(vec!["prog"]);
+    ///
+    /// assert!(res.is_err());
+    /// ```
+    pub fn any<I>(cs: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Constraint<'a>>,
+    {
+        Constraint::new(Kind::Any(cs.into_iter().map(Into::into).collect()))
+    }
+
+    /// Satisfied when exactly one of the given constraints is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, Constraint};
+    /// let res = App::new("prog")
+    ///     .args_from_usage(
+    ///         "--json 'json'
+    ///          --yaml 'yaml'")
+    ///     .constraint(Constraint::exactly_one(vec!["json", "yaml"]))
+    ///     .get_matches_from_safe(vec!["prog", "--json", "--yaml"]);
+    ///
+    /// assert!(res.is_err());
+    /// ```
+    pub fn exactly_one<I>(cs: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Constraint<'a>>,
+    {
+        Constraint::new(Kind::ExactlyOne(cs.into_iter().map(Into::into).collect()))
+    }
+
+    /// Satisfied when both this constraint and `other` are.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::Constraint;
+    /// assert_eq!(
+    ///     Constraint::present("a").and("b"),
+    ///     Constraint::all(vec!["a", "b"])
+    /// );
+    /// ```
+    pub fn and<C: Into<Constraint<'a>>>(self, other: C) -> Self {
+        match self {
+            Constraint {
+                kind: Kind::All(mut cs),
+                message: None,
+            } => {
+                cs.push(other.into());
+                Constraint::new(Kind::All(cs))
+            }
+            c => Constraint::new(Kind::All(vec![c, other.into()])),
+        }
+    }
+
+    /// Satisfied when this constraint, `other` or both are.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::Constraint;
+    /// assert_eq!(
+    ///     Constraint::present("a").or("b"),
+    ///     Constraint::any(vec!["a", "b"])
+    /// );
+    /// ```
+    pub fn or<C: Into<Constraint<'a>>>(self, other: C) -> Self {
+        match self {
+            Constraint {
+                kind: Kind::Any(mut cs),
+                message: None,
+            } => {
+                cs.push(other.into());
+                Constraint::new(Kind::Any(cs))
+            }
+            c => Constraint::new(Kind::Any(vec![c, other.into()])),
+        }
+    }
+
+    /// Satisfied when `other` is whenever this constraint is, i.e. this constraint requires
+    /// `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Constraint};
+    /// let app = App::new("prog")
+    ///     .args_from_usage(
+    ///         "-d 'd'
+    ///          -e 'e'
+    ///          -f 'f'")
+    ///     .constraint(Constraint::present("d").implies(Constraint::any(vec!["e", "f"])));
+    ///
+    /// assert!(app.clone().get_matches_from_safe(vec!["prog", "-d", "-e"]).is_ok());
+    /// assert!(app.clone().get_matches_from_safe(vec!["prog", "-e"]).is_ok());
+    /// assert!(app.get_matches_from_safe(vec!["prog", "-d"]).is_err());
+    /// ```
+    pub fn implies<C: Into<Constraint<'a>>>(self, other: C) -> Self {
+        Constraint::new(Kind::Implies(Box::new(self), Box::new(other.into())))
+    }
+
+    /// Satisfied when this constraint or `other` is, i.e. this constraint only applies when
+    /// `other` isn't satisfied.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Constraint};
+    /// let app = App::new("prog")
+    ///     .args_from_usage(
+    ///         "-a 'a'
+    ///          -b 'b'
+    ///          -c 'c'")
+    ///     .constraint(Constraint::exactly_one(vec!["a", "b"]).unless("c"));
+    ///
+    /// assert!(app.clone().get_matches_from_safe(vec!["prog", "-a"]).is_ok());
+    /// assert!(app.clone().get_matches_from_safe(vec!["prog", "-c"]).is_ok());
+    /// assert!(app.get_matches_from_safe(vec!["prog"]).is_err());
+    /// ```
+    pub fn unless<C: Into<Constraint<'a>>>(self, other: C) -> Self {
+        Constraint::new(Kind::Unless(Box::new(self), Box::new(other.into())))
+    }
+
+    /// Sets the message of the error returned when this constraint isn't satisfied, instead of
+    /// describing it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Constraint};
+    /// let res = App::new("prog")
+    ///     .args_from_usage(
+    ///         "--start [start] 'start'
+    ///          --end [end] 'end'")
+    ///     .constraint(Constraint::present("end")
+    ///         .implies("start")
+    ///         .message("a range needs a start"))
+    ///     .get_matches_from_safe(vec!["prog", "--end", "9"]);
+    ///
+    /// assert!(res.unwrap_err().message.starts_with("error: a range needs a start"));
+    /// ```
+    pub fn message(mut self, msg: &'a str) -> Self {
+        self.message = Some(msg);
+        self
+    }
+
+    #[doc(hidden)]
+    pub fn custom_message(&self) -> Option<&'a str> { self.message }
+
+    #[doc(hidden)]
+    pub fn is_satisfied(&self, matcher: &ArgMatcher<'a>) -> bool {
+        match self.kind {
+            Kind::Present(name) => matcher.contains(name),
+            Kind::Value(name, val) => match matcher.get(name) {
+                Some(ma) => ma.vals.iter().any(|v| v == OsStr::new(val)),
+                None => false,
+            },
+            Kind::Not(ref c) => !c.is_satisfied(matcher),
+            Kind::All(ref cs) => cs.iter().all(|c| c.is_satisfied(matcher)),
+            Kind::Any(ref cs) => cs.iter().any(|c| c.is_satisfied(matcher)),
+            Kind::ExactlyOne(ref cs) => cs.iter().filter(|c| c.is_satisfied(matcher)).count() == 1,
+            Kind::Implies(ref c, ref other) => {
+                !c.is_satisfied(matcher) || other.is_satisfied(matcher)
+            }
+            Kind::Unless(ref c, ref other) => {
+                c.is_satisfied(matcher) || other.is_satisfied(matcher)
+            }
+        }
+    }
+
+    // The part of this constraint to blame when it isn't satisfied: the first unsatisfied one of
+    // an `and` without its own message, this constraint otherwise
+    #[doc(hidden)]
+    pub fn violated(&self, matcher: &ArgMatcher<'a>) -> Option<&Self> {
+        if self.is_satisfied(matcher) {
+            return None;
+        }
+        match *self {
+            Constraint {
+                kind: Kind::All(ref cs),
+                message: None,
+            } => cs.iter().filter_map(|c| c.violated(matcher)).next(),
+            _ => Some(self),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn arg_names(&self) -> Vec<&'a str> {
+        let mut names = vec![];
+        self.push_arg_names(&mut names);
+        names
+    }
+
+    fn push_arg_names(&self, names: &mut Vec<&'a str>) {
+        match self.kind {
+            Kind::Present(name) | Kind::Value(name, _) => if !names.contains(&name) {
+                names.push(name);
+            },
+            Kind::Not(ref c) => c.push_arg_names(names),
+            Kind::All(ref cs) | Kind::Any(ref cs) | Kind::ExactlyOne(ref cs) => for c in cs {
+                c.push_arg_names(names);
+            },
+            Kind::Implies(ref c, ref other) | Kind::Unless(ref c, ref other) => {
+                c.push_arg_names(names);
+                other.push_arg_names(names);
+            }
+        }
+    }
+
+    // Describes this constraint in words, `arg` gives how to refer to an argument or group
+    #[doc(hidden)]
+    pub fn describe<F: Fn(&str) -> String>(&self, arg: &F) -> String {
+        let list = |cs: &[Constraint<'a>]| {
+            cs.iter()
+                .map(|c| c.describe(arg))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match self.kind {
+            Kind::Present(name) => arg(name),
+            Kind::Value(name, val) => format!("{} is '{}'", arg(name), val),
+            Kind::Not(ref c) => format!("not {}", c.describe_operand(arg)),
+            Kind::All(ref cs) => format!("all of ({})", list(cs)),
+            Kind::Any(ref cs) => format!("any of ({})", list(cs)),
+            Kind::ExactlyOne(ref cs) => format!("exactly one of ({})", list(cs)),
+            Kind::Implies(ref c, ref other) => format!(
+                "{} requires {}",
+                c.describe_operand(arg),
+                other.describe_operand(arg)
+            ),
+            Kind::Unless(ref c, ref other) => format!(
+                "{} unless {}",
+                c.describe_operand(arg),
+                other.describe_operand(arg)
+            ),
+        }
+    }
+
+    // Like `describe`, in parentheses when it could be read more than one way
+    fn describe_operand<F: Fn(&str) -> String>(&self, arg: &F) -> String {
+        match self.kind {
+            Kind::Value(..) | Kind::Not(_) | Kind::Implies(..) | Kind::Unless(..) => {
+                format!("({})", self.describe(arg))
+            }
+            _ => self.describe(arg),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Constraint<'a> {
+    fn from(name: &'a str) -> Self { Constraint::present(name) }
+}
@@ -85,6 +85,8 @@ pub struct ArgGroup<'a> {
     #[doc(hidden)] pub requires: Option<Vec<&'a str>>,
     #[doc(hidden)] pub conflicts: Option<Vec<&'a str>>,
     #[doc(hidden)] pub multiple: bool,
+    #[doc(hidden)] pub min_args: Option<u64>,
+    #[doc(hidden)] pub max_args: Option<u64>,
 }
 
 impl<'a> ArgGroup<'a> {
@@ -106,6 +108,8 @@ impl<'a> ArgGroup<'a> {
             requires: None,
             conflicts: None,
             multiple: false,
+            min_args: None,
+            max_args: None,
         }
     }
 
@@ -229,6 +233,73 @@ impl<'a> ArgGroup<'a> {
         self
     }
 
+    /// Sets the minimum number of [arguments] of this group to be used when any of them is. This
+    /// allows more than one of them to be used, overriding [`ArgGroup::multiple`]. A [required]
+    /// group is displayed in the usage string as `<arg|arg2|arg3>{2,}`.
+    ///
+    /// **NOTE:** Like for [`Arg::min_values`], using none of the arguments is fine unless the
+    /// group is [required] too.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ArgGroup, ErrorKind};
+    /// let app = App::new("myprog")
+    ///     .arg(Arg::with_name("red").long("red"))
+    ///     .arg(Arg::with_name("green").long("green"))
+    ///     .arg(Arg::with_name("blue").long("blue"))
+    ///     .group(ArgGroup::with_name("mix")
+    ///         .args(&["red", "green", "blue"])
+    ///         .min_args(2));
+    ///
+    /// assert!(app.clone().get_matches_from_safe(vec!["myprog"]).is_ok());
+    /// assert!(app.clone().get_matches_from_safe(vec!["myprog", "--red", "--blue"]).is_ok());
+    ///
+    /// let res = app.get_matches_from_safe(vec!["myprog", "--red"]);
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+    /// ```
+    /// [arguments]: ./struct.Arg.html
+    /// [`ArgGroup::multiple`]: ./struct.ArgGroup.html#method.multiple
+    /// [required]: ./struct.ArgGroup.html#method.required
+    /// [`Arg::min_values`]: ./struct.Arg.html#method.min_values
+    pub fn min_args(mut self, n: u64) -> Self {
+        self.min_args = Some(n);
+        self
+    }
+
+    /// Sets the maximum number of [arguments] of this group which can be used together. This
+    /// allows more than one of them to be used, overriding [`ArgGroup::multiple`]. A [required]
+    /// group is displayed in the usage string as `<arg|arg2|arg3>{,2}`, or `<arg|arg2|arg3>{1,2}`
+    /// along with [`ArgGroup::min_args`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ArgGroup, ErrorKind};
+    /// let app = App::new("myprog")
+    ///     .arg(Arg::with_name("a").short("a"))
+    ///     .arg(Arg::with_name("b").short("b"))
+    ///     .arg(Arg::with_name("c").short("c"))
+    ///     .group(ArgGroup::with_name("inputs")
+    ///         .args(&["a", "b", "c"])
+    ///         .min_args(2)
+    ///         .max_args(2)
+    ///         .required(true));
+    ///
+    /// assert!(app.clone().get_matches_from_safe(vec!["myprog", "-a", "-c"]).is_ok());
+    ///
+    /// let res = app.get_matches_from_safe(vec!["myprog", "-a", "-b", "-c"]);
+    /// assert_eq!(res.unwrap_err().kind, ErrorKind::ArgumentConflict);
+    /// ```
+    /// [arguments]: ./struct.Arg.html
+    /// [`ArgGroup::multiple`]: ./struct.ArgGroup.html#method.multiple
+    /// [required]: ./struct.ArgGroup.html#method.required
+    /// [`ArgGroup::min_args`]: ./struct.ArgGroup.html#method.min_args
+    pub fn max_args(mut self, n: u64) -> Self {
+        self.max_args = Some(n);
+        self
+    }
+
     /// Sets the group as required or not. A required group will be displayed in the usage string
     /// of the application in the format `<arg|arg2|arg3>`. A required `ArgGroup` simply states
     /// that one argument from this group *must* be present at runtime (unless
@@ -430,12 +501,16 @@ impl<'a> Debug for ArgGroup<'a> {
              \trequired: {:?},\n\
              \trequires: {:?},\n\
              \tconflicts: {:?},\n\
+             \tmin_args: {:?},\n\
+             \tmax_args: {:?},\n\
              }}",
             self.name,
             self.args,
             self.required,
             self.requires,
-            self.conflicts
+            self.conflicts,
+            self.min_args,
+            self.max_args
         )
     }
 }
@@ -449,6 +524,8 @@ impl<'a, 'z> From<&'z ArgGroup<'a>> for ArgGroup<'a> {
             requires: g.requires.clone(),
             conflicts: g.conflicts.clone(),
             multiple: g.multiple,
+            min_args: g.min_args,
+            max_args: g.max_args,
         }
     }
 }
@@ -476,6 +553,8 @@ impl<'a> From<&'a BTreeMap<Yaml, Yaml>> for ArgGroup<'a> {
             a = match k.as_str().unwrap() {
                 "required" => a.required(v.as_bool().unwrap()),
                 "multiple" => a.multiple(v.as_bool().unwrap()),
+                "min_args" => yaml_to_u64!(a, v, min_args),
+                "max_args" => yaml_to_u64!(a, v, max_args),
                 "args" => yaml_vec_or_str!(v, a, arg),
                 "arg" => {
                     if let Some(ys) = v.as_str() {
@@ -545,7 +624,8 @@ mod test {
             .conflicts_with("c4")
             .requires("r1")
             .requires_all(&["r2", "r3"])
-            .requires("r4");
+            .requires("r4")
+            .max_args(3);
 
         let args = vec!["a1", "a4", "a2", "a3"];
         let reqs = vec!["r1", "r2", "r3", "r4"];
@@ -558,11 +638,15 @@ mod test {
              \trequired: {:?},\n\
              \trequires: {:?},\n\
              \tconflicts: {:?},\n\
+             \tmin_args: {:?},\n\
+             \tmax_args: {:?},\n\
              }}",
             args,
             true,
             Some(reqs),
-            Some(confs)
+            Some(confs),
+            None::<u64>,
+            Some(3)
         );
         assert_eq!(&*format!("{:?}", g), &*debug_str);
     }
@@ -609,7 +693,9 @@ requires:
 - r1
 - r2
 - r3
-- r4";
+- r4
+min_args: 2
+max_args: 3";
         let yml = &YamlLoader::load_from_str(g_yaml).expect("failed to load YAML file")[0];
         let g = ArgGroup::from_yaml(yml);
         let args = vec!["a1", "a4", "a2", "a3"];
@@ -618,6 +704,8 @@ requires:
         assert_eq!(g.args, args);
         assert_eq!(g.requires, Some(reqs));
         assert_eq!(g.conflicts, Some(confs));
+        assert_eq!(g.min_args, Some(2));
+        assert_eq!(g.max_args, Some(3));
     }
 }
 
@@ -630,6 +718,8 @@ impl<'a> Clone for ArgGroup<'a> {
             requires: self.requires.clone(),
             conflicts: self.conflicts.clone(),
             multiple: self.multiple,
+            min_args: self.min_args,
+            max_args: self.max_args,
         }
     }
 }
@@ -1,5 +1,11 @@
 // Std
+use std::any::Any;
 use std::ffi::OsString;
+use std::fmt;
+use std::sync::Arc;
+
+// Internal
+use args::ValueSource;
 
 #[doc(hidden)]
 #[derive(Debug, Clone)]
@@ -7,6 +13,9 @@ pub struct MatchedArg {
     #[doc(hidden)] pub occurs: u64,
     #[doc(hidden)] pub indices: Vec<usize>,
     #[doc(hidden)] pub vals: Vec<OsString>,
+    #[doc(hidden)] pub parsed: Vec<AnyValue>,
+    #[doc(hidden)] pub sources: Vec<ValueSource>,
+    #[doc(hidden)] pub key_values: Option<Vec<(String, String)>>,
 }
 
 impl Default for MatchedArg {
@@ -15,6 +24,9 @@ impl Default for MatchedArg {
             occurs: 1,
             indices: Vec::new(),
             vals: Vec::new(),
+            parsed: Vec::new(),
+            sources: Vec::new(),
+            key_values: None,
         }
     }
 }
@@ -22,3 +34,19 @@ impl Default for MatchedArg {
 impl MatchedArg {
     pub fn new() -> Self { MatchedArg::default() }
 }
+
+/// A value produced by an `Arg::value_parser`, with its type erased so it can be stored alongside
+/// the raw values.
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct AnyValue(Arc<Any + Send + Sync>);
+
+impl AnyValue {
+    pub fn new<T: Any + Send + Sync>(val: T) -> Self { AnyValue(Arc::new(val)) }
+
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> { self.0.downcast_ref::<T>() }
+}
+
+impl fmt::Debug for AnyValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str("AnyValue(..)") }
+}
@@ -2,10 +2,13 @@ pub use self::any_arg::{AnyArg, DispOrder};
 pub use self::arg::Arg;
 pub use self::arg_builder::{Base, FlagBuilder, OptBuilder, PosBuilder, Switched, Valued};
 pub use self::arg_matcher::ArgMatcher;
-pub use self::arg_matches::{ArgMatches, OsValues, Values};
+pub use self::constraint::Constraint;
+pub use self::arg_matches::{ArgMatches, KeyValues, OsValues, SubCommands, TypedValues,
+                             ValueSources, Values};
 pub use self::group::ArgGroup;
-pub use self::matched_arg::MatchedArg;
+pub use self::matched_arg::{AnyValue, MatchedArg};
 pub use self::settings::{ArgFlags, ArgSettings};
+pub use self::source::{ValueProvider, ValueSource};
 pub use self::subcommand::SubCommand;
 
 #[macro_use]
@@ -18,4 +21,6 @@ mod subcommand;
 mod arg_builder;
 mod matched_arg;
 mod group;
+mod constraint;
 pub mod settings;
+mod source;
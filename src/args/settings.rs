@@ -23,6 +23,7 @@ bitflags! {
         const HIDE_DEFAULT_VAL = 1 << 15;
         const CASE_INSENSITIVE = 1 << 16;
         const HIDE_ENV_VALS    = 1 << 17;
+        const NEGATABLE        = 1 << 18;
     }
 }
 
@@ -51,6 +52,7 @@ impl ArgFlags {
         Last => Flags::LAST,
         CaseInsensitive => Flags::CASE_INSENSITIVE,
         HideEnvValues => Flags::HIDE_ENV_VALS,
+        Negatable => Flags::NEGATABLE,
         HideDefaultValue => Flags::HIDE_DEFAULT_VAL
     }
 }
@@ -101,6 +103,8 @@ pub enum ArgSettings {
     CaseInsensitive,
     /// Hides ENV values in the help message
     HideEnvValues,
+    /// The flag may be turned off with `--no-<long>`
+    Negatable,
     #[doc(hidden)] RequiredUnlessAll,
     #[doc(hidden)] ValueDelimiterNotSet,
 }
@@ -127,6 +131,7 @@ impl FromStr for ArgSettings {
             "hidedefaultvalue" => Ok(ArgSettings::HideDefaultValue),
             "caseinsensitive" => Ok(ArgSettings::CaseInsensitive),
             "hideenvvalues" => Ok(ArgSettings::HideEnvValues),
+            "negatable" => Ok(ArgSettings::Negatable),
             _ => Err("unknown ArgSetting, cannot convert from str".to_owned()),
         }
     }
@@ -207,6 +212,10 @@ mod test {
             "hideenvvalues".parse::<ArgSettings>().unwrap(),
             ArgSettings::HideEnvValues
         );
+        assert_eq!(
+            "negatable".parse::<ArgSettings>().unwrap(),
+            ArgSettings::Negatable
+        );
         assert!("hahahaha".parse::<ArgSettings>().is_err());
     }
 }
@@ -0,0 +1,82 @@
+// Std
+use std::ffi::OsString;
+
+/// A source of argument values consulted after the command line and the environment, but before
+/// the default values, such as a configuration file. Providers are registered with
+/// [`App::value_provider`].
+///
+/// Only arguments which take a value, i.e. options and positional arguments, are looked up.
+/// Values from a provider go through the same validation as those from the command line, so
+/// [`Arg::possible_values`], [`Arg::validator`] and friends apply to them too.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, ValueProvider, ValueSource};
+/// # use std::collections::HashMap;
+/// # use std::ffi::OsString;
+/// struct Config(HashMap<&'static str, &'static str>);
+///
+/// impl ValueProvider for Config {
+///     fn name(&self) -> &str { "config.toml" }
+///
+///     fn values(&self, cmd: &[&str], arg: &str) -> Option<Vec<OsString>> {
+///         if !cmd.is_empty() {
+///             return None;
+///         }
+///         self.0.get(arg).map(|v| vec![OsString::from(v)])
+///     }
+/// }
+///
+/// let mut config = HashMap::new();
+/// config.insert("color", "always");
+///
+/// let m = App::new("prog")
+///     .value_provider(Config(config))
+///     .arg(Arg::with_name("color")
+///         .long("color")
+///         .takes_value(true)
+///         .default_value("auto"))
+///     .get_matches_from(vec!["prog"]);
+///
+/// assert_eq!(m.value_of("color"), Some("always"));
+/// assert_eq!(m.value_source("color"), Some(ValueSource::Provider("config.toml".into())));
+/// ```
+/// [`App::value_provider`]: ./struct.App.html#method.value_provider
+/// [`Arg::possible_values`]: ./struct.Arg.html#method.possible_values
+/// [`Arg::validator`]: ./struct.Arg.html#method.validator
+pub trait ValueProvider {
+    /// The name of this provider, such as the path of the configuration file, as reported by
+    /// [`ArgMatches::value_source`]
+    ///
+    /// [`ArgMatches::value_source`]: ./struct.ArgMatches.html#method.value_source
+    fn name(&self) -> &str;
+
+    /// Returns the values of the argument `arg` of the command at `cmd`, if this provider has any.
+    ///
+    /// `cmd` holds the names of the subcommands leading to the command being parsed, and is
+    /// empty for the top level app, i.e. for `git remote add` it would be `["remote", "add"]`.
+    fn values(&self, cmd: &[&str], arg: &str) -> Option<Vec<OsString>>;
+}
+
+/// Where the values of an argument came from, as reported by [`ArgMatches::value_source`]
+///
+/// [`ArgMatches::value_source`]: ./struct.ArgMatches.html#method.value_source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueSource {
+    /// The values were given on the command line
+    CommandLine,
+    /// The values were read from the environment variable set with [`Arg::env`]
+    ///
+    /// [`Arg::env`]: ./struct.Arg.html#method.env
+    EnvVariable,
+    /// The values were returned by the [`ValueProvider`] with this name
+    ///
+    /// [`ValueProvider`]: ./trait.ValueProvider.html
+    Provider(String),
+    /// The values are the ones set with [`Arg::default_value`] or [`Arg::default_value_if`]
+    ///
+    /// [`Arg::default_value`]: ./struct.Arg.html#method.default_value
+    /// [`Arg::default_value_if`]: ./struct.Arg.html#method.default_value_if
+    DefaultValue,
+}
@@ -3,6 +3,7 @@ use std::io::Write;
 
 // Internal
 use app::parser::Parser;
+use AppSettings as AS;
 use args::OptBuilder;
 use completions;
 
@@ -138,14 +139,20 @@ complete -F _{name} -o bashdefault -o default {name}
         let mut opts = String::new();
         for o in p.opts() {
             if let Some(l) = o.s.long {
+                let mut pattern = format!("--{}", l);
+                if p.is_set(AS::InferLongArgs) {
+                    for a in p.long_abbreviations(l) {
+                        pattern.push_str(&format!("|--{}", a));
+                    }
+                }
                 opts = format!(
                     "{}
-                --{})
+                {})
                     COMPREPLY=({})
                     return 0
                     ;;",
                     opts,
-                    l,
+                    pattern,
                     self.vals_for(o)
                 );
             }
@@ -188,6 +195,9 @@ complete -F _{name} -o bashdefault -o default {name}
             opts,
             longs!(p).fold(String::new(), |acc, l| format!("{} --{}", acc, l))
         );
+        for l in p.flags().filter_map(|f| f.negated_long()) {
+            opts = format!("{} --{}", opts, l);
+        }
         opts = format!(
             "{} {}",
             opts,
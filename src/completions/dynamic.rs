@@ -0,0 +1,296 @@
+// Std
+use std::cmp;
+use std::io::Write;
+
+// Internal
+use app::parser::Parser;
+use AppSettings as AS;
+use args::{ArgSettings, OptBuilder, Valued};
+use completions::Shell;
+use completions::nushell::NushellGen;
+
+pub struct DynamicGen<'a, 'b>
+where
+    'a: 'b,
+{
+    p: &'b Parser<'a, 'b>,
+}
+
+impl<'a, 'b> DynamicGen<'a, 'b> {
+    pub fn new(p: &'b Parser<'a, 'b>) -> Self { DynamicGen { p: p } }
+
+    // The scripts only forward the words of the command line and the index of the word being
+    // completed to `{name} --complete <CWORD> -- <WORDS>...`, the binary does the rest
+    pub fn generate<W: Write>(&self, for_shell: Shell, buf: &mut W) {
+        let name = self.p.meta.bin_name.as_ref().unwrap();
+        let fn_name = name.replace("-", "_");
+        let script = match for_shell {
+            Shell::Bash => format!(
+                "_{fn_name}() {{
+    local IFS=$'\\n'
+    COMPREPLY=( $(\"${{COMP_WORDS[0]}}\" --complete \"${{COMP_CWORD}}\" -- \"${{COMP_WORDS[@]}}\" 2>/dev/null | cut -f1) )
+}}
+
+complete -F _{fn_name} -o bashdefault -o default {name}
+",
+                name = name,
+                fn_name = fn_name
+            ),
+            Shell::Fish => format!(
+                "function __fish_{fn_name}_complete
+    set -l words (commandline -opc)
+    set -l cur (commandline -ct)
+    set -l candidates ($words[1] --complete (count $words) -- $words \"$cur\" 2>/dev/null)
+    if test (count $candidates) -gt 0
+        printf '%s\\n' $candidates
+    else
+        __fish_complete_path \"$cur\"
+    end
+end
+
+complete -c {name} -f -a '(__fish_{fn_name}_complete)'
+",
+                name = name,
+                fn_name = fn_name
+            ),
+            Shell::Zsh => format!(
+                "#compdef {name}
+
+_{fn_name}() {{
+    local -a candidates
+    candidates=(\"${{(@f)$(${{words[1]}} --complete $((CURRENT - 1)) -- \"${{words[@]}}\" 2>/dev/null)}}\")
+    candidates=(\"${{(@)${{(@)candidates//:/\\\\:}}//$'\\t'/:}}\")
+    if [[ -n \"${{candidates[1]}}\" ]]; then
+        _describe 'values' candidates
+    else
+        _files
+    fi
+}}
+
+_{fn_name} \"$@\"
+",
+                name = name,
+                fn_name = fn_name
+            ),
+            Shell::PowerShell => format!(
+                "Register-ArgumentCompleter -Native -CommandName '{name}' -ScriptBlock {{
+    param($wordToComplete, $commandAst, $cursorPosition)
+
+    $words = @($commandAst.CommandElements |
+        Where-Object {{ $_.Extent.StartOffset -lt $cursorPosition }} |
+        ForEach-Object {{ $_.ToString() }})
+    $cword = if ($wordToComplete -eq '') {{ $words.Count }} else {{ $words.Count - 1 }}
+    & $words[0] --complete $cword -- @words 2>$null | ForEach-Object {{
+        $value, $description = $_ -split \"`t\", 2
+        if (-not $description) {{ $description = $value }}
+        [System.Management.Automation.CompletionResult]::new($value, $value, 'ParameterValue', $description)
+    }}
+}}
+",
+                name = name
+            ),
+            Shell::Elvish => format!(
+                "use str
+
+set edit:completion:arg-completer[{name}] = {{|@words|
+    try {{
+        (external $words[0]) --complete (- (count $words) 1) -- $@words 2>/dev/null | from-lines | each {{|line|
+            var parts = [(str:split \"\\t\" $line)]
+            edit:complex-candidate $parts[0] &display=(str:join ' ' $parts)
+        }}
+    }} catch e {{
+    }}
+}}
+",
+                name = name
+            ),
+            // The signatures are still needed for Nushell to accept the flags, only the values are
+            // completed by the binary
+            Shell::Nushell => return NushellGen::dynamic(self.p).generate_to(buf),
+            Shell::__Nonexhaustive => unreachable!(),
+        };
+        w!(buf, script.as_bytes());
+    }
+}
+
+// Computes the candidates for the word at index `cword` of `words`, where `words[0]` is the
+// binary name. Each candidate is written on its own line, followed by a tab and the first line
+// of its help message when it has one.
+pub fn complete(p: &Parser, cword: usize, words: &[String]) -> String {
+    debugln!("complete: cword={}, words={:?}", cword, words);
+    let prev = if words.len() > 1 {
+        &words[1..cmp::max(cmp::min(cword, words.len()), 1)]
+    } else {
+        &[]
+    };
+    let cur = words.get(cword).map_or("", |w| &**w);
+    candidates(p, prev, cur)
+        .into_iter()
+        .map(|(c, h)| match h {
+            Some(h) => format!("{}\t{}", c, h),
+            None => c,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// A value to complete the current word with, and the help message to show next to it
+pub type Candidate = (String, Option<String>);
+
+// Computes the candidates for `cur`, given the words before it but after the binary name
+pub fn candidates(mut p: &Parser, prev: &[String], cur: &str) -> Vec<Candidate> {
+    // the option still waiting for values, and how many
+    let mut pending: Option<(&Valued, u64)> = None;
+    let mut pos = 1;
+    let mut trailing = false;
+    for w in prev {
+        if let Some((v, n)) = pending.take() {
+            // bash splits `--opt=val` into three words
+            if w == "=" {
+                pending = Some((v, n));
+            } else if n > 1 {
+                pending = Some((v, n - 1));
+            }
+            continue;
+        }
+        if trailing {
+            pos = next_pos(p, pos);
+        } else if w == "--" {
+            trailing = true;
+        } else if w.starts_with("--") {
+            if !w.contains('=') {
+                pending = find_opt(p, &w[2..]).map(|o| (&o.v, o.v.num_vals.unwrap_or(1)));
+            }
+        } else if w.starts_with('-') && w.len() > 1 {
+            // only an option at the end of a group of shorts still needs its value
+            for (i, c) in w[1..].char_indices() {
+                if let Some(o) = find_opt_by_short!(p, c) {
+                    if i + c.len_utf8() == w.len() - 1 {
+                        pending = Some((&o.v, o.v.num_vals.unwrap_or(1)));
+                    }
+                    break;
+                }
+            }
+        } else if let Some(sc) = find_subcmd!(p, &**w) {
+            p = &sc.p;
+            pos = 1;
+        } else {
+            pos = next_pos(p, pos);
+        }
+    }
+
+    if let Some((v, _)) = pending {
+        if cur == "=" {
+            return values(v, "", "=");
+        }
+        return values(v, cur, "");
+    }
+    if !trailing && cur.starts_with("--") && cur.contains('=') {
+        let (long, val) = cur.split_at(cur.find('=').unwrap());
+        return match find_opt(p, &long[2..]) {
+            Some(o) => values(&o.v, &val[1..], &cur[..long.len() + 1]),
+            None => vec![],
+        };
+    }
+    if !trailing && cur.starts_with('-') {
+        return switches(p, cur);
+    }
+
+    let mut c = vec![];
+    if !trailing {
+        for sc in p.subcommands.iter().filter(|sc| !sc.p.is_set(AS::Hidden)) {
+            let mut names = vec![&*sc.p.meta.name];
+            if let Some(ref aliases) = sc.p.meta.aliases {
+                names.extend(aliases.iter().filter(|a| a.1).map(|a| a.0));
+            }
+            for n in names.into_iter().filter(|n| n.starts_with(cur)) {
+                if !c.iter().any(|c: &Candidate| c.0 == n) {
+                    c.push((n.to_owned(), first_line(sc.p.meta.about)));
+                }
+            }
+        }
+        for (name, _) in p.plugins() {
+            if name.starts_with(cur) {
+                c.push((name, None));
+            }
+        }
+    }
+    if let Some(pb) = p.positionals.get(pos) {
+        if !pb.b.is_set(ArgSettings::Hidden) {
+            c.extend(values(&pb.v, cur, ""));
+        }
+    }
+    c
+}
+
+// Finds the option with the long `long`, which may be abbreviated if the parser allows it
+fn find_opt<'p, 'a, 'b>(p: &'p Parser<'a, 'b>, long: &str) -> Option<&'p OptBuilder<'a, 'b>> {
+    if !p.is_set(AS::InferLongArgs) {
+        return find_opt_by_long!(p, long);
+    }
+    let longs = p.long_candidates(long);
+    if longs.len() == 1 {
+        find_opt_by_long!(p, longs[0])
+    } else {
+        None
+    }
+}
+
+fn next_pos(p: &Parser, pos: usize) -> usize {
+    match p.positionals.get(pos) {
+        Some(pb) if pb.b.is_set(ArgSettings::Multiple) => pos,
+        _ => pos + 1,
+    }
+}
+
+fn values(v: &Valued, cur: &str, prefix: &str) -> Vec<Candidate> {
+    if let Some(ref f) = v.completer {
+        return f(cur)
+            .into_iter()
+            .map(|c| (format!("{}{}", prefix, c), None))
+            .collect();
+    }
+    v.possible_vals.as_ref().map_or(vec![], |pv| {
+        pv.iter()
+            .filter(|pv| pv.starts_with(cur))
+            .map(|pv| (format!("{}{}", prefix, pv), None))
+            .collect()
+    })
+}
+
+fn switches(p: &Parser, cur: &str) -> Vec<Candidate> {
+    let args = p.flags
+        .iter()
+        .map(|f| (&f.b, &f.s))
+        .chain(p.opts.iter().map(|o| (&o.b, &o.s)));
+    let mut c = vec![];
+    for (b, s) in args.filter(|&(b, _)| !b.is_set(ArgSettings::Hidden)) {
+        let mut names = vec![];
+        if let Some(l) = s.long {
+            names.push(format!("--{}", l));
+        }
+        if let Some(ref aliases) = s.aliases {
+            names.extend(aliases.iter().filter(|a| a.1).map(|a| format!("--{}", a.0)));
+        }
+        if let Some(sh) = s.short {
+            names.push(format!("-{}", sh));
+        }
+        for n in names.into_iter().filter(|n| n.starts_with(cur)) {
+            c.push((n, first_line(b.help)));
+        }
+    }
+    for f in p.flags.iter().filter(|f| !f.b.is_set(ArgSettings::Hidden)) {
+        if let Some(n) = f.negated_long().map(|l| format!("--{}", l)) {
+            if n.starts_with(cur) {
+                c.push((n, first_line(f.b.help)));
+            }
+        }
+    }
+    c
+}
+
+fn first_line(s: Option<&str>) -> Option<String> {
+    s.and_then(|s| s.lines().next())
+        .map(|l| l.trim().replace('\t', " "))
+        .and_then(|l| if l.is_empty() { None } else { Some(l) })
+}
@@ -0,0 +1,221 @@
+// Std
+use std::io::Write;
+
+// Internal
+use app::parser::Parser;
+use args::ArgSettings;
+use AppSettings as AS;
+use INTERNAL_ERROR_MSG;
+
+pub struct ElvishGen<'a, 'b>
+where
+    'a: 'b,
+{
+    p: &'b Parser<'a, 'b>,
+}
+
+impl<'a, 'b> ElvishGen<'a, 'b> {
+    pub fn new(p: &'b Parser<'a, 'b>) -> Self { ElvishGen { p: p } }
+
+    pub fn generate_to<W: Write>(&self, buf: &mut W) {
+        let bin_name = self.p.meta.bin_name.as_ref().unwrap();
+
+        let mut subcommands = String::new();
+        let mut options = String::new();
+        let mut completions = String::new();
+        generate_inner(
+            self.p,
+            "",
+            &mut subcommands,
+            &mut options,
+            &mut completions,
+        );
+
+        let result = format!(
+            r#"use str
+
+set edit:completion:arg-completer[{bin_name}] = {{|@words|
+    fn cand {{|text desc|
+        edit:complex-candidate $text &display=$text' '$desc
+    }}
+    var subcommands = [{subcommands}
+    ]
+    var options = [{options}
+    ]
+    var command = '{bin_name}'
+    var option = ''
+    for word $words[1..-1] {{
+        if (!=s $option '') {{
+            set option = ''
+        }} elif (has-key $options $command';'$word) {{
+            set option = $command';'$word
+        }} elif (has-key $subcommands $command';'$word) {{
+            set command = $subcommands[$command';'$word]
+        }}
+    }}
+    var completions = [{completions}
+    ]
+    if (!=s $option '') {{
+        all $options[$option]
+    }} else {{
+        $completions[$command]
+    }}
+}}
+"#,
+            bin_name = bin_name,
+            subcommands = or_empty_map(subcommands),
+            options = or_empty_map(options),
+            completions = completions
+        );
+
+        w!(buf, result.as_bytes());
+    }
+}
+
+// Escape string inside single quotes
+fn escape_string(string: &str) -> String { string.replace("'", "''") }
+
+// Elvish needs `&` to tell an empty map from an empty list
+fn or_empty_map(entries: String) -> String {
+    if entries.is_empty() {
+        String::from("&")
+    } else {
+        entries
+    }
+}
+
+fn get_tooltip<T: ToString>(help: Option<&str>, data: T) -> String {
+    match help.and_then(|h| h.lines().next()) {
+        Some(help) => escape_string(help.trim()),
+        _ => escape_string(&data.to_string()),
+    }
+}
+
+// Fills three maps keyed by the `;` separated path of each command:
+//
+// * `subcommands` resolves the path of a subcommand, or one of its aliases, to the path of the
+//   subcommand itself
+// * `options` holds the possible values of every option which takes a value, so the word after it
+//   isn't mistaken for a subcommand and gets completed with those values
+// * `completions` holds the candidates for the word being completed once the command is known
+fn generate_inner<'a, 'b>(
+    p: &Parser<'a, 'b>,
+    previous_command_name: &str,
+    subcommands: &mut String,
+    options: &mut String,
+    completions: &mut String,
+) {
+    debugln!("ElvishGen::generate_inner;");
+    let command_name = if previous_command_name.is_empty() {
+        p.meta.bin_name.as_ref().expect(INTERNAL_ERROR_MSG).clone()
+    } else {
+        format!("{};{}", previous_command_name, &p.meta.name)
+    };
+
+    let mut candidates = String::new();
+    let preamble = String::from("\n            cand ");
+
+    for option in p.opts() {
+        let values = option
+            .v
+            .possible_vals
+            .as_ref()
+            .map_or(String::new(), |pv| {
+                pv.iter()
+                    .map(|v| format!("'{}'", escape_string(v)))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            });
+        let mut names = vec![];
+        if let Some(s) = option.s.short {
+            names.push(format!("-{}", s));
+        }
+        if let Some(l) = option.s.long {
+            names.push(format!("--{}", l));
+            if p.is_set(AS::InferLongArgs) {
+                names.extend(p.long_abbreviations(l).iter().map(|a| format!("--{}", a)));
+            }
+        }
+        if let Some(ref aliases) = option.s.aliases {
+            names.extend(aliases.iter().map(|&(a, _)| format!("--{}", a)));
+        }
+        for name in names {
+            options.push_str(&format!(
+                "\n        &'{};{}'=[{}]",
+                escape_string(&command_name),
+                name,
+                values
+            ));
+        }
+    }
+
+    let switches = p.flags()
+        .map(|f| (&f.b, &f.s))
+        .chain(p.opts().map(|o| (&o.b, &o.s)))
+        .filter(|&(b, _)| !b.is_set(ArgSettings::Hidden));
+    for (b, s) in switches {
+        if let Some(data) = s.short {
+            let tooltip = get_tooltip(b.help, data);
+            candidates.push_str(&preamble);
+            candidates.push_str(&format!("-{} '{}'", data, tooltip));
+        }
+        if let Some(data) = s.long {
+            let tooltip = get_tooltip(b.help, data);
+            candidates.push_str(&preamble);
+            candidates.push_str(&format!("--{} '{}'", data, tooltip));
+        }
+        if let Some(ref aliases) = s.aliases {
+            for &(data, _) in aliases.iter().filter(|a| a.1) {
+                let tooltip = get_tooltip(b.help, data);
+                candidates.push_str(&preamble);
+                candidates.push_str(&format!("--{} '{}'", data, tooltip));
+            }
+        }
+    }
+    for f in p.flags().filter(|f| !f.b.is_set(ArgSettings::Hidden)) {
+        if let Some(data) = f.negated_long() {
+            let tooltip = get_tooltip(f.b.help, &data);
+            candidates.push_str(&preamble);
+            candidates.push_str(&format!("--{} '{}'", data, tooltip));
+        }
+    }
+
+    for subcommand in &p.subcommands {
+        let sc_path = format!("{};{}", command_name, &subcommand.p.meta.name);
+        let mut names = vec![(&*subcommand.p.meta.name, true)];
+        if let Some(ref aliases) = subcommand.p.meta.aliases {
+            names.extend(aliases.iter().cloned());
+        }
+        for (name, visible) in names {
+            subcommands.push_str(&format!(
+                "\n        &'{};{}'='{}'",
+                escape_string(&command_name),
+                escape_string(name),
+                escape_string(&sc_path)
+            ));
+            if visible && !subcommand.p.is_set(AS::Hidden) {
+                let tooltip = get_tooltip(subcommand.p.meta.about, name);
+                candidates.push_str(&preamble);
+                candidates.push_str(&format!("'{}' '{}'", escape_string(name), tooltip));
+            }
+        }
+    }
+
+    completions.push_str(&format!(
+        "
+        &'{}'= {{{}
+        }}",
+        escape_string(&command_name),
+        candidates
+    ));
+
+    for subcommand in &p.subcommands {
+        generate_inner(
+            &subcommand.p,
+            &command_name,
+            subcommands,
+            options,
+            completions,
+        );
+    }
+}
@@ -3,6 +3,7 @@ use std::io::Write;
 
 // Internal
 use app::parser::Parser;
+use AppSettings as AS;
 
 pub struct FishGen<'a, 'b>
 where
@@ -78,6 +79,24 @@ fn gen_fish_inner(root_command: &str, comp_gen: &FishGen, parent_cmds: &str, buf
         }
         buffer.push_str(template.as_str());
         buffer.push_str("\n");
+
+        // The abbreviations allowed by `AppSettings::InferLongArgs` aren't offered, only the
+        // values following them are
+        if let (true, Some(long), Some(vals)) = (
+            comp_gen.p.is_set(AS::InferLongArgs),
+            option.s.long,
+            option.v.possible_vals.as_ref(),
+        ) {
+            for abbrev in comp_gen.p.long_abbreviations(long) {
+                buffer.push_str(&format!(
+                    "complete -c {} -n \"__fish_using_command {} --{}\" -f -a \"{}\"\n",
+                    root_command,
+                    parent_cmds,
+                    abbrev,
+                    vals.join(" ")
+                ));
+            }
+        }
     }
 
     for flag in comp_gen.p.flags() {
@@ -88,6 +107,9 @@ fn gen_fish_inner(root_command: &str, comp_gen: &FishGen, parent_cmds: &str, buf
         if let Some(data) = flag.s.long {
             template.push_str(format!(" -l {}", data).as_str());
         }
+        if let Some(data) = flag.negated_long() {
+            template.push_str(format!(" -l {}", data).as_str());
+        }
         if let Some(data) = flag.b.help {
             template.push_str(format!(" -d '{}'", escape_string(data)).as_str());
         }
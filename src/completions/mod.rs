@@ -1,7 +1,10 @@
 #[macro_use]
 mod macros;
 mod bash;
+mod dynamic;
+mod elvish;
 mod fish;
+mod nushell;
 mod zsh;
 mod powershell;
 mod shell;
@@ -12,7 +15,10 @@ use std::io::Write;
 // Internal
 use app::parser::Parser;
 use self::bash::BashGen;
+pub use self::dynamic::{candidates, complete, DynamicGen};
+use self::elvish::ElvishGen;
 use self::fish::FishGen;
+use self::nushell::NushellGen;
 use self::zsh::ZshGen;
 use self::powershell::PowerShellGen;
 pub use self::shell::Shell;
@@ -33,6 +39,9 @@ impl<'a, 'b> ComplGen<'a, 'b> {
             Shell::Fish => FishGen::new(self.p).generate_to(buf),
             Shell::Zsh => ZshGen::new(self.p).generate_to(buf),
             Shell::PowerShell => PowerShellGen::new(self.p).generate_to(buf),
+            Shell::Elvish => ElvishGen::new(self.p).generate_to(buf),
+            Shell::Nushell => NushellGen::new(self.p).generate_to(buf),
+            Shell::__Nonexhaustive => unreachable!(),
         }
     }
 }
@@ -0,0 +1,185 @@
+// Std
+use std::io::Write;
+
+// Internal
+use app::parser::Parser;
+use args::{ArgSettings, Base, Valued};
+
+pub struct NushellGen<'a, 'b>
+where
+    'a: 'b,
+{
+    p: &'b Parser<'a, 'b>,
+    dynamic: bool,
+}
+
+impl<'a, 'b> NushellGen<'a, 'b> {
+    pub fn new(p: &'b Parser<'a, 'b>) -> Self {
+        NushellGen {
+            p: p,
+            dynamic: false,
+        }
+    }
+
+    // Values are completed by calling back into the binary instead of from the possible values
+    pub fn dynamic(p: &'b Parser<'a, 'b>) -> Self {
+        NushellGen {
+            p: p,
+            dynamic: true,
+        }
+    }
+
+    pub fn generate_to<W: Write>(&self, buf: &mut W) {
+        let bin_name = self.p.meta.bin_name.as_ref().unwrap();
+
+        let mut completers = String::new();
+        if self.dynamic {
+            completers.push_str(&format!(
+                r#"
+  def "nu-complete {name}" [context: string] {{
+    let words = ($context | split row -r '\s+')
+    ^{name} --complete (($words | length) - 1) -- ...$words | lines | each {{|line|
+      let parts = ($line | split row "\t")
+      {{ value: $parts.0, description: ($parts | skip 1 | str join " ") }}
+    }}
+  }}
+"#,
+                name = escape_string(bin_name)
+            ));
+        }
+        let mut externs = String::new();
+        self.generate_inner(self.p, bin_name, &mut completers, &mut externs);
+
+        let result = format!(
+            "module completions {{
+{completers}{externs}
+}}
+
+export use completions *
+",
+            completers = completers,
+            externs = externs
+        );
+
+        w!(buf, result.as_bytes());
+    }
+
+    // Writes an `extern` signature for the command at `path` and each of its subcommands, under
+    // their aliases too. Nushell rejects flags missing from the signature, so hidden arguments
+    // are included as well.
+    fn generate_inner(&self, p: &Parser, path: &str, completers: &mut String, externs: &mut String) {
+        debugln!("NushellGen::generate_inner;");
+        let mut params = vec![];
+
+        let switches = p.flags()
+            .map(|f| (&f.b, &f.s, None))
+            .chain(p.opts().map(|o| (&o.b, &o.s, Some(&o.v))));
+        for (b, s, v) in switches {
+            let ty = match v {
+                Some(v) => format!(": string{}", self.completer(path, b, v, completers)),
+                None => String::new(),
+            };
+            let mut longs: Vec<_> = s.long.into_iter().collect();
+            if let Some(ref aliases) = s.aliases {
+                longs.extend(aliases.iter().map(|a| a.0));
+            }
+            if longs.is_empty() {
+                if let Some(short) = s.short {
+                    params.push((format!("-{}{}", short, ty), b.help));
+                }
+            }
+            for (i, long) in longs.into_iter().enumerate() {
+                let short = match s.short {
+                    Some(short) if i == 0 => format!("(-{})", short),
+                    _ => String::new(),
+                };
+                params.push((format!("--{}{}{}", long, short, ty), b.help));
+            }
+        }
+        for f in p.flags() {
+            if let Some(no) = f.negated_long() {
+                params.push((format!("--{}", no), f.b.help));
+            }
+        }
+
+        for pos in p.positionals() {
+            let name = pos.b.name.replace("-", "_");
+            let completer = self.completer(path, &pos.b, &pos.v, completers);
+            let param = if pos.b.is_set(ArgSettings::Multiple) {
+                format!("...{}: string{}", name, completer)
+            } else if pos.b.is_set(ArgSettings::Required) {
+                format!("{}: string{}", name, completer)
+            } else {
+                format!("{}?: string{}", name, completer)
+            };
+            params.push((param, pos.b.help));
+        }
+
+        let width = params.iter().map(|p| p.0.len()).max().unwrap_or(0);
+        let mut sig = String::new();
+        if let Some(about) = first_line(p.meta.about) {
+            sig.push_str(&format!("\n  # {}", about));
+        }
+        sig.push_str(&format!("\n  export extern \"{}\" [", escape_string(path)));
+        for (param, help) in params {
+            match first_line(help) {
+                Some(help) => sig.push_str(&format!(
+                    "\n    {:width$}  # {}",
+                    param,
+                    help,
+                    width = width
+                )),
+                None => sig.push_str(&format!("\n    {}", param)),
+            }
+        }
+        sig.push_str("\n  ]\n");
+        externs.push_str(&sig);
+
+        for sc in &p.subcommands {
+            let mut names = vec![&*sc.p.meta.name];
+            if let Some(ref aliases) = sc.p.meta.aliases {
+                names.extend(aliases.iter().map(|a| a.0));
+            }
+            for name in names {
+                let sc_path = format!("{} {}", path, name);
+                self.generate_inner(&sc.p, &sc_path, completers, externs);
+            }
+        }
+    }
+
+    // Defines the command completing the values of an argument, if it has any, and returns the
+    // annotation referring to it
+    fn completer(&self, path: &str, b: &Base, v: &Valued, completers: &mut String) -> String {
+        if self.dynamic {
+            if v.completer.is_some() || v.possible_vals.is_some() {
+                let bin_name = self.p.meta.bin_name.as_ref().unwrap();
+                return format!("@\"nu-complete {}\"", escape_string(bin_name));
+            }
+            return String::new();
+        }
+        match v.possible_vals {
+            Some(ref pv) => {
+                let name = format!("nu-complete {} {}", path, b.name);
+                let values: Vec<_> = pv.iter()
+                    .map(|v| format!("\"{}\"", escape_string(v)))
+                    .collect();
+                completers.push_str(&format!(
+                    "\n  def \"{}\" [] {{\n    [ {} ]\n  }}\n",
+                    escape_string(&name),
+                    values.join(" ")
+                ));
+                format!("@\"{}\"", escape_string(&name))
+            }
+            None => String::new(),
+        }
+    }
+}
+
+// Escape string inside double quotes
+fn escape_string(string: &str) -> String { string.replace("\\", "\\\\").replace("\"", "\\\"") }
+
+fn first_line(s: Option<&str>) -> Option<String> {
+    s.and_then(|s| s.lines().next())
+        .map(|l| l.trim().to_owned())
+        .and_then(|l| if l.is_empty() { None } else { Some(l) })
+}
@@ -110,6 +110,12 @@ fn generate_inner<'a, 'b, 'p>(
             completions.push_str(format!("'--{}', '{}', {}, '{}')",
                                          data, data, "[CompletionResultType]::ParameterName", tooltip).as_str());
         }
+        if let Some(data) = flag.negated_long() {
+            let tooltip = get_tooltip(flag.b.help, &data);
+            completions.push_str(&preamble);
+            completions.push_str(format!("'--{}', '{}', {}, '{}')",
+                                         data, data, "[CompletionResultType]::ParameterName", tooltip).as_str());
+        }
     }
 
     for subcommand in &p.subcommands {
@@ -4,6 +4,9 @@ use std::str::FromStr;
 use std::fmt;
 
 /// Describes which shell to produce a completions file for
+///
+/// **NOTE:** More shells may be supported in the future, so matches on `Shell` need a wildcard
+/// arm.
 #[cfg_attr(feature = "lints", allow(enum_variant_names))]
 #[derive(Debug, Copy, Clone)]
 pub enum Shell {
@@ -15,11 +18,33 @@ pub enum Shell {
     Zsh,
     /// Generates a completion file for PowerShell
     PowerShell,
+    /// Generates a completion file for Elvish
+    Elvish,
+    /// Generates a completion module for Nushell
+    Nushell,
+    // Keeps matches on `Shell` from being exhaustive, so adding shells isn't a breaking change
+    #[doc(hidden)]
+    __Nonexhaustive,
 }
 
 impl Shell {
     /// A list of possible variants in `&'static str` form
-    pub fn variants() -> [&'static str; 4] { ["zsh", "bash", "fish", "powershell"] }
+    ///
+    /// **NOTE:** More shells may be supported in the future, so don't rely on the length of this
+    /// list. It can be handed to [`Arg::possible_values`] as is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{Arg, Shell};
+    /// Arg::with_name("shell")
+    ///     .possible_values(&Shell::variants())
+    /// # ;
+    /// ```
+    /// [`Arg::possible_values`]: ./struct.Arg.html#method.possible_values
+    pub fn variants() -> &'static [&'static str] {
+        &["zsh", "bash", "fish", "powershell", "elvish", "nushell"]
+    }
 }
 
 impl FromStr for Shell {
@@ -31,7 +56,11 @@ impl FromStr for Shell {
             "FISH" | _ if s.eq_ignore_ascii_case("fish") => Ok(Shell::Fish),
             "BASH" | _ if s.eq_ignore_ascii_case("bash") => Ok(Shell::Bash),
             "POWERSHELL" | _ if s.eq_ignore_ascii_case("powershell") => Ok(Shell::PowerShell),
-            _ => Err(String::from("[valid values: bash, fish, zsh, powershell]")),
+            "ELVISH" | _ if s.eq_ignore_ascii_case("elvish") => Ok(Shell::Elvish),
+            "NUSHELL" | _ if s.eq_ignore_ascii_case("nushell") => Ok(Shell::Nushell),
+            _ => Err(String::from(
+                "[valid values: bash, fish, zsh, powershell, elvish, nushell]",
+            )),
         }
     }
 }
@@ -43,6 +72,9 @@ impl fmt::Display for Shell {
             Shell::Fish => write!(f, "FISH"),
             Shell::Zsh => write!(f, "ZSH"),
             Shell::PowerShell => write!(f, "POWERSHELL"),
+            Shell::Elvish => write!(f, "ELVISH"),
+            Shell::Nushell => write!(f, "NUSHELL"),
+            Shell::__Nonexhaustive => unreachable!(),
         }
     }
 }
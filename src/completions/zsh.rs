@@ -8,6 +8,7 @@ use app::App;
 use app::parser::Parser;
 use args::{AnyArg, ArgSettings};
 use completions;
+use AppSettings as AS;
 use INTERNAL_ERROR_MSG;
 
 pub struct ZshGen<'a, 'b>
@@ -390,6 +391,19 @@ fn write_opts_of(p: &Parser) -> String {
 
             debugln!("write_opts_of:iter: Wrote...{}", &*l);
             ret.push(l);
+
+            // The abbreviations allowed by `AppSettings::InferLongArgs` are hidden with `!`, so
+            // they aren't offered but their values still get completed
+            if p.is_set(AS::InferLongArgs) {
+                for abbrev in p.long_abbreviations(long) {
+                    ret.push(format!(
+                        "'!--{arg}=[{help}]{possible_values}' \\",
+                        arg = abbrev,
+                        possible_values = pv,
+                        help = help
+                    ));
+                }
+            }
         }
     }
 
@@ -402,11 +416,11 @@ fn write_flags_of(p: &Parser) -> String {
     for f in p.flags() {
         debugln!("write_flags_of:iter: f={}", f.name());
         let help = f.help().map_or(String::new(), escape_help);
-        let mut conflicts = get_zsh_arg_conflicts!(p, f, INTERNAL_ERROR_MSG);
-        conflicts = if conflicts.is_empty() {
+        let raw_conflicts = get_zsh_arg_conflicts!(p, f, INTERNAL_ERROR_MSG);
+        let conflicts = if raw_conflicts.is_empty() {
             String::new()
         } else {
-            format!("({})", conflicts)
+            format!("({})", raw_conflicts)
         };
 
         let multiple = if f.is_set(ArgSettings::Multiple) {
@@ -427,7 +441,25 @@ fn write_flags_of(p: &Parser) -> String {
             ret.push(s);
         }
 
-        if let Some(long) = f.long() {
+        if let (Some(long), Some(no)) = (f.long(), f.negated_long()) {
+            // Both forms share one spec, and rule each other out unless the flag may be repeated
+            let conflicts = if multiple.is_empty() {
+                format!("({} --{} --{})", raw_conflicts, long, no).replace("( ", "(")
+            } else {
+                conflicts
+            };
+            let l = format!(
+                "'{conflicts}{multiple}'{{--{arg},--{no}}}'[{help}]' \\",
+                conflicts = conflicts,
+                multiple = multiple,
+                arg = long,
+                no = no,
+                help = help
+            );
+
+            debugln!("write_flags_of:iter: Wrote...{}", &*l);
+            ret.push(l);
+        } else if let Some(long) = f.long() {
             let l = format!(
                 "'{conflicts}{multiple}--{arg}[{help}]' \\",
                 conflicts = conflicts,
@@ -0,0 +1,920 @@
+// Internal
+use app::{App, AppSettings};
+use args::{Arg, ArgGroup, ArgSettings, Base, Switched, Valued};
+
+#[cfg(feature = "serde_defs")]
+mod schema;
+
+#[cfg(feature = "serde_defs")]
+pub use self::schema::DefError;
+
+/// An owned description of an [`App`], for CLIs which are only known at runtime (i.e. built from
+/// plugin manifests or configuration files).
+///
+/// [`App`] and [`Arg`] borrow every name and help message, which means the strings have to
+/// outlive them. An `AppDef` instead owns all of its strings, so it can be built from runtime
+/// data, stored, and returned from functions freely. When it's time to parse, an [`App`] which
+/// borrows from the `AppDef` is created with [`AppDef::to_app`]. That [`App`] can't outlive the
+/// `AppDef`, so return or store the definition rather than the [`App`] made from it.
+///
+/// Only what can be expressed as data is part of a definition. Closures such as
+/// [`App::validator`], [`Arg::validator`], [`Arg::value_parser`] and [`Arg::completer`] have to
+/// be added to the [`App`] or [`Arg`] afterwards, and are lost when an [`App`] is described with
+/// `AppDef::from`.
+///
+/// All fields are public and map one to one onto the [`App`] builder method of the same name, so
+/// definitions can be built either with the builder style methods or with struct update syntax.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{AppDef, ArgDef};
+/// fn plugin_cli(plugin: &str, opts: &[(String, String)]) -> AppDef {
+///     let mut app = AppDef::new(plugin).about(format!("Runs the {} plugin", plugin));
+///     for &(ref name, ref help) in opts {
+///         app = app.arg(ArgDef {
+///             long: Some(name.clone()),
+///             help: Some(help.clone()),
+///             takes_value: true,
+///             ..ArgDef::with_name(name.clone())
+///         });
+///     }
+///     app
+/// }
+///
+/// let def = plugin_cli("fmt", &[("width".into(), "Maximum line width".into())]);
+/// let m = def.to_app().get_matches_from(vec!["fmt", "--width", "80"]);
+/// assert_eq!(m.value_of("width"), Some("80"));
+/// ```
+/// [`App`]: ./struct.App.html
+/// [`Arg`]: ./struct.Arg.html
+/// [`AppDef::to_app`]: ./struct.AppDef.html#method.to_app
+/// [`App::validator`]: ./struct.App.html#method.validator
+/// [`Arg::validator`]: ./struct.Arg.html#method.validator
+/// [`Arg::value_parser`]: ./struct.Arg.html#method.value_parser
+/// [`Arg::completer`]: ./struct.Arg.html#method.completer
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AppDef {
+    /// See [`App::new`](./struct.App.html#method.new)
+    pub name: String,
+    /// See [`App::bin_name`](./struct.App.html#method.bin_name)
+    pub bin_name: Option<String>,
+    /// See [`App::version`](./struct.App.html#method.version)
+    pub version: Option<String>,
+    /// See [`App::long_version`](./struct.App.html#method.long_version)
+    pub long_version: Option<String>,
+    /// See [`App::author`](./struct.App.html#method.author)
+    pub author: Option<String>,
+    /// See [`App::about`](./struct.App.html#method.about)
+    pub about: Option<String>,
+    /// See [`App::long_about`](./struct.App.html#method.long_about)
+    pub long_about: Option<String>,
+    /// See [`App::before_help`](./struct.App.html#method.before_help)
+    pub before_help: Option<String>,
+    /// See [`App::after_help`](./struct.App.html#method.after_help)
+    pub after_help: Option<String>,
+    /// See [`App::usage`](./struct.App.html#method.usage)
+    pub usage: Option<String>,
+    /// See [`App::help`](./struct.App.html#method.help)
+    pub help: Option<String>,
+    /// See [`App::template`](./struct.App.html#method.template)
+    pub template: Option<String>,
+    /// See [`App::help_short`](./struct.App.html#method.help_short)
+    pub help_short: Option<String>,
+    /// See [`App::version_short`](./struct.App.html#method.version_short)
+    pub version_short: Option<String>,
+    /// See [`App::help_message`](./struct.App.html#method.help_message)
+    pub help_message: Option<String>,
+    /// See [`App::version_message`](./struct.App.html#method.version_message)
+    pub version_message: Option<String>,
+    /// See [`App::aliases`](./struct.App.html#method.aliases)
+    pub aliases: Vec<String>,
+    /// See [`App::visible_aliases`](./struct.App.html#method.visible_aliases)
+    pub visible_aliases: Vec<String>,
+    /// See [`App::display_order`](./struct.App.html#method.display_order)
+    pub display_order: Option<usize>,
+    /// See [`App::settings`](./struct.App.html#method.settings)
+    pub settings: Vec<AppSettings>,
+    /// See [`App::global_settings`](./struct.App.html#method.global_settings)
+    pub global_settings: Vec<AppSettings>,
+    /// See [`App::arg`](./struct.App.html#method.arg)
+    pub args: Vec<ArgDef>,
+    /// See [`App::group`](./struct.App.html#method.group)
+    pub groups: Vec<ArgGroupDef>,
+    /// See [`App::subcommand`](./struct.App.html#method.subcommand)
+    pub subcommands: Vec<AppDef>,
+}
+
+impl AppDef {
+    /// Creates a new, empty, definition with the given name.
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        AppDef {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets [`AppDef::version`](#structfield.version)
+    pub fn version<S: Into<String>>(mut self, ver: S) -> Self {
+        self.version = Some(ver.into());
+        self
+    }
+
+    /// Sets [`AppDef::author`](#structfield.author)
+    pub fn author<S: Into<String>>(mut self, author: S) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Sets [`AppDef::about`](#structfield.about)
+    pub fn about<S: Into<String>>(mut self, about: S) -> Self {
+        self.about = Some(about.into());
+        self
+    }
+
+    /// Adds a setting to [`AppDef::settings`](#structfield.settings)
+    pub fn setting(mut self, setting: AppSettings) -> Self {
+        self.settings.push(setting);
+        self
+    }
+
+    /// Adds a setting to [`AppDef::global_settings`](#structfield.global_settings)
+    pub fn global_setting(mut self, setting: AppSettings) -> Self {
+        self.global_settings.push(setting);
+        self
+    }
+
+    /// Adds an argument to [`AppDef::args`](#structfield.args)
+    pub fn arg(mut self, arg: ArgDef) -> Self {
+        self.args.push(arg);
+        self
+    }
+
+    /// Adds a group to [`AppDef::groups`](#structfield.groups)
+    pub fn group(mut self, group: ArgGroupDef) -> Self {
+        self.groups.push(group);
+        self
+    }
+
+    /// Adds a subcommand to [`AppDef::subcommands`](#structfield.subcommands)
+    pub fn subcommand(mut self, subcmd: AppDef) -> Self {
+        self.subcommands.push(subcmd);
+        self
+    }
+
+    /// Creates an [`App`] borrowing its strings from this definition.
+    ///
+    /// [`App`]: ./struct.App.html
+    pub fn to_app<'a>(&'a self) -> App<'a, 'a> { App::from(self) }
+}
+
+impl<'a> From<&'a AppDef> for App<'a, 'a> {
+    fn from(d: &'a AppDef) -> Self {
+        let mut a = App::new(&*d.name);
+        macro_rules! opt_str {
+            ($($i:ident),*) => {
+                $(
+                    if let Some(ref v) = d.$i {
+                        a = a.$i(&**v);
+                    }
+                )*
+            };
+        }
+        opt_str!(
+            bin_name,
+            version,
+            long_version,
+            author,
+            about,
+            long_about,
+            before_help,
+            after_help,
+            usage,
+            help,
+            template,
+            help_short,
+            version_short,
+            help_message,
+            version_message
+        );
+        for alias in &d.aliases {
+            a = a.alias(&**alias);
+        }
+        for alias in &d.visible_aliases {
+            a = a.visible_alias(&**alias);
+        }
+        if let Some(ord) = d.display_order {
+            a = a.display_order(ord);
+        }
+        for s in &d.settings {
+            a = a.setting(*s);
+        }
+        for s in &d.global_settings {
+            a = a.global_setting(*s);
+        }
+        for arg in &d.args {
+            a = a.arg(arg);
+        }
+        for group in &d.groups {
+            a = a.group(ArgGroup::from(group));
+        }
+        for sc in &d.subcommands {
+            a = a.subcommand(App::from(sc));
+        }
+        a
+    }
+}
+
+// The settings which may be set by users, i.e. not the internal or deprecated ones
+static USER_SETTINGS: &[AppSettings] = &[
+    AppSettings::AllArgsOverrideSelf,
+    AppSettings::AllowExternalSubcommands,
+    AppSettings::AllowInvalidUtf8,
+    AppSettings::AllowLeadingHyphen,
+    AppSettings::AllowMissingPositional,
+    AppSettings::AllowNegativeNumbers,
+    AppSettings::ArgRequiredElseHelp,
+    AppSettings::ArgsNegateSubcommands,
+    AppSettings::ChainSubcommands,
+    AppSettings::ColorAlways,
+    AppSettings::ColorAuto,
+    AppSettings::ColorNever,
+    AppSettings::ColoredHelp,
+    AppSettings::DeriveDisplayOrder,
+    AppSettings::DisableHelpSubcommand,
+    AppSettings::DisableVersion,
+    AppSettings::DontCollapseArgsInUsage,
+    AppSettings::DontDelimitTrailingValues,
+    AppSettings::DynamicCompletion,
+    AppSettings::GlobalVersion,
+    AppSettings::Hidden,
+    AppSettings::HidePossibleValuesInHelp,
+    AppSettings::InferLongArgs,
+    AppSettings::InferSubcommands,
+    AppSettings::Multicall,
+    AppSettings::NextLineHelp,
+    AppSettings::NoBinaryName,
+    AppSettings::PluginSubcommands,
+    AppSettings::ReportAllErrors,
+    AppSettings::ResponseFiles,
+    AppSettings::StrictUtf8,
+    AppSettings::SubcommandRequired,
+    AppSettings::SubcommandRequiredElseHelp,
+    AppSettings::SubcommandsNegateReqs,
+    AppSettings::TrailingVarArg,
+    AppSettings::UnifiedHelpMessage,
+    AppSettings::VersionlessSubcommands,
+    AppSettings::WaitOnError,
+];
+
+/// Describes an existing [`App`], i.e. in order to serialize it.
+///
+/// The auto generated help and version flags, and the `help` subcommand, are left out so the
+/// result can be turned back into an equivalent [`App`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, AppDef, Arg};
+/// let app = App::new("prog")
+///     .version("1.0")
+///     .arg(Arg::with_name("verbose").short("v").multiple(true));
+/// let def = AppDef::from(&app);
+/// assert_eq!(def.version, Some("1.0".into()));
+/// assert_eq!(def.args[0].short, Some("v".into()));
+/// ```
+/// [`App`]: ./struct.App.html
+impl<'a, 'b, 'z> From<&'z App<'a, 'b>> for AppDef {
+    fn from(app: &'z App<'a, 'b>) -> Self {
+        let p = &app.p;
+        let m = &p.meta;
+        let defaults = App::new("");
+        let owned = |s: Option<&str>| s.map(String::from);
+
+        let mut aliases = vec![];
+        let mut visible_aliases = vec![];
+        if let Some(ref als) = m.aliases {
+            for &(n, vis) in als {
+                if vis {
+                    visible_aliases.push(n.into());
+                } else {
+                    aliases.push(n.into());
+                }
+            }
+        }
+
+        let builtin = |n: &str| n == "hclap_help" || n == "vclap_version";
+        let mut switched: Vec<_> = p.flags
+            .iter()
+            .filter(|f| !builtin(f.b.name))
+            .map(|f| (f.s.unified_ord, arg_def(&f.b, Some(&f.s), None, None, &p.r_ifs)))
+            .chain(p.opts.iter().filter(|o| !builtin(o.b.name)).map(|o| {
+                (
+                    o.s.unified_ord,
+                    arg_def(&o.b, Some(&o.s), Some(&o.v), None, &p.r_ifs),
+                )
+            }))
+            .collect();
+        switched.sort_by_key(|&(ord, _)| ord);
+        let mut args: Vec<ArgDef> = switched.into_iter().map(|(_, a)| a).collect();
+        args.extend(
+            p.positionals
+                .values()
+                .map(|pos| arg_def(&pos.b, None, Some(&pos.v), Some(pos.index), &p.r_ifs)),
+        );
+        let groups = p.groups.iter().filter_map(|g| group_def(g, &args)).collect();
+
+        AppDef {
+            name: m.name.clone(),
+            bin_name: m.bin_name.clone(),
+            version: owned(m.version),
+            long_version: owned(m.long_version),
+            author: owned(m.author),
+            about: owned(m.about),
+            long_about: owned(m.long_about),
+            before_help: owned(m.pre_help),
+            after_help: owned(m.more_help),
+            usage: owned(m.usage_str),
+            help: owned(m.help_str),
+            template: owned(m.template),
+            help_short: p.help_short.map(|c| c.to_string()),
+            version_short: p.version_short.map(|c| c.to_string()),
+            help_message: owned(p.help_message),
+            version_message: owned(p.version_message),
+            aliases,
+            visible_aliases,
+            display_order: if m.disp_ord == 999 {
+                None
+            } else {
+                Some(m.disp_ord)
+            },
+            settings: USER_SETTINGS
+                .iter()
+                .cloned()
+                .filter(|&s| p.is_set(s) && !defaults.p.is_set(s) && !p.g_settings.is_set(s))
+                .collect(),
+            global_settings: USER_SETTINGS
+                .iter()
+                .cloned()
+                .filter(|&s| p.g_settings.is_set(s))
+                .collect(),
+            args,
+            groups,
+            subcommands: p.subcommands
+                .iter()
+                .filter(|sc| {
+                    !(sc.p.meta.name == "help" && p.is_set(AppSettings::NeedsSubcommandHelp))
+                })
+                .map(AppDef::from)
+                .collect(),
+        }
+    }
+}
+
+fn arg_def<'a, 'b>(
+    b: &Base<'a, 'b>,
+    s: Option<&Switched<'b>>,
+    v: Option<&Valued<'a, 'b>>,
+    index: Option<u64>,
+    r_ifs: &[(&'a str, &'b str, &'a str)],
+) -> ArgDef {
+    let names = |ns: &Option<Vec<&str>>| -> Vec<String> {
+        ns.iter().flat_map(|ns| ns.iter()).map(|&n| n.into()).collect()
+    };
+    let mut d = ArgDef {
+        help: b.help.map(String::from),
+        long_help: b.long_help.map(String::from),
+        index,
+        required: b.is_set(ArgSettings::Required),
+        takes_value: b.is_set(ArgSettings::TakesValue),
+        multiple: b.is_set(ArgSettings::Multiple),
+        max_occurrences: b.max_occurs,
+        decrements: b.decrements.map(String::from),
+        global: b.is_set(ArgSettings::Global),
+        hidden: b.is_set(ArgSettings::Hidden),
+        last: b.is_set(ArgSettings::Last),
+        next_line_help: b.is_set(ArgSettings::NextLineHelp),
+        empty_values: b.is_set(ArgSettings::EmptyValues),
+        allow_hyphen_values: b.is_set(ArgSettings::AllowLeadingHyphen),
+        require_equals: b.is_set(ArgSettings::RequireEquals),
+        require_delimiter: b.is_set(ArgSettings::RequireDelimiter),
+        case_insensitive: b.is_set(ArgSettings::CaseInsensitive),
+        hide_possible_values: b.is_set(ArgSettings::HidePossibleValues),
+        hide_default_value: b.is_set(ArgSettings::HideDefaultValue),
+        hide_env_values: b.is_set(ArgSettings::HideEnvValues),
+        negatable: b.is_set(ArgSettings::Negatable),
+        conflicts_with: names(&b.blacklist),
+        overrides_with: names(&b.overrides),
+        groups: names(&b.groups),
+        required_ifs: r_ifs
+            .iter()
+            .filter(|&&(_, _, n)| n == b.name)
+            .map(|&(arg, val, _)| (arg.into(), val.into()))
+            .collect(),
+        ..ArgDef::with_name(b.name)
+    };
+    if b.is_set(ArgSettings::RequiredUnlessAll) {
+        d.required_unless_all = names(&b.r_unless);
+    } else {
+        d.required_unless_one = names(&b.r_unless);
+    }
+    if let Some(ref reqs) = b.requires {
+        for &(val, name) in reqs {
+            match val {
+                Some(val) => d.requires_ifs.push((val.into(), name.into())),
+                None => d.requires.push(name.into()),
+            }
+        }
+    }
+    if let Some(s) = s {
+        d.short = s.short.map(|c| c.to_string());
+        d.long = s.long.map(String::from);
+        if let Some(ref als) = s.aliases {
+            for &(n, vis) in als {
+                if vis {
+                    d.visible_aliases.push(n.into());
+                } else {
+                    d.aliases.push(n.into());
+                }
+            }
+        }
+        if s.disp_ord != 999 {
+            d.display_order = Some(s.disp_ord);
+        }
+    }
+    if let Some(v) = v {
+        d.use_delimiter = b.is_set(ArgSettings::UseValueDelimiter) && v.val_delim.is_some();
+        d.value_delimiter = match v.val_delim {
+            Some(',') | None => None,
+            Some(c) => Some(c.to_string()),
+        };
+        d.key_value_delimiter = v.kv_delim.map(|c| c.to_string());
+        d.value_terminator = v.terminator.map(String::from);
+        d.number_of_values = v.num_vals;
+        d.max_values = v.max_vals;
+        d.min_values = v.min_vals;
+        d.value_names = v.val_names
+            .iter()
+            .flat_map(|vn| vn.values())
+            .map(|&n| n.into())
+            .collect();
+        d.possible_values = names(&v.possible_vals);
+        d.possible_keys = names(&v.possible_keys);
+        d.default_value = v.default_val.map(|dv| dv.to_string_lossy().into_owned());
+        d.default_value_ifs = v.default_vals_ifs
+            .iter()
+            .flat_map(|ifs| ifs.values())
+            .map(|&(arg, val, default)| {
+                (
+                    arg.into(),
+                    val.map(|v| v.to_string_lossy().into_owned()),
+                    default.to_string_lossy().into_owned(),
+                )
+            })
+            .collect();
+        d.env = v.env
+            .as_ref()
+            .map(|&(name, _)| name.to_string_lossy().into_owned());
+    }
+    d
+}
+
+fn group_def(g: &ArgGroup, args: &[ArgDef]) -> Option<ArgGroupDef> {
+    // Args which joined the group through `Arg::group` already record it themselves
+    let members: Vec<String> = g.args
+        .iter()
+        .filter(|&&n| {
+            !args.iter()
+                .any(|a| a.name == n && a.groups.iter().any(|ag| ag == g.name))
+        })
+        .map(|&n| n.into())
+        .collect();
+    let names = |ns: &Option<Vec<&str>>| -> Vec<String> {
+        ns.iter().flat_map(|ns| ns.iter()).map(|&n| n.into()).collect()
+    };
+    let d = ArgGroupDef {
+        name: g.name.into(),
+        args: members,
+        required: g.required,
+        multiple: g.multiple,
+        min_args: g.min_args,
+        max_args: g.max_args,
+        requires: names(&g.requires),
+        conflicts_with: names(&g.conflicts),
+    };
+    if d == ArgGroupDef::with_name(g.name) {
+        None
+    } else {
+        Some(d)
+    }
+}
+
+/// An owned description of an [`Arg`], see [`AppDef`] for details.
+///
+/// The boolean fields correspond to the [`Arg`] builder methods of the same name taking a `bool`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, ArgDef};
+/// let name = String::from("config");
+/// let def = ArgDef {
+///     short: Some("c".into()),
+///     takes_value: true,
+///     required: true,
+///     ..ArgDef::with_name(name)
+/// };
+/// let m = App::new("prog")
+///     .arg(&def)
+///     .get_matches_from(vec!["prog", "-c", "file.toml"]);
+/// assert_eq!(m.value_of("config"), Some("file.toml"));
+/// ```
+/// [`Arg`]: ./struct.Arg.html
+/// [`AppDef`]: ./struct.AppDef.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArgDef {
+    /// See [`Arg::with_name`](./struct.Arg.html#method.with_name)
+    pub name: String,
+    /// See [`Arg::short`](./struct.Arg.html#method.short)
+    pub short: Option<String>,
+    /// See [`Arg::long`](./struct.Arg.html#method.long)
+    pub long: Option<String>,
+    /// See [`Arg::aliases`](./struct.Arg.html#method.aliases)
+    pub aliases: Vec<String>,
+    /// See [`Arg::visible_aliases`](./struct.Arg.html#method.visible_aliases)
+    pub visible_aliases: Vec<String>,
+    /// See [`Arg::help`](./struct.Arg.html#method.help)
+    pub help: Option<String>,
+    /// See [`Arg::long_help`](./struct.Arg.html#method.long_help)
+    pub long_help: Option<String>,
+    /// See [`Arg::index`](./struct.Arg.html#method.index)
+    pub index: Option<u64>,
+    /// See [`Arg::required`](./struct.Arg.html#method.required)
+    pub required: bool,
+    /// See [`Arg::takes_value`](./struct.Arg.html#method.takes_value)
+    pub takes_value: bool,
+    /// See [`Arg::multiple`](./struct.Arg.html#method.multiple)
+    pub multiple: bool,
+    /// See [`Arg::max_occurrences`](./struct.Arg.html#method.max_occurrences)
+    pub max_occurrences: Option<u64>,
+    /// See [`Arg::decrements`](./struct.Arg.html#method.decrements)
+    pub decrements: Option<String>,
+    /// See [`Arg::global`](./struct.Arg.html#method.global)
+    pub global: bool,
+    /// See [`Arg::hidden`](./struct.Arg.html#method.hidden)
+    pub hidden: bool,
+    /// See [`Arg::last`](./struct.Arg.html#method.last)
+    pub last: bool,
+    /// See [`Arg::next_line_help`](./struct.Arg.html#method.next_line_help)
+    pub next_line_help: bool,
+    /// See [`Arg::empty_values`](./struct.Arg.html#method.empty_values), defaults to `true`
+    pub empty_values: bool,
+    /// See [`Arg::allow_hyphen_values`](./struct.Arg.html#method.allow_hyphen_values)
+    pub allow_hyphen_values: bool,
+    /// See [`Arg::require_equals`](./struct.Arg.html#method.require_equals)
+    pub require_equals: bool,
+    /// See [`Arg::use_delimiter`](./struct.Arg.html#method.use_delimiter)
+    pub use_delimiter: bool,
+    /// See [`Arg::require_delimiter`](./struct.Arg.html#method.require_delimiter)
+    pub require_delimiter: bool,
+    /// See [`Arg::case_insensitive`](./struct.Arg.html#method.case_insensitive)
+    pub case_insensitive: bool,
+    /// See [`Arg::hide_possible_values`](./struct.Arg.html#method.hide_possible_values)
+    pub hide_possible_values: bool,
+    /// See [`Arg::hide_default_value`](./struct.Arg.html#method.hide_default_value)
+    pub hide_default_value: bool,
+    /// See [`Arg::hide_env_values`](./struct.Arg.html#method.hide_env_values)
+    pub hide_env_values: bool,
+    /// See [`Arg::negatable`](./struct.Arg.html#method.negatable)
+    pub negatable: bool,
+    /// See [`Arg::value_delimiter`](./struct.Arg.html#method.value_delimiter)
+    pub value_delimiter: Option<String>,
+    /// See [`Arg::key_value_delimiter`](./struct.Arg.html#method.key_value_delimiter)
+    pub key_value_delimiter: Option<String>,
+    /// See [`Arg::value_terminator`](./struct.Arg.html#method.value_terminator)
+    pub value_terminator: Option<String>,
+    /// See [`Arg::number_of_values`](./struct.Arg.html#method.number_of_values)
+    pub number_of_values: Option<u64>,
+    /// See [`Arg::max_values`](./struct.Arg.html#method.max_values)
+    pub max_values: Option<u64>,
+    /// See [`Arg::min_values`](./struct.Arg.html#method.min_values)
+    pub min_values: Option<u64>,
+    /// See [`Arg::value_names`](./struct.Arg.html#method.value_names)
+    pub value_names: Vec<String>,
+    /// See [`Arg::possible_values`](./struct.Arg.html#method.possible_values)
+    pub possible_values: Vec<String>,
+    /// See [`Arg::possible_keys`](./struct.Arg.html#method.possible_keys)
+    pub possible_keys: Vec<String>,
+    /// See [`Arg::default_value`](./struct.Arg.html#method.default_value)
+    pub default_value: Option<String>,
+    /// See [`Arg::default_value_ifs`](./struct.Arg.html#method.default_value_ifs), as
+    /// `(arg, value, default)`
+    pub default_value_ifs: Vec<(String, Option<String>, String)>,
+    /// See [`Arg::env`](./struct.Arg.html#method.env)
+    pub env: Option<String>,
+    /// See [`Arg::requires_all`](./struct.Arg.html#method.requires_all)
+    pub requires: Vec<String>,
+    /// See [`Arg::requires_ifs`](./struct.Arg.html#method.requires_ifs), as `(value, arg)`
+    pub requires_ifs: Vec<(String, String)>,
+    /// See [`Arg::required_ifs`](./struct.Arg.html#method.required_ifs), as `(arg, value)`
+    pub required_ifs: Vec<(String, String)>,
+    /// See [`Arg::required_unless_one`](./struct.Arg.html#method.required_unless_one)
+    pub required_unless_one: Vec<String>,
+    /// See [`Arg::required_unless_all`](./struct.Arg.html#method.required_unless_all)
+    pub required_unless_all: Vec<String>,
+    /// See [`Arg::conflicts_with_all`](./struct.Arg.html#method.conflicts_with_all)
+    pub conflicts_with: Vec<String>,
+    /// See [`Arg::overrides_with_all`](./struct.Arg.html#method.overrides_with_all)
+    pub overrides_with: Vec<String>,
+    /// See [`Arg::groups`](./struct.Arg.html#method.groups)
+    pub groups: Vec<String>,
+    /// See [`Arg::display_order`](./struct.Arg.html#method.display_order)
+    pub display_order: Option<usize>,
+}
+
+impl Default for ArgDef {
+    fn default() -> Self {
+        ArgDef {
+            name: String::new(),
+            short: None,
+            long: None,
+            aliases: vec![],
+            visible_aliases: vec![],
+            help: None,
+            long_help: None,
+            index: None,
+            required: false,
+            takes_value: false,
+            multiple: false,
+            max_occurrences: None,
+            decrements: None,
+            global: false,
+            hidden: false,
+            last: false,
+            next_line_help: false,
+            empty_values: true,
+            allow_hyphen_values: false,
+            require_equals: false,
+            use_delimiter: false,
+            require_delimiter: false,
+            case_insensitive: false,
+            hide_possible_values: false,
+            hide_default_value: false,
+            hide_env_values: false,
+            negatable: false,
+            value_delimiter: None,
+            key_value_delimiter: None,
+            value_terminator: None,
+            number_of_values: None,
+            max_values: None,
+            min_values: None,
+            value_names: vec![],
+            possible_values: vec![],
+            possible_keys: vec![],
+            default_value: None,
+            default_value_ifs: vec![],
+            env: None,
+            requires: vec![],
+            requires_ifs: vec![],
+            required_ifs: vec![],
+            required_unless_one: vec![],
+            required_unless_all: vec![],
+            conflicts_with: vec![],
+            overrides_with: vec![],
+            groups: vec![],
+            display_order: None,
+        }
+    }
+}
+
+impl ArgDef {
+    /// Creates a new definition with the given name, everything else being the same as
+    /// [`Arg::with_name`].
+    ///
+    /// [`Arg::with_name`]: ./struct.Arg.html#method.with_name
+    pub fn with_name<S: Into<String>>(name: S) -> Self {
+        ArgDef {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets [`ArgDef::short`](#structfield.short)
+    pub fn short<S: Into<String>>(mut self, s: S) -> Self {
+        self.short = Some(s.into());
+        self
+    }
+
+    /// Sets [`ArgDef::long`](#structfield.long)
+    pub fn long<S: Into<String>>(mut self, l: S) -> Self {
+        self.long = Some(l.into());
+        self
+    }
+
+    /// Sets [`ArgDef::help`](#structfield.help)
+    pub fn help<S: Into<String>>(mut self, h: S) -> Self {
+        self.help = Some(h.into());
+        self
+    }
+
+    /// Sets [`ArgDef::takes_value`](#structfield.takes_value)
+    pub fn takes_value(mut self, tv: bool) -> Self {
+        self.takes_value = tv;
+        self
+    }
+
+    /// Sets [`ArgDef::required`](#structfield.required)
+    pub fn required(mut self, r: bool) -> Self {
+        self.required = r;
+        self
+    }
+
+    /// Sets [`ArgDef::multiple`](#structfield.multiple)
+    pub fn multiple(mut self, m: bool) -> Self {
+        self.multiple = m;
+        self
+    }
+
+    /// Creates an [`Arg`] borrowing its strings from this definition.
+    ///
+    /// [`Arg`]: ./struct.Arg.html
+    pub fn to_arg<'a>(&'a self) -> Arg<'a, 'a> { Arg::from(self) }
+}
+
+impl<'a> From<&'a ArgDef> for Arg<'a, 'a> {
+    fn from(d: &'a ArgDef) -> Self {
+        let mut a = Arg::with_name(&d.name);
+        macro_rules! opt {
+            ($($i:ident),*) => {
+                $(
+                    if let Some(ref v) = d.$i {
+                        a = a.$i(&**v);
+                    }
+                )*
+            };
+        }
+        macro_rules! opt_copy {
+            ($($i:ident),*) => {
+                $(
+                    if let Some(v) = d.$i {
+                        a = a.$i(v);
+                    }
+                )*
+            };
+        }
+        macro_rules! each {
+            ($($v:ident => $i:ident),*) => {
+                $(
+                    for v in &d.$v {
+                        a = a.$i(&**v);
+                    }
+                )*
+            };
+        }
+        macro_rules! flags {
+            ($($i:ident),*) => {
+                $(
+                    if d.$i {
+                        a = a.$i(true);
+                    }
+                )*
+            };
+        }
+        opt!(
+            short,
+            long,
+            help,
+            long_help,
+            value_delimiter,
+            key_value_delimiter,
+            value_terminator,
+            default_value,
+            env,
+            decrements
+        );
+        opt_copy!(
+            index,
+            max_occurrences,
+            number_of_values,
+            max_values,
+            min_values,
+            display_order
+        );
+        each!(
+            aliases => alias,
+            visible_aliases => visible_alias,
+            value_names => value_name,
+            possible_values => possible_value,
+            possible_keys => possible_key,
+            requires => requires,
+            conflicts_with => conflicts_with,
+            overrides_with => overrides_with,
+            groups => group
+        );
+        flags!(
+            required,
+            takes_value,
+            multiple,
+            global,
+            hidden,
+            last,
+            next_line_help,
+            allow_hyphen_values,
+            require_equals,
+            use_delimiter,
+            require_delimiter,
+            case_insensitive,
+            hide_possible_values,
+            hide_default_value,
+            hide_env_values,
+            negatable
+        );
+        if !d.empty_values {
+            a = a.empty_values(false);
+        }
+        for &(ref arg, ref val, ref default) in &d.default_value_ifs {
+            a = a.default_value_if(arg, val.as_ref().map(|v| &**v), default);
+        }
+        for &(ref val, ref arg) in &d.requires_ifs {
+            a = a.requires_if(val, arg);
+        }
+        for &(ref arg, ref val) in &d.required_ifs {
+            a = a.required_if(arg, val);
+        }
+        if !d.required_unless_one.is_empty() {
+            let names: Vec<&str> = d.required_unless_one.iter().map(|s| &**s).collect();
+            a = a.required_unless_one(&names);
+        }
+        if !d.required_unless_all.is_empty() {
+            let names: Vec<&str> = d.required_unless_all.iter().map(|s| &**s).collect();
+            a = a.required_unless_all(&names);
+        }
+        a
+    }
+}
+
+/// An owned description of an [`ArgGroup`], see [`AppDef`] for details.
+///
+/// [`ArgGroup`]: ./struct.ArgGroup.html
+/// [`AppDef`]: ./struct.AppDef.html
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ArgGroupDef {
+    /// See [`ArgGroup::with_name`](./struct.ArgGroup.html#method.with_name)
+    pub name: String,
+    /// See [`ArgGroup::args`](./struct.ArgGroup.html#method.args)
+    pub args: Vec<String>,
+    /// See [`ArgGroup::required`](./struct.ArgGroup.html#method.required)
+    pub required: bool,
+    /// See [`ArgGroup::multiple`](./struct.ArgGroup.html#method.multiple)
+    pub multiple: bool,
+    /// See [`ArgGroup::min_args`](./struct.ArgGroup.html#method.min_args)
+    pub min_args: Option<u64>,
+    /// See [`ArgGroup::max_args`](./struct.ArgGroup.html#method.max_args)
+    pub max_args: Option<u64>,
+    /// See [`ArgGroup::requires_all`](./struct.ArgGroup.html#method.requires_all)
+    pub requires: Vec<String>,
+    /// See [`ArgGroup::conflicts_with_all`](./struct.ArgGroup.html#method.conflicts_with_all)
+    pub conflicts_with: Vec<String>,
+}
+
+impl ArgGroupDef {
+    /// Creates a new, empty, definition with the given name.
+    pub fn with_name<S: Into<String>>(name: S) -> Self {
+        ArgGroupDef {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Adds an argument to [`ArgGroupDef::args`](#structfield.args)
+    pub fn arg<S: Into<String>>(mut self, name: S) -> Self {
+        self.args.push(name.into());
+        self
+    }
+
+    /// Sets [`ArgGroupDef::required`](#structfield.required)
+    pub fn required(mut self, r: bool) -> Self {
+        self.required = r;
+        self
+    }
+}
+
+impl<'a> From<&'a ArgGroupDef> for ArgGroup<'a> {
+    fn from(d: &'a ArgGroupDef) -> Self {
+        let mut g = ArgGroup::with_name(&d.name)
+            .required(d.required)
+            .multiple(d.multiple);
+        g.min_args = d.min_args;
+        g.max_args = d.max_args;
+        for arg in &d.args {
+            g = g.arg(arg);
+        }
+        for r in &d.requires {
+            g = g.requires(r);
+        }
+        for c in &d.conflicts_with {
+            g = g.conflicts_with(c);
+        }
+        g
+    }
+}
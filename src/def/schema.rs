@@ -0,0 +1,744 @@
+// Std
+use std::error::Error as StdError;
+use std::fmt;
+
+// Third Party
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+use serde_json;
+use toml;
+
+// Internal
+use INTERNAL_ERROR_MSG;
+use app::AppSettings;
+use def::{AppDef, ArgDef, ArgGroupDef};
+
+/// The error returned when an [`AppDef`] can't be loaded from, or saved to, a JSON or TOML
+/// document.
+///
+/// [`AppDef`]: ./struct.AppDef.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefError {
+    /// The path to the offending key, i.e. `subcommands[0].test.args[1].verbose.short`. Empty
+    /// when the document itself couldn't be parsed or written.
+    pub path: String,
+    /// What went wrong
+    pub message: String,
+}
+
+impl DefError {
+    fn new<P: Into<String>, M: Into<String>>(path: P, message: M) -> Self {
+        DefError {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    fn mismatch(v: &Value, expected: &str, path: &str) -> Self {
+        DefError::new(path, format!("expected {}, found {}", expected, v.kind()))
+    }
+}
+
+impl fmt::Display for DefError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+impl StdError for DefError {
+    fn description(&self) -> &str { &self.message }
+}
+
+impl AppDef {
+    /// Loads a definition from a JSON document, which uses the same layout as the YAML files
+    /// accepted by [`App::from_yaml`].
+    ///
+    /// Unlike [`App::from_yaml`], a malformed document isn't a [`panic!`] but a [`DefError`]
+    /// pointing at the offending key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::AppDef;
+    /// let def = AppDef::from_json(r#"{
+    ///     "name": "prog",
+    ///     "args": [
+    ///         { "verbose": { "short": "v", "multiple": true } },
+    ///         { "input": { "index": 1, "required": true } }
+    ///     ]
+    /// }"#).unwrap();
+    /// let m = def.to_app().get_matches_from(vec!["prog", "-vv", "file"]);
+    /// assert_eq!(m.occurrences_of("verbose"), 2);
+    ///
+    /// let err = AppDef::from_json(r#"{ "name": "prog", "args": [{ "v": { "short": 1 } }] }"#)
+    ///     .unwrap_err();
+    /// assert_eq!(err.path, "args[0].v.short");
+    /// ```
+    /// [`App::from_yaml`]: ./struct.App.html#method.from_yaml
+    /// [`panic!`]: https://doc.rust-lang.org/std/macro.panic!.html
+    /// [`DefError`]: ./struct.DefError.html
+    pub fn from_json(json: &str) -> Result<AppDef, DefError> {
+        let v: Value = serde_json::from_str(json).map_err(|e| DefError::new("", e.to_string()))?;
+        AppDef::from_value(&v)
+    }
+
+    /// Loads a definition from a TOML document, see [`AppDef::from_json`] for details.
+    ///
+    /// **NOTE:** TOML has no `null`, so a [`default_value_if`] without a value can't be expressed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::AppDef;
+    /// let def = AppDef::from_toml(r#"
+    /// name = "prog"
+    /// settings = ["ArgRequiredElseHelp"]
+    ///
+    /// [[args]]
+    /// config = { short = "c", takes_value = true }
+    /// "#).unwrap();
+    /// let m = def.to_app().get_matches_from(vec!["prog", "-c", "prog.toml"]);
+    /// assert_eq!(m.value_of("config"), Some("prog.toml"));
+    /// ```
+    /// [`AppDef::from_json`]: ./struct.AppDef.html#method.from_json
+    /// [`default_value_if`]: ./struct.Arg.html#method.default_value_if
+    pub fn from_toml(toml: &str) -> Result<AppDef, DefError> {
+        let v: Value = toml::from_str(toml).map_err(|e| DefError::new("", e.to_string()))?;
+        AppDef::from_value(&v)
+    }
+
+    /// Writes this definition as a pretty printed JSON document which can be loaded again with
+    /// [`AppDef::from_json`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, AppDef, Arg};
+    /// let app = App::new("prog").arg(Arg::with_name("debug").long("debug"));
+    /// let json = AppDef::from(&app).to_json();
+    /// assert_eq!(AppDef::from_json(&json).unwrap(), AppDef::from(&app));
+    /// ```
+    /// [`AppDef::from_json`]: ./struct.AppDef.html#method.from_json
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&app_to_value(self)).expect(INTERNAL_ERROR_MSG)
+    }
+
+    /// Writes this definition as a TOML document which can be loaded again with
+    /// [`AppDef::from_toml`].
+    ///
+    /// [`AppDef::from_toml`]: ./struct.AppDef.html#method.from_toml
+    pub fn to_toml(&self) -> Result<String, DefError> {
+        toml::to_string(&app_to_value(self)).map_err(|e| DefError::new("", e.to_string()))
+    }
+
+    fn from_value(v: &Value) -> Result<AppDef, DefError> {
+        let entries = as_map(v, "")?;
+        if entries.iter().any(|&(ref k, _)| k == "name") {
+            return app_from(String::new(), entries, "");
+        }
+        // Like subcommands, the top level may also be a table with the name as the only key
+        match entries.first() {
+            Some(&(ref name, ref body)) if entries.len() == 1 => {
+                app_from(name.clone(), as_map(body, name)?, name)
+            }
+            _ => Err(DefError::new("", "missing key 'name'")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for AppDef {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        AppDef::from_value(&Value::deserialize(d)?).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for AppDef {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        app_to_value(self).serialize(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for ArgDef {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let v = Value::deserialize(d)?;
+        let (name, body, path) = named(&v, "", "argument").map_err(de::Error::custom)?;
+        arg_from(name, body, &path).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for ArgDef {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        arg_to_value(self).serialize(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for ArgGroupDef {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        group_from(&Value::deserialize(d)?, "").map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for ArgGroupDef {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        group_to_value(self).serialize(s)
+    }
+}
+
+// A format agnostic document, so errors can be reported with the path to the key and tables keep
+// the order of the document (which is the order of the args)
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Seq(Vec<Value>),
+    Map(Vec<(String, Value)>),
+}
+
+impl Value {
+    fn kind(&self) -> &'static str {
+        match *self {
+            Value::Null => "null",
+            Value::Bool(_) => "a boolean",
+            Value::Int(_) => "an integer",
+            Value::Float(_) => "a float",
+            Value::Str(_) => "a string",
+            Value::Seq(_) => "an array",
+            Value::Map(_) => "a table",
+        }
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str("any value") }
+
+    fn visit_bool<E>(self, b: bool) -> Result<Value, E> { Ok(Value::Bool(b)) }
+
+    fn visit_i64<E>(self, i: i64) -> Result<Value, E> { Ok(Value::Int(i)) }
+
+    fn visit_u64<E>(self, u: u64) -> Result<Value, E> {
+        if u > i64::MAX as u64 {
+            Ok(Value::Float(u as f64))
+        } else {
+            Ok(Value::Int(u as i64))
+        }
+    }
+
+    fn visit_f64<E>(self, f: f64) -> Result<Value, E> { Ok(Value::Float(f)) }
+
+    fn visit_str<E>(self, s: &str) -> Result<Value, E> { Ok(Value::Str(s.into())) }
+
+    fn visit_string<E>(self, s: String) -> Result<Value, E> { Ok(Value::Str(s)) }
+
+    fn visit_unit<E>(self) -> Result<Value, E> { Ok(Value::Null) }
+
+    fn visit_none<E>(self) -> Result<Value, E> { Ok(Value::Null) }
+
+    fn visit_some<D: Deserializer<'de>>(self, d: D) -> Result<Value, D::Error> {
+        Value::deserialize(d)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut vals = vec![];
+        while let Some(v) = seq.next_element()? {
+            vals.push(v);
+        }
+        Ok(Value::Seq(vals))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut entries = vec![];
+        while let Some(e) = map.next_entry()? {
+            entries.push(e);
+        }
+        Ok(Value::Map(entries))
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        d.deserialize_any(ValueVisitor)
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            Value::Null => s.serialize_none(),
+            Value::Bool(b) => s.serialize_bool(b),
+            Value::Int(i) => s.serialize_i64(i),
+            Value::Float(f) => s.serialize_f64(f),
+            Value::Str(ref st) => s.serialize_str(st),
+            Value::Seq(ref vals) => {
+                let mut seq = s.serialize_seq(Some(vals.len()))?;
+                for v in vals {
+                    seq.serialize_element(v)?;
+                }
+                seq.end()
+            }
+            Value::Map(ref entries) => {
+                let mut map = s.serialize_map(Some(entries.len()))?;
+                for &(ref k, ref v) in entries {
+                    map.serialize_entry(k, v)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+fn child(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.into()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn as_map<'v>(v: &'v Value, path: &str) -> Result<&'v [(String, Value)], DefError> {
+    match *v {
+        Value::Map(ref entries) => Ok(entries),
+        // i.e. `- verbose:` in YAML, an arg without any settings
+        Value::Null => Ok(&[]),
+        _ => Err(DefError::mismatch(v, "a table", path)),
+    }
+}
+
+fn as_seq<'v>(v: &'v Value, path: &str) -> Result<&'v [Value], DefError> {
+    match *v {
+        Value::Seq(ref vals) => Ok(vals),
+        _ => Err(DefError::mismatch(v, "an array", path)),
+    }
+}
+
+fn as_str(v: &Value, path: &str) -> Result<String, DefError> {
+    match *v {
+        Value::Str(ref s) => Ok(s.clone()),
+        _ => Err(DefError::mismatch(v, "a string", path)),
+    }
+}
+
+fn as_opt_str(v: &Value, path: &str) -> Result<Option<String>, DefError> {
+    match *v {
+        Value::Null => Ok(None),
+        Value::Str(ref s) => Ok(Some(s.clone())),
+        _ => Err(DefError::mismatch(v, "a string or null", path)),
+    }
+}
+
+fn as_bool(v: &Value, path: &str) -> Result<bool, DefError> {
+    match *v {
+        Value::Bool(b) => Ok(b),
+        _ => Err(DefError::mismatch(v, "a boolean", path)),
+    }
+}
+
+fn as_u64(v: &Value, path: &str) -> Result<u64, DefError> {
+    match *v {
+        Value::Int(i) if i >= 0 => Ok(i as u64),
+        _ => Err(DefError::mismatch(v, "a positive integer", path)),
+    }
+}
+
+// A single string is accepted as a shorthand for an array with one element
+fn str_or_seq(v: &Value, path: &str) -> Result<Vec<(String, String)>, DefError> {
+    match *v {
+        Value::Str(ref s) => Ok(vec![(s.clone(), path.into())]),
+        Value::Seq(ref vals) => vals.iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let path = format!("{}[{}]", path, i);
+                as_str(v, &path).map(|s| (s, path))
+            })
+            .collect(),
+        _ => Err(DefError::mismatch(v, "a string or an array of strings", path)),
+    }
+}
+
+fn strs(v: &Value, path: &str) -> Result<Vec<String>, DefError> {
+    Ok(str_or_seq(v, path)?.into_iter().map(|(s, _)| s).collect())
+}
+
+fn settings(v: &Value, path: &str) -> Result<Vec<AppSettings>, DefError> {
+    str_or_seq(v, path)?
+        .into_iter()
+        .map(|(s, path)| {
+            s.parse()
+                .map_err(|_| DefError::new(path, format!("unknown setting '{}'", s)))
+        })
+        .collect()
+}
+
+// i.e. `[["flag", null, "some"], ["other", "val", "else"]]`
+fn tuples<'v>(v: &'v Value, len: usize, path: &str) -> Result<Vec<(&'v [Value], String)>, DefError> {
+    as_seq(v, path)?
+        .iter()
+        .enumerate()
+        .map(|(i, t)| {
+            let path = format!("{}[{}]", path, i);
+            match *t {
+                Value::Seq(ref vals) if vals.len() == len => Ok((&**vals, path)),
+                _ => Err(DefError::mismatch(
+                    t,
+                    &format!("an array of {} elements", len),
+                    &path,
+                )),
+            }
+        })
+        .collect()
+}
+
+// The name, the entries and the path of the entries
+type Named<'v> = (String, &'v [(String, Value)], String);
+
+// Args, groups and subcommands are tables with their name as the only key
+fn named<'v>(v: &'v Value, path: &str, what: &str) -> Result<Named<'v>, DefError> {
+    let entries = as_map(v, path)?;
+    match entries.first() {
+        Some(&(ref name, ref body)) if entries.len() == 1 => {
+            let path = child(path, name);
+            Ok((name.clone(), as_map(body, &path)?, path))
+        }
+        _ => Err(DefError::mismatch(
+            v,
+            &format!("a table with the {} name as its only key", what),
+            path,
+        )),
+    }
+}
+
+fn app_from(name: String, entries: &[(String, Value)], path: &str) -> Result<AppDef, DefError> {
+    let mut d = AppDef::new(name);
+    for &(ref k, ref v) in entries {
+        let path = child(path, k);
+        let path = &*path;
+        match &**k {
+            "name" => d.name = as_str(v, path)?,
+            "bin_name" => d.bin_name = Some(as_str(v, path)?),
+            "version" => d.version = Some(as_str(v, path)?),
+            "long_version" => d.long_version = Some(as_str(v, path)?),
+            "author" => d.author = Some(as_str(v, path)?),
+            "about" => d.about = Some(as_str(v, path)?),
+            "long_about" => d.long_about = Some(as_str(v, path)?),
+            "before_help" => d.before_help = Some(as_str(v, path)?),
+            "after_help" => d.after_help = Some(as_str(v, path)?),
+            "usage" => d.usage = Some(as_str(v, path)?),
+            "help" => d.help = Some(as_str(v, path)?),
+            "template" => d.template = Some(as_str(v, path)?),
+            "help_short" => d.help_short = Some(as_str(v, path)?),
+            "version_short" => d.version_short = Some(as_str(v, path)?),
+            "help_message" => d.help_message = Some(as_str(v, path)?),
+            "version_message" => d.version_message = Some(as_str(v, path)?),
+            "alias" | "aliases" => d.aliases.extend(strs(v, path)?),
+            "visible_alias" | "visible_aliases" => d.visible_aliases.extend(strs(v, path)?),
+            "display_order" => d.display_order = Some(as_u64(v, path)? as usize),
+            "setting" | "settings" => d.settings.extend(settings(v, path)?),
+            "global_setting" | "global_settings" => {
+                d.global_settings.extend(settings(v, path)?)
+            }
+            "args" => for (i, a) in as_seq(v, path)?.iter().enumerate() {
+                let (name, body, path) = named(a, &format!("{}[{}]", path, i), "argument")?;
+                d.args.push(arg_from(name, body, &path)?);
+            },
+            "groups" => for (i, g) in as_seq(v, path)?.iter().enumerate() {
+                d.groups.push(group_from(g, &format!("{}[{}]", path, i))?);
+            },
+            "subcommands" => for (i, sc) in as_seq(v, path)?.iter().enumerate() {
+                let (name, body, path) = named(sc, &format!("{}[{}]", path, i), "subcommand")?;
+                d.subcommands.push(app_from(name, body, &path)?);
+            },
+            _ => return Err(DefError::new(path, "unknown key")),
+        }
+    }
+    Ok(d)
+}
+
+fn arg_from(name: String, entries: &[(String, Value)], path: &str) -> Result<ArgDef, DefError> {
+    let mut d = ArgDef::with_name(name);
+    for &(ref k, ref v) in entries {
+        let path = child(path, k);
+        let path = &*path;
+        match &**k {
+            "short" => d.short = Some(as_str(v, path)?),
+            "long" => d.long = Some(as_str(v, path)?),
+            "aliases" => d.aliases.extend(strs(v, path)?),
+            "visible_aliases" => d.visible_aliases.extend(strs(v, path)?),
+            "help" => d.help = Some(as_str(v, path)?),
+            "long_help" => d.long_help = Some(as_str(v, path)?),
+            "index" => d.index = Some(as_u64(v, path)?),
+            "required" => d.required = as_bool(v, path)?,
+            "takes_value" => d.takes_value = as_bool(v, path)?,
+            "multiple" => d.multiple = as_bool(v, path)?,
+            "max_occurrences" => d.max_occurrences = Some(as_u64(v, path)?),
+            "decrements" => d.decrements = Some(as_str(v, path)?),
+            "global" => d.global = as_bool(v, path)?,
+            "hidden" => d.hidden = as_bool(v, path)?,
+            "last" => d.last = as_bool(v, path)?,
+            "next_line_help" => d.next_line_help = as_bool(v, path)?,
+            "empty_values" => d.empty_values = as_bool(v, path)?,
+            "allow_hyphen_values" => d.allow_hyphen_values = as_bool(v, path)?,
+            "require_equals" => d.require_equals = as_bool(v, path)?,
+            "use_delimiter" => d.use_delimiter = as_bool(v, path)?,
+            "require_delimiter" => d.require_delimiter = as_bool(v, path)?,
+            "case_insensitive" => d.case_insensitive = as_bool(v, path)?,
+            "hide_possible_values" => d.hide_possible_values = as_bool(v, path)?,
+            "hide_default_value" => d.hide_default_value = as_bool(v, path)?,
+            "hide_env_values" => d.hide_env_values = as_bool(v, path)?,
+            "negatable" => d.negatable = as_bool(v, path)?,
+            "value_delimiter" => d.value_delimiter = Some(as_str(v, path)?),
+            "key_value_delimiter" => d.key_value_delimiter = Some(as_str(v, path)?),
+            "value_terminator" => d.value_terminator = Some(as_str(v, path)?),
+            "number_of_values" => d.number_of_values = Some(as_u64(v, path)?),
+            "max_values" => d.max_values = Some(as_u64(v, path)?),
+            "min_values" => d.min_values = Some(as_u64(v, path)?),
+            "value_name" | "value_names" => d.value_names.extend(strs(v, path)?),
+            "possible_values" => d.possible_values.extend(strs(v, path)?),
+            "possible_keys" => d.possible_keys.extend(strs(v, path)?),
+            "default_value" => d.default_value = Some(as_str(v, path)?),
+            "default_value_if" | "default_value_ifs" => for (t, path) in tuples(v, 3, path)? {
+                d.default_value_ifs.push((
+                    as_str(&t[0], &format!("{}[0]", path))?,
+                    as_opt_str(&t[1], &format!("{}[1]", path))?,
+                    as_str(&t[2], &format!("{}[2]", path))?,
+                ));
+            },
+            "env" => d.env = Some(as_str(v, path)?),
+            "requires" => d.requires.extend(strs(v, path)?),
+            "requires_if" | "requires_ifs" => for (t, path) in tuples(v, 2, path)? {
+                d.requires_ifs.push((
+                    as_str(&t[0], &format!("{}[0]", path))?,
+                    as_str(&t[1], &format!("{}[1]", path))?,
+                ));
+            },
+            "required_if" | "required_ifs" => for (t, path) in tuples(v, 2, path)? {
+                d.required_ifs.push((
+                    as_str(&t[0], &format!("{}[0]", path))?,
+                    as_str(&t[1], &format!("{}[1]", path))?,
+                ));
+            },
+            "required_unless" | "required_unless_one" => {
+                d.required_unless_one.extend(strs(v, path)?)
+            }
+            "required_unless_all" => d.required_unless_all.extend(strs(v, path)?),
+            "conflicts_with" => d.conflicts_with.extend(strs(v, path)?),
+            "overrides_with" => d.overrides_with.extend(strs(v, path)?),
+            "group" | "groups" => d.groups.extend(strs(v, path)?),
+            "display_order" => d.display_order = Some(as_u64(v, path)? as usize),
+            _ => return Err(DefError::new(path, "unknown key")),
+        }
+    }
+    Ok(d)
+}
+
+fn group_from(v: &Value, path: &str) -> Result<ArgGroupDef, DefError> {
+    // Groups may either be named by their only key, or by a `name` key
+    let entries = as_map(v, path)?;
+    let (mut d, entries, path) = if entries.iter().any(|&(ref k, _)| k == "name") {
+        (ArgGroupDef::default(), entries, String::from(path))
+    } else {
+        let (name, body, path) = named(v, path, "group")?;
+        (ArgGroupDef::with_name(name), body, path)
+    };
+    for &(ref k, ref v) in entries {
+        let path = child(&path, k);
+        let path = &*path;
+        match &**k {
+            "name" => d.name = as_str(v, path)?,
+            "arg" | "args" => d.args.extend(strs(v, path)?),
+            "required" => d.required = as_bool(v, path)?,
+            "multiple" => d.multiple = as_bool(v, path)?,
+            "min_args" => d.min_args = Some(as_u64(v, path)?),
+            "max_args" => d.max_args = Some(as_u64(v, path)?),
+            "requires" => d.requires.extend(strs(v, path)?),
+            "conflicts_with" => d.conflicts_with.extend(strs(v, path)?),
+            _ => return Err(DefError::new(path, "unknown key")),
+        }
+    }
+    Ok(d)
+}
+
+// Builds tables leaving out everything which is unset, so the output stays close to what
+// someone would write by hand
+#[derive(Default)]
+struct Table(Vec<(String, Value)>);
+
+impl Table {
+    fn push(&mut self, k: &str, v: Value) { self.0.push((k.into(), v)); }
+
+    fn str(&mut self, k: &str, v: &Option<String>) {
+        if let Some(ref s) = *v {
+            self.push(k, Value::Str(s.clone()));
+        }
+    }
+
+    fn strs(&mut self, k: &str, v: &[String]) {
+        if !v.is_empty() {
+            self.push(k, Value::Seq(v.iter().cloned().map(Value::Str).collect()));
+        }
+    }
+
+    fn flag(&mut self, k: &str, b: bool) {
+        if b {
+            self.push(k, Value::Bool(true));
+        }
+    }
+
+    fn int(&mut self, k: &str, v: Option<u64>) {
+        if let Some(i) = v {
+            self.push(k, Value::Int(i as i64));
+        }
+    }
+
+    fn named(self, name: &str) -> Value { Value::Map(vec![(name.into(), Value::Map(self.0))]) }
+}
+
+fn app_to_value(d: &AppDef) -> Value {
+    let mut t = Table::default();
+    t.push("name", Value::Str(d.name.clone()));
+    t.0.extend(app_body(d).0);
+    Value::Map(t.0)
+}
+
+fn app_body(d: &AppDef) -> Table {
+    let mut t = Table::default();
+    t.str("bin_name", &d.bin_name);
+    t.str("version", &d.version);
+    t.str("long_version", &d.long_version);
+    t.str("author", &d.author);
+    t.str("about", &d.about);
+    t.str("long_about", &d.long_about);
+    t.str("before_help", &d.before_help);
+    t.str("after_help", &d.after_help);
+    t.str("usage", &d.usage);
+    t.str("help", &d.help);
+    t.str("template", &d.template);
+    t.str("help_short", &d.help_short);
+    t.str("version_short", &d.version_short);
+    t.str("help_message", &d.help_message);
+    t.str("version_message", &d.version_message);
+    t.strs("aliases", &d.aliases);
+    t.strs("visible_aliases", &d.visible_aliases);
+    t.int("display_order", d.display_order.map(|o| o as u64));
+    let names = |ss: &[AppSettings]| -> Vec<String> { ss.iter().map(|s| format!("{:?}", s)).collect() };
+    t.strs("settings", &names(&d.settings));
+    t.strs("global_settings", &names(&d.global_settings));
+    // Tables go last, TOML doesn't allow plain values after them
+    if !d.args.is_empty() {
+        t.push("args", Value::Seq(d.args.iter().map(arg_to_value).collect()));
+    }
+    if !d.groups.is_empty() {
+        t.push("groups", Value::Seq(d.groups.iter().map(group_to_value).collect()));
+    }
+    if !d.subcommands.is_empty() {
+        t.push(
+            "subcommands",
+            Value::Seq(
+                d.subcommands
+                    .iter()
+                    .map(|sc| app_body(sc).named(&sc.name))
+                    .collect(),
+            ),
+        );
+    }
+    t
+}
+
+fn arg_to_value(d: &ArgDef) -> Value {
+    let mut t = Table::default();
+    t.str("short", &d.short);
+    t.str("long", &d.long);
+    t.strs("aliases", &d.aliases);
+    t.strs("visible_aliases", &d.visible_aliases);
+    t.str("help", &d.help);
+    t.str("long_help", &d.long_help);
+    t.int("index", d.index);
+    t.flag("required", d.required);
+    t.flag("takes_value", d.takes_value);
+    t.flag("multiple", d.multiple);
+    t.int("max_occurrences", d.max_occurrences);
+    t.str("decrements", &d.decrements);
+    t.flag("global", d.global);
+    t.flag("hidden", d.hidden);
+    t.flag("last", d.last);
+    t.flag("next_line_help", d.next_line_help);
+    if !d.empty_values {
+        t.push("empty_values", Value::Bool(false));
+    }
+    t.flag("allow_hyphen_values", d.allow_hyphen_values);
+    t.flag("require_equals", d.require_equals);
+    t.flag("use_delimiter", d.use_delimiter);
+    t.flag("require_delimiter", d.require_delimiter);
+    t.flag("case_insensitive", d.case_insensitive);
+    t.flag("hide_possible_values", d.hide_possible_values);
+    t.flag("hide_default_value", d.hide_default_value);
+    t.flag("hide_env_values", d.hide_env_values);
+    t.flag("negatable", d.negatable);
+    t.str("value_delimiter", &d.value_delimiter);
+    t.str("key_value_delimiter", &d.key_value_delimiter);
+    t.str("value_terminator", &d.value_terminator);
+    t.int("number_of_values", d.number_of_values);
+    t.int("max_values", d.max_values);
+    t.int("min_values", d.min_values);
+    t.strs("value_names", &d.value_names);
+    t.strs("possible_values", &d.possible_values);
+    t.strs("possible_keys", &d.possible_keys);
+    t.str("default_value", &d.default_value);
+    if !d.default_value_ifs.is_empty() {
+        let ifs = d.default_value_ifs
+            .iter()
+            .map(|&(ref arg, ref val, ref default)| {
+                Value::Seq(vec![
+                    Value::Str(arg.clone()),
+                    val.clone().map_or(Value::Null, Value::Str),
+                    Value::Str(default.clone()),
+                ])
+            })
+            .collect();
+        t.push("default_value_ifs", Value::Seq(ifs));
+    }
+    t.str("env", &d.env);
+    t.strs("requires", &d.requires);
+    let pairs = |ps: &[(String, String)]| -> Value {
+        Value::Seq(
+            ps.iter()
+                .map(|&(ref a, ref b)| Value::Seq(vec![Value::Str(a.clone()), Value::Str(b.clone())]))
+                .collect(),
+        )
+    };
+    if !d.requires_ifs.is_empty() {
+        t.push("requires_ifs", pairs(&d.requires_ifs));
+    }
+    if !d.required_ifs.is_empty() {
+        t.push("required_ifs", pairs(&d.required_ifs));
+    }
+    t.strs("required_unless_one", &d.required_unless_one);
+    t.strs("required_unless_all", &d.required_unless_all);
+    t.strs("conflicts_with", &d.conflicts_with);
+    t.strs("overrides_with", &d.overrides_with);
+    t.strs("groups", &d.groups);
+    t.int("display_order", d.display_order.map(|o| o as u64));
+    t.named(&d.name)
+}
+
+fn group_to_value(d: &ArgGroupDef) -> Value {
+    let mut t = Table::default();
+    t.strs("args", &d.args);
+    t.flag("required", d.required);
+    t.flag("multiple", d.multiple);
+    t.int("min_args", d.min_args);
+    t.int("max_args", d.max_args);
+    t.strs("requires", &d.requires);
+    t.strs("conflicts_with", &d.conflicts_with);
+    t.named(&d.name)
+}
+
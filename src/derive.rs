@@ -0,0 +1,121 @@
+// Std
+use std::ffi::OsString;
+
+// Internal
+use app::App;
+use args::ArgMatches;
+use errors::Error;
+
+/// Parses the command line arguments straight into a type implementing both [`IntoApp`] and
+/// [`FromArgMatches`], which is what `#[derive(Clap)]` generates.
+///
+/// This trait is implemented automatically for every such type, so it only needs to be in scope.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate clap;
+/// use clap::ClapApp;
+///
+/// #[derive(Clap)]
+/// #[clap(name = "prog")]
+/// struct Opts {
+///     /// Sets the level of verbosity
+///     #[clap(short, from_occurrences)]
+///     verbose: u64,
+///     /// The file to read
+///     input: String,
+/// }
+///
+/// # fn main() {
+/// let opts = Opts::parse_from(vec!["prog", "-vv", "notes.txt"]);
+/// assert_eq!(opts.verbose, 2);
+/// assert_eq!(opts.input, "notes.txt");
+/// # }
+/// ```
+/// [`IntoApp`]: ./trait.IntoApp.html
+/// [`FromArgMatches`]: ./trait.FromArgMatches.html
+pub trait ClapApp: IntoApp + FromArgMatches + Sized {
+    /// Parses `env::args_os`, exiting on failure the same way [`App::get_matches`] does.
+    ///
+    /// [`App::get_matches`]: ./struct.App.html#method.get_matches
+    fn parse() -> Self { Self::from_argmatches(&Self::into_app().get_matches()) }
+
+    /// Parses the given iterator, exiting on failure the same way [`App::get_matches_from`] does.
+    ///
+    /// [`App::get_matches_from`]: ./struct.App.html#method.get_matches_from
+    fn parse_from<I, T>(argv: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        Self::from_argmatches(&Self::into_app().get_matches_from(argv))
+    }
+
+    /// Parses `env::args_os`, returning a [`clap::Result`] on failure instead of exiting.
+    ///
+    /// [`clap::Result`]: ./type.Result.html
+    fn try_parse() -> Result<Self, Error> {
+        Self::try_from_argmatches(&Self::into_app().get_matches_safe()?)
+    }
+
+    /// Parses the given iterator, returning a [`clap::Result`] on failure instead of exiting.
+    ///
+    /// [`clap::Result`]: ./type.Result.html
+    fn try_parse_from<I, T>(argv: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        Self::try_from_argmatches(&Self::into_app().get_matches_from_safe(argv)?)
+    }
+}
+
+impl<T: IntoApp + FromArgMatches> ClapApp for T {}
+
+/// Builds the [`App`] describing a type.
+///
+/// For structs, every field becomes an [`Arg`] named after the field (or a set of subcommands
+/// when the field is marked `#[clap(subcommand)]`). For enums, every variant becomes a
+/// [`SubCommand`] and one of them is required.
+///
+/// [`App`]: ./struct.App.html
+/// [`Arg`]: ./struct.Arg.html
+/// [`SubCommand`]: ./struct.SubCommand.html
+pub trait IntoApp {
+    /// Returns the [`App`] for this type.
+    ///
+    /// [`App`]: ./struct.App.html
+    fn into_app<'a, 'b>() -> App<'a, 'b>;
+}
+
+/// Converts the [`ArgMatches`] produced by the [`App`] from [`IntoApp::into_app`] into the type.
+///
+/// [`ArgMatches`]: ./struct.ArgMatches.html
+/// [`App`]: ./struct.App.html
+/// [`IntoApp::into_app`]: ./trait.IntoApp.html#tymethod.into_app
+pub trait FromArgMatches: Sized {
+    /// Converts the matches, exiting the process with the error message on failure.
+    fn from_argmatches<'a>(matches: &ArgMatches<'a>) -> Self {
+        Self::try_from_argmatches(matches).unwrap_or_else(|e| e.exit())
+    }
+
+    /// Converts the matches, returning a [`clap::Error`] when a value doesn't parse into the type
+    /// of its field or a required value is missing.
+    ///
+    /// [`clap::Error`]: ./struct.Error.html
+    fn try_from_argmatches<'a>(matches: &ArgMatches<'a>) -> Result<Self, Error>;
+}
+
+/// Implemented by enums deriving `Clap`, so they can be used as a `#[clap(subcommand)]` field.
+pub trait Subcommand: Sized {
+    /// Adds one [`SubCommand`] per variant to `app`.
+    ///
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    fn augment_subcommands<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b>;
+
+    /// Builds the variant matching the subcommand `name`, or returns `None` when no variant
+    /// matches (i.e. no subcommand was used).
+    fn from_subcommand<'a>(name: &str, matches: Option<&ArgMatches<'a>>)
+        -> Option<Result<Self, Error>>;
+}
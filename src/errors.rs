@@ -11,6 +11,7 @@ use std::result::Result as StdResult;
 use args::AnyArg;
 use fmt::{ColorWhen, Colorizer, ColorizerOption};
 use suggestions;
+use INTERNAL_ERROR_MSG;
 
 /// Short hand for [`Result`] type
 ///
@@ -21,7 +22,8 @@ pub type Result<T> = StdResult<T, Error>;
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum ErrorKind {
     /// Occurs when an [`Arg`] has a set of possible values,
-    /// and the user provides a value which isn't in that set.
+    /// and the user provides a value which isn't in that set. The same goes for the keys of an
+    /// [`Arg`] with a set of possible keys.
     ///
     /// # Examples
     ///
@@ -52,6 +54,24 @@ pub enum ErrorKind {
     /// ```
     UnknownArgument,
 
+    /// Occurs when the user abbreviates a long argument in a way which matches more than one
+    /// argument while [`AppSettings::InferLongArgs`] is set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings, ErrorKind};
+    /// let result = App::new("prog")
+    ///     .setting(AppSettings::InferLongArgs)
+    ///     .arg(Arg::from_usage("--config [file] 'config file'"))
+    ///     .arg(Arg::from_usage("--color [when] 'when to color'"))
+    ///     .get_matches_from_safe(vec!["prog", "--co"]);
+    /// assert!(result.is_err());
+    /// assert_eq!(result.unwrap_err().kind, ErrorKind::AmbiguousArgument);
+    /// ```
+    /// [`AppSettings::InferLongArgs`]: ./enum.AppSettings.html#variant.InferLongArgs
+    AmbiguousArgument,
+
     /// Occurs when the user provides an unrecognized [`SubCommand`] which meets the threshold for
     /// being similar enough to an existing subcommand.
     /// If it doesn't meet the threshold, or the 'suggestions' feature is disabled,
@@ -120,8 +140,8 @@ pub enum ErrorKind {
     /// ```
     EmptyValue,
 
-    /// Occurs when the user provides a value for an argument with a custom validation and the
-    /// value fails that validation.
+    /// Occurs when the user provides a value for an argument with a custom validation (or an
+    /// [`Arg::value_parser`]) and the value fails that validation.
     ///
     /// # Examples
     ///
@@ -141,6 +161,7 @@ pub enum ErrorKind {
     /// assert!(result.is_err());
     /// assert_eq!(result.unwrap_err().kind, ErrorKind::ValueValidation);
     /// ```
+    /// [`Arg::value_parser`]: ./struct.Arg.html#method.value_parser
     ValueValidation,
 
     /// Occurs when a user provides more values for an argument than were defined by setting
@@ -235,6 +256,45 @@ pub enum ErrorKind {
     /// ```
     MissingRequiredArgument,
 
+    /// Occurs when the arguments used don't satisfy one of the [`Constraint`]s of the [`App`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Constraint, ErrorKind};
+    /// let result = App::new("prog")
+    ///     .args_from_usage(
+    ///         "--json 'json output'
+    ///          --yaml 'yaml output'")
+    ///     .constraint(Constraint::exactly_one(vec!["json", "yaml"]))
+    ///     .get_matches_from_safe(vec!["prog"]);
+    /// assert!(result.is_err());
+    /// assert_eq!(result.unwrap_err().kind, ErrorKind::ConstraintViolation);
+    /// ```
+    /// [`Constraint`]: ./struct.Constraint.html
+    /// [`App`]: ./struct.App.html
+    ConstraintViolation,
+
+    /// Occurs when [`AppSettings::ReportAllErrors`] is used and more than one problem was found
+    /// with the arguments. The individual errors are available in [`Error::errors`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, AppSettings, Arg, ErrorKind};
+    /// let result = App::new("prog")
+    ///     .setting(AppSettings::ReportAllErrors)
+    ///     .arg(Arg::with_name("input").required(true))
+    ///     .get_matches_from_safe(vec!["prog", "--verbose"]);
+    /// assert!(result.is_err());
+    /// let err = result.unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::MultipleErrors);
+    /// assert_eq!(err.errors().len(), 2);
+    /// ```
+    /// [`AppSettings::ReportAllErrors`]: ./enum.AppSettings.html#variant.ReportAllErrors
+    /// [`Error::errors`]: ./struct.Error.html#method.errors
+    MultipleErrors,
+
     /// Occurs when a subcommand is required (as defined by [`AppSettings::SubcommandRequired`]),
     /// but the user does not provide one.
     ///
@@ -351,6 +411,40 @@ pub enum ErrorKind {
     /// ```
     VersionDisplayed,
 
+    /// Not a true "error" as it means the hidden `--complete` entry point of an `App` with
+    /// [`AppSettings::DynamicCompletion`] was used. The completion candidates will be sent to
+    /// `stdout`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, AppSettings, ErrorKind, SubCommand};
+    /// let result = App::new("prog")
+    ///     .setting(AppSettings::DynamicCompletion)
+    ///     .subcommand(SubCommand::with_name("build"))
+    ///     .get_matches_from_safe(vec!["prog", "--complete", "1", "--", "prog", "bu"]);
+    /// assert!(result.is_err());
+    /// assert_eq!(result.unwrap_err().kind, ErrorKind::CompletionsDisplayed);
+    /// ```
+    /// [`AppSettings::DynamicCompletion`]: ./enum.AppSettings.html#variant.DynamicCompletion
+    CompletionsDisplayed,
+
+    /// Occurs when a line read by a [`Repl`] can't be split into arguments, i.e. because a quote
+    /// isn't closed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind, Repl};
+    /// let mut repl = Repl::new(App::new("console")
+    ///     .arg(Arg::with_name("words").multiple(true)));
+    /// let result = repl.parse_line("say 'hello");
+    /// assert!(result.is_err());
+    /// assert_eq!(result.unwrap_err().kind, ErrorKind::InvalidLine);
+    /// ```
+    /// [`Repl`]: ./struct.Repl.html
+    InvalidLine,
+
     /// Occurs when using the [`value_t!`] and [`values_t!`] macros to convert an argument value
     /// into type `T`, but the argument you requested wasn't used. I.e. you asked for an argument
     /// with name `config` to be converted, but `config` wasn't used by the user.
@@ -359,7 +453,7 @@ pub enum ErrorKind {
     ArgumentNotFound,
 
     /// Represents an [I/O error].
-    /// Can occur when writing to `stderr` or `stdout` or reading a configuration file.
+    /// Can occur when writing to `stderr` or `stdout`, or reading a configuration or response file.
     /// [I/O error]: https://doc.rust-lang.org/std/io/struct.Error.html
     Io,
 
@@ -370,22 +464,108 @@ pub enum ErrorKind {
     Format,
 }
 
+/// The pieces of information an [`Error`] is made of, for inspecting it or [rendering] it
+/// differently. Which of them are set depends on the [`ErrorKind`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, ErrorKind};
+/// let err = App::new("prog")
+///     .arg(Arg::with_name("mode")
+///         .long("mode")
+///         .takes_value(true)
+///         .possible_values(&["fast", "slow"]))
+///     .get_matches_from_safe(vec!["prog", "--mode", "fsat"])
+///     .unwrap_err();
+/// assert_eq!(err.kind, ErrorKind::InvalidValue);
+/// let context = err.context();
+/// assert_eq!(context.arg, Some("--mode <mode>".to_owned()));
+/// assert_eq!(context.value, Some("fsat".to_owned()));
+/// assert_eq!(context.valid_values, ["fast", "slow"]);
+/// assert_eq!(context.suggestion, Some("fast".to_owned()));
+/// assert!(context.usage.as_ref().unwrap().starts_with("USAGE:"));
+/// ```
+///
+/// **NOTE:** An `ErrorContext` is output only. It's filled in by clap when the [`Error`] is
+/// created and can't be constructed outside of it, every field is there to be read.
+///
+/// [`Error`]: ./struct.Error.html
+/// [rendering]: ./struct.Error.html#method.render
+/// [`ErrorKind`]: ./enum.ErrorKind.html
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ErrorContext {
+    /// Output only: the argument the error is about, as displayed in the usage (i.e.
+    /// `--mode <mode>`), or as it was typed for unknown arguments and subcommands
+    pub arg: Option<String>,
+    /// Output only: the value which was rejected
+    pub value: Option<String>,
+    /// Output only: the values, keys or arguments which would have been accepted instead
+    pub valid_values: Vec<String>,
+    /// Output only: the correction suggested for a mistyped argument, subcommand or value
+    pub suggestion: Option<String>,
+    /// Output only: the argument which couldn't be used together with [`arg`]
+    /// [`arg`]: #structfield.arg
+    pub conflicting_arg: Option<String>,
+    /// Output only: the arguments involved when there are several of them, i.e. the missing
+    /// required arguments or the ones of a violated constraint. For errors returned by an
+    /// [`App::validator`] these are the names of the arguments which were used
+    /// [`App::validator`]: ./struct.App.html#method.validator
+    pub args: Vec<String>,
+    /// Output only: the usage string shown with the error
+    pub usage: Option<String>,
+    /// Output only: what went wrong, without the `error:` prefix, the usage or any colors
+    pub description: String,
+    // Keeps the struct from being constructed outside of clap, so that adding fields doesn't
+    // break anyone matching on or reading it
+    _priv: (),
+}
+
+/// The ways an [`Error`] can be [rendered] from its [`ErrorContext`]
+/// [`Error`]: ./struct.Error.html
+/// [rendered]: ./struct.Error.html#method.render
+/// [`ErrorContext`]: ./struct.ErrorContext.html
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ErrorStyle {
+    /// The description followed by the usage and where to find more information, which is how
+    /// the [`Error::message`] reads without colors
+    /// [`Error::message`]: ./struct.Error.html#structfield.message
+    Full,
+    /// The description only
+    WithoutUsage,
+    /// The first line of the description, i.e. for logs. [`ErrorKind::MultipleErrors`] get one
+    /// line per error
+    /// [`ErrorKind::MultipleErrors`]: ./enum.ErrorKind.html#variant.MultipleErrors
+    Short,
+}
+
 /// Command Line Argument Parser Error
+///
+/// **NOTE:** Since v3.0.0 an `Error` has private fields, so it can't be created with a struct
+/// literal anymore. Use [`Error::with_description`] instead.
+///
+/// [`Error::with_description`]: ./struct.Error.html#method.with_description
 #[derive(Debug)]
 pub struct Error {
     /// Formatted error message
     pub message: String,
     /// The type of error
     pub kind: ErrorKind,
-    /// Any additional information passed along, such as the argument name that caused the error
+    /// Any additional information passed along, such as the argument name that caused the error.
+    /// See [`context`] for the same information by name.
+    /// [`context`]: #method.context
     pub info: Option<Vec<String>>,
+    errors: Vec<Error>,
+    context: ErrorContext,
 }
 
 impl Error {
     /// Should the message be written to `stdout` or not
     pub fn use_stderr(&self) -> bool {
         match self.kind {
-            ErrorKind::HelpDisplayed | ErrorKind::VersionDisplayed => false,
+            ErrorKind::HelpDisplayed
+            | ErrorKind::VersionDisplayed
+            | ErrorKind::CompletionsDisplayed => false,
             _ => true,
         }
     }
@@ -401,9 +581,115 @@ impl Error {
         process::exit(0);
     }
 
+    /// The individual errors of an [`ErrorKind::MultipleErrors`] error, empty otherwise
+    /// [`ErrorKind::MultipleErrors`]: ./enum.ErrorKind.html#variant.MultipleErrors
+    pub fn errors(&self) -> &[Error] { &self.errors }
+
+    /// The structured details of the error, see [`ErrorContext`]
+    /// [`ErrorContext`]: ./struct.ErrorContext.html
+    pub fn context(&self) -> &ErrorContext { &self.context }
+
+    /// Renders the error from its [`ErrorContext`] in the given [`ErrorStyle`], without colors.
+    ///
+    /// The help and version messages, and the help shown for
+    /// [`ErrorKind::MissingArgumentOrSubcommand`], are returned as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorStyle};
+    /// let err = App::new("prog")
+    ///     .arg(Arg::with_name("input").required(true))
+    ///     .get_matches_from_safe(vec!["prog", "--frobnicate"])
+    ///     .unwrap_err();
+    /// assert_eq!(
+    ///     err.render(ErrorStyle::Short),
+    ///     "error: Found argument '--frobnicate' which wasn't expected, or isn't valid in this context"
+    /// );
+    /// assert_eq!(
+    ///     err.render(ErrorStyle::Full),
+    ///     "error: Found argument '--frobnicate' which wasn't expected, or isn't valid in this context\n\n\
+    ///      USAGE:\n    prog <input>\n\n\
+    ///      For more information try --help"
+    /// );
+    /// ```
+    /// [`ErrorContext`]: ./struct.ErrorContext.html
+    /// [`ErrorStyle`]: ./enum.ErrorStyle.html
+    /// [`ErrorKind::MissingArgumentOrSubcommand`]: ./enum.ErrorKind.html#variant.MissingArgumentOrSubcommand
+    pub fn render(&self, style: ErrorStyle) -> String {
+        match self.kind {
+            ErrorKind::HelpDisplayed
+            | ErrorKind::VersionDisplayed
+            | ErrorKind::CompletionsDisplayed
+            | ErrorKind::MissingArgumentOrSubcommand => return self.message.clone(),
+            _ => (),
+        }
+        let desc = &self.context.description;
+        match style {
+            ErrorStyle::Full => match self.context.usage {
+                Some(ref usage) => format!(
+                    "error: {}\n\n{}\n\nFor more information try --help",
+                    desc, usage
+                ),
+                None => format!("error: {}", desc),
+            },
+            ErrorStyle::WithoutUsage => format!("error: {}", desc),
+            ErrorStyle::Short if self.kind == ErrorKind::MultipleErrors => self.errors
+                .iter()
+                .map(|e| e.render(ErrorStyle::Short))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ErrorStyle::Short => format!("error: {}", desc.lines().next().unwrap_or("")),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn displayed(message: String, kind: ErrorKind) -> Self {
+        Error {
+            message: message,
+            kind: kind,
+            info: None,
+            errors: vec![],
+            context: ErrorContext::default(),
+        }
+    }
+
     #[doc(hidden)]
     pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> { write!(w, "{}", self.message) }
 
+    // Builds the message out of the (colored) description and usage, the context gets their
+    // plain versions
+    fn new(
+        kind: ErrorKind,
+        desc: String,
+        usage: Option<String>,
+        info: Option<Vec<String>>,
+        mut context: ErrorContext,
+        c: &Colorizer,
+    ) -> Self {
+        let message = match usage {
+            Some(ref usage) => format!(
+                "{} {}\n\n\
+                 {}\n\n\
+                 For more information try {}",
+                c.error("error:"),
+                desc,
+                usage,
+                c.good("--help")
+            ),
+            None => format!("{} {}", c.error("error:"), desc),
+        };
+        context.description = strip_colors(&desc);
+        context.usage = usage.map(|u| strip_colors(&u));
+        Error {
+            message: message,
+            kind: kind,
+            info: info,
+            errors: vec![],
+            context: context,
+        }
+    }
+
     #[doc(hidden)]
     pub fn argument_conflict<'a, 'b, O, U>(
         arg: &AnyArg,
@@ -420,27 +706,30 @@ impl Error {
             use_stderr: true,
             when: color,
         });
-        Error {
-            message: format!(
-                "{} The argument '{}' cannot be used with {}\n\n\
-                 {}\n\n\
-                 For more information try {}",
-                c.error("error:"),
-                c.warning(&*arg.to_string()),
-                match other {
-                    Some(name) => {
-                        let n = name.into();
-                        v.push(n.clone());
-                        c.warning(format!("'{}'", n))
-                    }
-                    None => c.none("one or more of the other specified arguments".to_owned()),
-                },
-                usage,
-                c.good("--help")
-            ),
-            kind: ErrorKind::ArgumentConflict,
-            info: Some(v),
-        }
+        let other = other.map(|o| o.into());
+        let desc = format!(
+            "The argument '{}' cannot be used with {}",
+            c.warning(&*arg.to_string()),
+            match other {
+                Some(ref n) => {
+                    v.push(n.clone());
+                    c.warning(format!("'{}'", n))
+                }
+                None => c.none("one or more of the other specified arguments".to_owned()),
+            }
+        );
+        Error::new(
+            ErrorKind::ArgumentConflict,
+            desc,
+            Some(usage.to_string()),
+            Some(v),
+            ErrorContext {
+                arg: Some(arg.to_string()),
+                conflicting_arg: other,
+                ..Default::default()
+            },
+            &c,
+        )
     }
 
     #[doc(hidden)]
@@ -452,20 +741,20 @@ impl Error {
             use_stderr: true,
             when: color,
         });
-        Error {
-            message: format!(
-                "{} The argument '{}' requires a value but none was supplied\
-                 \n\n\
-                 {}\n\n\
-                 For more information try {}",
-                c.error("error:"),
-                c.warning(arg.to_string()),
-                usage,
-                c.good("--help")
+        Error::new(
+            ErrorKind::EmptyValue,
+            format!(
+                "The argument '{}' requires a value but none was supplied",
+                c.warning(arg.to_string())
             ),
-            kind: ErrorKind::EmptyValue,
-            info: Some(vec![arg.name().to_owned()]),
-        }
+            Some(usage.to_string()),
+            Some(vec![arg.name().to_owned()]),
+            ErrorContext {
+                arg: Some(arg.to_string()),
+                ..Default::default()
+            },
+            &c,
+        )
     }
 
     #[doc(hidden)]
@@ -494,24 +783,76 @@ impl Error {
         }
         sorted.sort();
         let valid_values = sorted.join(", ");
-        Error {
-            message: format!(
-                "{} '{}' isn't a valid value for '{}'\n\t\
+        Error::new(
+            ErrorKind::InvalidValue,
+            format!(
+                "'{}' isn't a valid value for '{}'\n\t\
                  [possible values: {}]\n\
-                 {}\n\n\
-                 {}\n\n\
-                 For more information try {}",
-                c.error("error:"),
+                 {}",
                 c.warning(bad_val.as_ref()),
                 c.warning(arg.to_string()),
                 valid_values,
-                suffix.0,
-                usage,
-                c.good("--help")
+                suffix.0
             ),
-            kind: ErrorKind::InvalidValue,
-            info: Some(vec![arg.name().to_owned(), bad_val.as_ref().to_owned()]),
+            Some(usage.to_string()),
+            Some(vec![arg.name().to_owned(), bad_val.as_ref().to_owned()]),
+            ErrorContext {
+                arg: Some(arg.to_string()),
+                value: Some(bad_val.as_ref().to_owned()),
+                valid_values: good_vals.iter().map(|v| v.as_ref().to_owned()).collect(),
+                suggestion: suffix.1.map(|s| s.to_owned()),
+                ..Default::default()
+            },
+            &c,
+        )
+    }
+
+    #[doc(hidden)]
+    pub fn invalid_key<B, G, U>(
+        bad_key: B,
+        good_keys: &[G],
+        arg: &AnyArg,
+        usage: U,
+        color: ColorWhen,
+    ) -> Self
+    where
+        B: AsRef<str>,
+        G: AsRef<str> + Display,
+        U: Display,
+    {
+        let c = Colorizer::new(ColorizerOption {
+            use_stderr: true,
+            when: color,
+        });
+        let suffix = suggestions::did_you_mean_value_suffix(bad_key.as_ref(), good_keys.iter());
+
+        let mut sorted = vec![];
+        for k in good_keys {
+            sorted.push(format!("{}", c.good(k)));
         }
+        sorted.sort();
+        Error::new(
+            ErrorKind::InvalidValue,
+            format!(
+                "'{}' isn't a valid key for '{}'\n\t\
+                 [possible keys: {}]\n\
+                 {}",
+                c.warning(bad_key.as_ref()),
+                c.warning(arg.to_string()),
+                sorted.join(", "),
+                suffix.0
+            ),
+            Some(usage.to_string()),
+            Some(vec![arg.name().to_owned(), bad_key.as_ref().to_owned()]),
+            ErrorContext {
+                arg: Some(arg.to_string()),
+                value: Some(bad_key.as_ref().to_owned()),
+                valid_values: good_keys.iter().map(|k| k.as_ref().to_owned()).collect(),
+                suggestion: suffix.1.map(|s| s.to_owned()),
+                ..Default::default()
+            },
+            &c,
+        )
     }
 
     #[doc(hidden)]
@@ -533,26 +874,28 @@ impl Error {
             use_stderr: true,
             when: color,
         });
-        Error {
-            message: format!(
-                "{} The subcommand '{}' wasn't recognized\n\t\
+        Error::new(
+            ErrorKind::InvalidSubcommand,
+            format!(
+                "The subcommand '{}' wasn't recognized\n\t\
                  Did you mean '{}'?\n\n\
                  If you believe you received this message in error, try \
-                 re-running with '{} {} {}'\n\n\
-                 {}\n\n\
-                 For more information try {}",
-                c.error("error:"),
+                 re-running with '{} {} {}'",
                 c.warning(&*s),
                 c.good(did_you_mean.as_ref()),
                 name,
                 c.good("--"),
-                &*s,
-                usage,
-                c.good("--help")
+                &*s
             ),
-            kind: ErrorKind::InvalidSubcommand,
-            info: Some(vec![s]),
-        }
+            Some(usage.to_string()),
+            Some(vec![s.clone()]),
+            ErrorContext {
+                arg: Some(s),
+                suggestion: Some(did_you_mean.as_ref().to_owned()),
+                ..Default::default()
+            },
+            &c,
+        )
     }
 
     #[doc(hidden)]
@@ -566,50 +909,97 @@ impl Error {
             use_stderr: true,
             when: color,
         });
-        Error {
-            message: format!(
-                "{} The subcommand '{}' wasn't recognized\n\n\
-                 {}\n\t\
-                 {} help <subcommands>...\n\n\
-                 For more information try {}",
-                c.error("error:"),
-                c.warning(&*s),
+        Error::new(
+            ErrorKind::UnrecognizedSubcommand,
+            format!("The subcommand '{}' wasn't recognized", c.warning(&*s)),
+            Some(format!(
+                "{}\n\t{} help <subcommands>...",
                 c.warning("USAGE:"),
-                name,
-                c.good("--help")
-            ),
-            kind: ErrorKind::UnrecognizedSubcommand,
-            info: Some(vec![s]),
-        }
+                name
+            )),
+            Some(vec![s.clone()]),
+            ErrorContext {
+                arg: Some(s),
+                ..Default::default()
+            },
+            &c,
+        )
     }
 
     #[doc(hidden)]
-    pub fn missing_required_argument<R, U>(required: R, usage: U, color: ColorWhen) -> Self
+    pub fn missing_required_argument<U>(required: Vec<String>, usage: U, color: ColorWhen) -> Self
     where
-        R: Display,
         U: Display,
     {
         let c = Colorizer::new(ColorizerOption {
             use_stderr: true,
             when: color,
         });
-        Error {
-            message: format!(
-                "{} The following required arguments were not provided:{}\n\n\
-                 {}\n\n\
-                 For more information try {}",
-                c.error("error:"),
-                required,
-                usage,
-                c.good("--help")
+        Error::new(
+            ErrorKind::MissingRequiredArgument,
+            format!(
+                "The following required arguments were not provided:{}",
+                required.iter().fold(String::new(), |acc, s| {
+                    acc + &format!("\n    {}", c.error(s))[..]
+                })
             ),
-            kind: ErrorKind::MissingRequiredArgument,
-            info: None,
-        }
+            Some(usage.to_string()),
+            None,
+            ErrorContext {
+                arg: required.first().cloned(),
+                args: required,
+                ..Default::default()
+            },
+            &c,
+        )
     }
 
     #[doc(hidden)]
-    pub fn missing_subcommand<N, U>(name: N, usage: U, color: ColorWhen) -> Self
+    pub fn constraint_violation<U>(
+        rule: &str,
+        msg: Option<&str>,
+        args: Vec<String>,
+        names: Vec<String>,
+        usage: U,
+        color: ColorWhen,
+    ) -> Self
+    where
+        U: Display,
+    {
+        let c = Colorizer::new(ColorizerOption {
+            use_stderr: true,
+            when: color,
+        });
+        Error::new(
+            ErrorKind::ConstraintViolation,
+            match msg {
+                Some(msg) => msg.to_owned(),
+                None => format!(
+                    "The constraint '{}' on the following arguments wasn't satisfied:{}",
+                    c.warning(rule),
+                    args.iter().fold(String::new(), |acc, s| {
+                        acc + &format!("\n    {}", c.error(s))[..]
+                    })
+                ),
+            },
+            Some(usage.to_string()),
+            Some(names),
+            ErrorContext {
+                arg: args.first().cloned(),
+                args: args,
+                ..Default::default()
+            },
+            &c,
+        )
+    }
+
+    #[doc(hidden)]
+    pub fn missing_subcommand<N, U>(
+        name: N,
+        subcommands: Vec<String>,
+        usage: U,
+        color: ColorWhen,
+    ) -> Self
     where
         N: AsRef<str> + Display,
         U: Display,
@@ -618,19 +1008,21 @@ impl Error {
             use_stderr: true,
             when: color,
         });
-        Error {
-            message: format!(
-                "{} '{}' requires a subcommand, but one was not provided\n\n\
-                 {}\n\n\
-                 For more information try {}",
-                c.error("error:"),
-                c.warning(name),
-                usage,
-                c.good("--help")
+        Error::new(
+            ErrorKind::MissingSubcommand,
+            format!(
+                "'{}' requires a subcommand, but one was not provided",
+                c.warning(&name)
             ),
-            kind: ErrorKind::MissingSubcommand,
-            info: None,
-        }
+            Some(usage.to_string()),
+            None,
+            ErrorContext {
+                arg: Some(name.as_ref().to_owned()),
+                valid_values: subcommands,
+                ..Default::default()
+            },
+            &c,
+        )
     }
 
 
@@ -643,18 +1035,14 @@ impl Error {
             use_stderr: true,
             when: color,
         });
-        Error {
-            message: format!(
-                "{} Invalid UTF-8 was detected in one or more arguments\n\n\
-                 {}\n\n\
-                 For more information try {}",
-                c.error("error:"),
-                usage,
-                c.good("--help")
-            ),
-            kind: ErrorKind::InvalidUtf8,
-            info: None,
-        }
+        Error::new(
+            ErrorKind::InvalidUtf8,
+            "Invalid UTF-8 was detected in one or more arguments".to_owned(),
+            Some(usage.to_string()),
+            None,
+            ErrorContext::default(),
+            &c,
+        )
     }
 
     #[doc(hidden)]
@@ -668,21 +1056,23 @@ impl Error {
             use_stderr: true,
             when: color,
         });
-        Error {
-            message: format!(
-                "{} The value '{}' was provided to '{}', but it wasn't expecting \
-                 any more values\n\n\
-                 {}\n\n\
-                 For more information try {}",
-                c.error("error:"),
+        Error::new(
+            ErrorKind::TooManyValues,
+            format!(
+                "The value '{}' was provided to '{}', but it wasn't expecting \
+                 any more values",
                 c.warning(v),
-                c.warning(arg.to_string()),
-                usage,
-                c.good("--help")
+                c.warning(arg.to_string())
             ),
-            kind: ErrorKind::TooManyValues,
-            info: Some(vec![arg.name().to_owned(), v.to_owned()]),
-        }
+            Some(usage.to_string()),
+            Some(vec![arg.name().to_owned(), v.to_owned()]),
+            ErrorContext {
+                arg: Some(arg.to_string()),
+                value: Some(v.to_owned()),
+                ..Default::default()
+            },
+            &c,
+        )
     }
 
     #[doc(hidden)]
@@ -700,23 +1090,93 @@ impl Error {
             use_stderr: true,
             when: color,
         });
-        Error {
-            message: format!(
-                "{} The argument '{}' requires at least {} values, but only {} w{} \
-                 provided\n\n\
-                 {}\n\n\
-                 For more information try {}",
-                c.error("error:"),
+        Error::new(
+            ErrorKind::TooFewValues,
+            format!(
+                "The argument '{}' requires at least {} values, but only {} w{} \
+                 provided",
                 c.warning(arg.to_string()),
                 c.warning(min_vals.to_string()),
                 c.warning(curr_vals.to_string()),
-                if curr_vals > 1 { "ere" } else { "as" },
-                usage,
-                c.good("--help")
+                if curr_vals > 1 { "ere" } else { "as" }
             ),
-            kind: ErrorKind::TooFewValues,
-            info: Some(vec![arg.name().to_owned()]),
-        }
+            Some(usage.to_string()),
+            Some(vec![arg.name().to_owned()]),
+            ErrorContext {
+                arg: Some(arg.to_string()),
+                ..Default::default()
+            },
+            &c,
+        )
+    }
+
+    #[doc(hidden)]
+    pub fn too_many_args_in_group<U>(
+        group: &str,
+        group_usage: &str,
+        max: u64,
+        curr: u64,
+        usage: U,
+        color: ColorWhen,
+    ) -> Self
+    where
+        U: Display,
+    {
+        let c = Colorizer::new(ColorizerOption {
+            use_stderr: true,
+            when: color,
+        });
+        Error::new(
+            ErrorKind::ArgumentConflict,
+            format!(
+                "The group '{}' allows at most {} arguments, but {} were provided",
+                c.warning(group_usage),
+                c.warning(max.to_string()),
+                c.warning(curr.to_string())
+            ),
+            Some(usage.to_string()),
+            Some(vec![group.to_owned()]),
+            ErrorContext {
+                arg: Some(group_usage.to_owned()),
+                ..Default::default()
+            },
+            &c,
+        )
+    }
+
+    #[doc(hidden)]
+    pub fn too_few_args_in_group<U>(
+        group: &str,
+        group_usage: &str,
+        min: u64,
+        curr: u64,
+        usage: U,
+        color: ColorWhen,
+    ) -> Self
+    where
+        U: Display,
+    {
+        let c = Colorizer::new(ColorizerOption {
+            use_stderr: true,
+            when: color,
+        });
+        Error::new(
+            ErrorKind::MissingRequiredArgument,
+            format!(
+                "The group '{}' requires at least {} arguments, but only {} w{} provided",
+                c.warning(group_usage),
+                c.warning(min.to_string()),
+                c.warning(curr.to_string()),
+                if curr > 1 { "ere" } else { "as" }
+            ),
+            Some(usage.to_string()),
+            Some(vec![group.to_owned()]),
+            ErrorContext {
+                arg: Some(group_usage.to_owned()),
+                ..Default::default()
+            },
+            &c,
+        )
     }
 
     #[doc(hidden)]
@@ -726,10 +1186,10 @@ impl Error {
             use_stderr: true,
             when: color,
         });
-        Error {
-            message: format!(
-                "{} Invalid value{}: {}",
-                c.error("error:"),
+        Error::new(
+            ErrorKind::ValueValidation,
+            format!(
+                "Invalid value{}: {}",
                 if let Some(a) = arg {
                     format!(" for '{}'", c.warning(a.to_string()))
                 } else {
@@ -737,9 +1197,43 @@ impl Error {
                 },
                 err
             ),
-            kind: ErrorKind::ValueValidation,
-            info: None,
-        }
+            None,
+            None,
+            ErrorContext {
+                arg: arg.map(|a| a.to_string()),
+                ..Default::default()
+            },
+            &c,
+        )
+    }
+
+    #[doc(hidden)]
+    pub fn value_parse<B, U>(bad_val: B, arg: &AnyArg, err: String, usage: U, color: ColorWhen) -> Self
+    where
+        B: AsRef<str>,
+        U: Display,
+    {
+        let c = Colorizer::new(ColorizerOption {
+            use_stderr: true,
+            when: color,
+        });
+        Error::new(
+            ErrorKind::ValueValidation,
+            format!(
+                "Invalid value '{}' for '{}': {}",
+                c.warning(bad_val.as_ref()),
+                c.warning(arg.to_string()),
+                err
+            ),
+            Some(usage.to_string()),
+            Some(vec![arg.name().to_owned(), bad_val.as_ref().to_owned()]),
+            ErrorContext {
+                arg: Some(arg.to_string()),
+                value: Some(bad_val.as_ref().to_owned()),
+                ..Default::default()
+            },
+            &c,
+        )
     }
 
     #[doc(hidden)]
@@ -765,23 +1259,24 @@ impl Error {
             use_stderr: true,
             when: color,
         });
-        Error {
-            message: format!(
-                "{} The argument '{}' requires {} values, but {} w{} \
-                 provided\n\n\
-                 {}\n\n\
-                 For more information try {}",
-                c.error("error:"),
+        Error::new(
+            ErrorKind::WrongNumberOfValues,
+            format!(
+                "The argument '{}' requires {} values, but {} w{} \
+                 provided",
                 c.warning(arg.to_string()),
                 c.warning(num_vals.to_string()),
                 c.warning(curr_vals.to_string()),
-                suffix,
-                usage,
-                c.good("--help")
+                suffix
             ),
-            kind: ErrorKind::WrongNumberOfValues,
-            info: Some(vec![arg.name().to_owned()]),
-        }
+            Some(usage.to_string()),
+            Some(vec![arg.name().to_owned()]),
+            ErrorContext {
+                arg: Some(arg.to_string()),
+                ..Default::default()
+            },
+            &c,
+        )
     }
 
     #[doc(hidden)]
@@ -793,24 +1288,31 @@ impl Error {
             use_stderr: true,
             when: color,
         });
-        Error {
-            message: format!(
-                "{} The argument '{}' was provided more than once, but cannot \
-                 be used multiple times\n\n\
-                 {}\n\n\
-                 For more information try {}",
-                c.error("error:"),
-                c.warning(arg.to_string()),
-                usage,
-                c.good("--help")
+        Error::new(
+            ErrorKind::UnexpectedMultipleUsage,
+            format!(
+                "The argument '{}' was provided more than once, but cannot \
+                 be used multiple times",
+                c.warning(arg.to_string())
             ),
-            kind: ErrorKind::UnexpectedMultipleUsage,
-            info: Some(vec![arg.name().to_owned()]),
-        }
+            Some(usage.to_string()),
+            Some(vec![arg.name().to_owned()]),
+            ErrorContext {
+                arg: Some(arg.to_string()),
+                ..Default::default()
+            },
+            &c,
+        )
     }
 
     #[doc(hidden)]
-    pub fn unknown_argument<A, U>(arg: A, did_you_mean: &str, usage: U, color: ColorWhen) -> Self
+    pub fn unknown_argument<A, U>(
+        arg: A,
+        did_you_mean: &str,
+        suggestion: Option<String>,
+        usage: U,
+        color: ColorWhen,
+    ) -> Self
     where
         A: Into<String>,
         U: Display,
@@ -820,25 +1322,110 @@ impl Error {
             use_stderr: true,
             when: color,
         });
-        Error {
-            message: format!(
-                "{} Found argument '{}' which wasn't expected, or isn't valid in \
-                 this context{}\n\
-                 {}\n\n\
-                 For more information try {}",
-                c.error("error:"),
+        Error::new(
+            ErrorKind::UnknownArgument,
+            format!(
+                "Found argument '{}' which wasn't expected, or isn't valid in \
+                 this context{}",
                 c.warning(&*a),
-                if did_you_mean.is_empty() {
-                    "\n".to_owned()
-                } else {
-                    format!("{}\n", did_you_mean)
-                },
-                usage,
-                c.good("--help")
+                did_you_mean
             ),
-            kind: ErrorKind::UnknownArgument,
-            info: Some(vec![a]),
-        }
+            Some(usage.to_string()),
+            Some(vec![a.clone()]),
+            ErrorContext {
+                arg: Some(a),
+                suggestion: suggestion,
+                ..Default::default()
+            },
+            &c,
+        )
+    }
+
+    #[doc(hidden)]
+    pub fn response_file(path: &str, reason: &str, color: ColorWhen) -> Self {
+        let c = Colorizer::new(ColorizerOption {
+            use_stderr: true,
+            when: color,
+        });
+        Error::new(
+            ErrorKind::Io,
+            format!(
+                "Couldn't read the response file '{}': {}",
+                c.warning(path),
+                reason
+            ),
+            None,
+            Some(vec![path.to_owned()]),
+            ErrorContext {
+                value: Some(path.to_owned()),
+                ..Default::default()
+            },
+            &c,
+        )
+    }
+
+    #[doc(hidden)]
+    pub fn invalid_line(line: &str, reason: &str, color: ColorWhen) -> Self {
+        let c = Colorizer::new(ColorizerOption {
+            use_stderr: true,
+            when: color,
+        });
+        Error::new(
+            ErrorKind::InvalidLine,
+            format!(
+                "Couldn't split the line '{}' into arguments: {}",
+                c.warning(line),
+                reason
+            ),
+            None,
+            Some(vec![line.to_owned()]),
+            ErrorContext {
+                value: Some(line.to_owned()),
+                ..Default::default()
+            },
+            &c,
+        )
+    }
+
+    #[doc(hidden)]
+    pub fn ambiguous_argument<A, U>(arg: A, candidates: &[&str], usage: U, color: ColorWhen) -> Self
+    where
+        A: Into<String>,
+        U: Display,
+    {
+        let a = arg.into();
+        let c = Colorizer::new(ColorizerOption {
+            use_stderr: true,
+            when: color,
+        });
+        let names = candidates
+            .iter()
+            .map(|l| format!("'{}'", c.good(format!("--{}", l))))
+            .collect::<Vec<_>>();
+        let (last, rest) = names.split_last().expect(INTERNAL_ERROR_MSG);
+        let longs = candidates
+            .iter()
+            .map(|l| format!("--{}", l))
+            .collect::<Vec<_>>();
+        let mut info = vec![a.clone()];
+        info.extend(longs.iter().cloned());
+        Error::new(
+            ErrorKind::AmbiguousArgument,
+            format!(
+                "The argument '{}' is ambiguous, it could be {} or {}",
+                c.warning(&*a),
+                rest.join(", "),
+                last
+            ),
+            Some(usage.to_string()),
+            Some(info),
+            ErrorContext {
+                arg: Some(a),
+                valid_values: longs,
+                ..Default::default()
+            },
+            &c,
+        )
     }
 
     #[doc(hidden)]
@@ -847,10 +1434,95 @@ impl Error {
             use_stderr: true,
             when: color,
         });
+        Error::new(
+            ErrorKind::Io,
+            e.message.clone(),
+            None,
+            None,
+            ErrorContext::default(),
+            &c,
+        )
+    }
+
+    #[doc(hidden)]
+    pub fn app_validation<M, U>(
+        message: M,
+        kind: ErrorKind,
+        args: Vec<String>,
+        usage: U,
+        color: ColorWhen,
+    ) -> Self
+    where
+        M: Display,
+        U: Display,
+    {
+        let c = Colorizer::new(ColorizerOption {
+            use_stderr: true,
+            when: color,
+        });
+        Error::new(
+            kind,
+            message.to_string(),
+            Some(usage.to_string()),
+            None,
+            ErrorContext {
+                args: args,
+                ..Default::default()
+            },
+            &c,
+        )
+    }
+
+    #[doc(hidden)]
+    pub fn multiple<U>(errors: Vec<Error>, usage: U, color: ColorWhen) -> Self
+    where
+        U: Display,
+    {
+        let mut all = vec![];
+        for e in errors {
+            if e.kind == ErrorKind::MultipleErrors {
+                all.extend(e.errors);
+            } else {
+                all.push(e);
+            }
+        }
+        if all.len() == 1 {
+            return all.pop().expect(INTERNAL_ERROR_MSG);
+        }
+        let c = Colorizer::new(ColorizerOption {
+            use_stderr: true,
+            when: color,
+        });
+        // Each error ends with its own usage, only the first part is kept for the combined message
+        let bodies = all.iter()
+            .map(|e| match e.message.rfind("\n\nUSAGE:") {
+                Some(i) => e.message[..i].trim_end(),
+                None => e.message.trim_end(),
+            })
+            .collect::<Vec<_>>();
+        let descs = all.iter()
+            .map(|e| e.context.description.trim_end())
+            .collect::<Vec<_>>();
+        let usage = usage.to_string();
+        let message = format!(
+            "{}\n\n\
+             {}\n\n\
+             For more information try {}",
+            bodies.join("\n\n"),
+            usage,
+            c.good("--help")
+        );
+        let description = descs.join("\n\nerror: ");
         Error {
-            message: format!("{} {}", c.error("error:"), e.description()),
-            kind: ErrorKind::Io,
+            message: message,
+            kind: ErrorKind::MultipleErrors,
             info: None,
+            errors: all,
+            context: ErrorContext {
+                usage: Some(strip_colors(&usage)),
+                description: description,
+                ..Default::default()
+            },
         }
     }
 
@@ -864,15 +1536,17 @@ impl Error {
             use_stderr: true,
             when: ColorWhen::Auto,
         });
-        Error {
-            message: format!(
-                "{} The argument '{}' wasn't found",
-                c.error("error:"),
-                a.clone()
-            ),
-            kind: ErrorKind::ArgumentNotFound,
-            info: Some(vec![a]),
-        }
+        Error::new(
+            ErrorKind::ArgumentNotFound,
+            format!("The argument '{}' wasn't found", a),
+            None,
+            Some(vec![a.clone()]),
+            ErrorContext {
+                arg: Some(a),
+                ..Default::default()
+            },
+            &c,
+        )
     }
 
     /// Create an error with a custom description.
@@ -884,12 +1558,52 @@ impl Error {
             use_stderr: true,
             when: ColorWhen::Auto,
         });
-        Error {
-            message: format!("{} {}", c.error("error:"), description),
-            kind: kind,
-            info: None,
+        Error::new(
+            kind,
+            description.to_owned(),
+            None,
+            None,
+            ErrorContext::default(),
+            &c,
+        )
+    }
+}
+
+// Removes the ANSI escape sequences the message was colored with
+fn strip_colors(s: &str) -> String {
+    let mut plain = String::with_capacity(s.len());
+    let mut in_escape = false;
+    for ch in s.chars() {
+        if in_escape {
+            in_escape = ch != 'm';
+        } else if ch == '\u{1b}' {
+            in_escape = true;
+        } else {
+            plain.push(ch);
         }
     }
+    plain
+}
+
+/// When `all` is set, stores an error about the arguments in `errors` instead of returning it, so
+/// that parsing and validation can go on and report it along with any later ones.
+pub fn collect(all: bool, res: Result<()>, errors: &mut Vec<Error>) -> Result<()> {
+    match res {
+        Err(e) => match e.kind {
+            ErrorKind::HelpDisplayed
+            | ErrorKind::VersionDisplayed
+            | ErrorKind::CompletionsDisplayed
+            | ErrorKind::MissingArgumentOrSubcommand
+            | ErrorKind::Io
+            | ErrorKind::Format => Err(e),
+            _ if all => {
+                errors.push(e);
+                Ok(())
+            }
+            _ => Err(e),
+        },
+        Ok(()) => Ok(()),
+    }
 }
 
 impl StdError for Error {
@@ -355,6 +355,12 @@
 //! #### Opt-in features
 //!
 //! * **"yaml"**: Enables building CLIs from YAML documents. (builds dependency `yaml-rust`)
+//! * **"derive"**: Enables `#[derive(Clap)]` to build an `App` from a struct or enum and convert
+//!   the `ArgMatches` back into it. (builds dependency `clap_derive`)
+//! * **"serde_defs"**: Enables loading an `AppDef` from, and saving it to, JSON or TOML documents using
+//!   the same layout as the YAML files. (builds dependencies `serde`, `serde_json` and `toml`)
+//!   It isn't called `serde` because Cargo already names a feature after the optional `serde`
+//!   dependency, which doesn't enable the other two.
 //! * **"unstable"**: Enables unstable `clap` features that may change from release to release
 //!
 //! ### Dependencies Tree
@@ -534,11 +540,19 @@ extern crate ansi_term;
 extern crate atty;
 #[macro_use]
 extern crate bitflags;
+#[cfg(feature = "derive")]
+extern crate clap_derive;
+#[cfg(feature = "serde_defs")]
+extern crate serde;
+#[cfg(feature = "serde_defs")]
+extern crate serde_json;
 #[cfg(feature = "suggestions")]
 extern crate strsim;
 #[cfg(feature = "wrap_help")]
 extern crate term_size;
 extern crate textwrap;
+#[cfg(feature = "serde_defs")]
+extern crate toml;
 extern crate unicode_width;
 #[cfg(feature = "vec_map")]
 extern crate vec_map;
@@ -547,11 +561,20 @@ extern crate yaml_rust;
 
 #[cfg(feature = "yaml")]
 pub use yaml_rust::YamlLoader;
-pub use args::{Arg, ArgGroup, ArgMatches, ArgSettings, OsValues, SubCommand, Values};
-pub use app::{App, AppSettings};
+pub use args::{Arg, ArgGroup, ArgMatches, ArgSettings, Constraint, KeyValues, OsValues,
+               SubCommand, SubCommands, TypedValues, ValueProvider, ValueSource, ValueSources,
+               Values};
+pub use app::{App, AppSettings, DocFormat, Repl};
 pub use fmt::Format;
-pub use errors::{Error, ErrorKind, Result};
+pub use errors::{Error, ErrorContext, ErrorKind, ErrorStyle, Result};
 pub use completions::Shell;
+pub use def::{AppDef, ArgDef, ArgGroupDef};
+#[cfg(feature = "serde_defs")]
+pub use def::DefError;
+#[cfg(feature = "derive")]
+pub use clap_derive::Clap;
+#[cfg(feature = "derive")]
+pub use derive::{ClapApp, FromArgMatches, IntoApp, Subcommand};
 
 #[macro_use]
 mod macros;
@@ -563,63 +586,13 @@ mod suggestions;
 mod errors;
 mod osstringext;
 mod strext;
+mod shell_words;
 mod completions;
 mod map;
+mod def;
+#[cfg(feature = "derive")]
+mod derive;
 
 const INTERNAL_ERROR_MSG: &'static str = "Fatal internal error. Please consider filing a bug \
                                           report at https://github.com/kbknapp/clap-rs/issues";
 const INVALID_UTF8: &'static str = "unexpected invalid UTF-8 code point";
-
-#[cfg(unstable)]
-pub use derive::{ArgEnum, ClapApp, FromArgMatches, IntoApp};
-
-#[cfg(unstable)]
-mod derive {
-    /// @TODO @release @docs
-    pub trait ClapApp: IntoApp + FromArgMatches + Sized {
-        /// @TODO @release @docs
-        fn parse() -> Self { Self::from_argmatches(Self::into_app().get_matches()) }
-
-        /// @TODO @release @docs
-        fn parse_from<I, T>(argv: I) -> Self
-        where
-            I: IntoIterator<Item = T>,
-            T: Into<OsString> + Clone,
-        {
-            Self::from_argmatches(Self::into_app().get_matches_from(argv))
-        }
-
-        /// @TODO @release @docs
-        fn try_parse() -> Result<Self, clap::Error> {
-            Self::try_from_argmatches(Self::into_app().get_matches_safe()?)
-        }
-
-
-        /// @TODO @release @docs
-        fn try_parse_from<I, T>(argv: I) -> Result<Self, clap::Error>
-        where
-            I: IntoIterator<Item = T>,
-            T: Into<OsString> + Clone,
-        {
-            Self::try_from_argmatches(Self::into_app().get_matches_from_safe(argv)?)
-        }
-    }
-
-    /// @TODO @release @docs
-    pub trait IntoApp {
-        /// @TODO @release @docs
-        fn into_app<'a, 'b>() -> clap::App<'a, 'b>;
-    }
-
-    /// @TODO @release @docs
-    pub trait FromArgMatches: Sized {
-        /// @TODO @release @docs
-        fn from_argmatches<'a>(matches: clap::ArgMatches<'a>) -> Self;
-
-        /// @TODO @release @docs
-        fn try_from_argmatches<'a>(matches: clap::ArgMatches<'a>) -> Result<Self, clap::Error>;
-    }
-
-    /// @TODO @release @docs
-    pub trait ArgEnum {}
-}
@@ -37,6 +37,9 @@ macro_rules! load_yaml {
 /// and those where the argument wasn't present (such as a non-required argument). You can use
 /// it to get a single value, or a iterator as with the [`ArgMatches::values_of`]
 ///
+/// **NOTE:** This re-parses the value on every call; [`Arg::value_parser`] together with
+/// [`ArgMatches::get`] parses it once, while the arguments are validated.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -57,6 +60,8 @@ macro_rules! load_yaml {
 /// [`std::str::FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
 /// [`ArgMatches::values_of`]: ./struct.ArgMatches.html#method.values_of
 /// [`Result<T,String>`]: https://doc.rust-lang.org/std/result/enum.Result.html
+/// [`Arg::value_parser`]: ./struct.Arg.html#method.value_parser
+/// [`ArgMatches::get`]: ./struct.ArgMatches.html#method.get
 #[macro_export]
 macro_rules! value_t {
     ($m:ident, $v:expr, $t:ty) => {
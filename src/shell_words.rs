@@ -0,0 +1,160 @@
+// Splits `s` into words the way a POSIX shell would, without doing any expansion.
+//
+// Words are separated by whitespace. Single quotes keep everything up to the next single quote
+// as is, double quotes do the same except that a backslash still escapes `"`, `\`, `$` and `` ` ``,
+// and outside of quotes a backslash escapes any character, or joins two lines when followed by a
+// newline.
+pub fn split(s: &str) -> Result<Vec<String>, String> {
+    let mut sp = scan(s);
+    if let Some(e) = sp.error {
+        return Err(e);
+    }
+    if sp.in_word {
+        sp.words.push(sp.word);
+    }
+    Ok(sp.words)
+}
+
+// Splits a line which is still being typed, returning the finished words separately from the
+// last one, which is empty if the line ends in whitespace. Unclosed quotes are ignored since the
+// last word may well be in the middle of one.
+pub fn split_incomplete(s: &str) -> (Vec<String>, String) {
+    let sp = scan(s);
+    (sp.words, sp.word)
+}
+
+struct Scan {
+    words: Vec<String>,
+    // The last word, unless `s` ended in whitespace
+    word: String,
+    in_word: bool,
+    error: Option<String>,
+}
+
+fn scan(s: &str) -> Scan {
+    #[derive(PartialEq)]
+    enum State {
+        Unquoted,
+        Single,
+        Double,
+    }
+
+    let mut error = None;
+    let mut words = vec![];
+    let mut word = String::new();
+    // Quotes start a word even if nothing follows, i.e. `''` is an empty word
+    let mut in_word = false;
+    let mut state = State::Unquoted;
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match state {
+            State::Unquoted => match c {
+                '\'' => {
+                    state = State::Single;
+                    in_word = true;
+                }
+                '"' => {
+                    state = State::Double;
+                    in_word = true;
+                }
+                '\\' => match chars.next() {
+                    Some('\n') => (),
+                    Some(e) => {
+                        word.push(e);
+                        in_word = true;
+                    }
+                    None => {
+                        error = Some(String::from("a backslash doesn't escape anything"));
+                        break;
+                    }
+                },
+                c if c.is_whitespace() => if in_word {
+                    words.push(word.clone());
+                    word.clear();
+                    in_word = false;
+                },
+                c => {
+                    word.push(c);
+                    in_word = true;
+                }
+            },
+            State::Single => match c {
+                '\'' => state = State::Unquoted,
+                c => word.push(c),
+            },
+            State::Double => match c {
+                '"' => state = State::Unquoted,
+                '\\' => match chars.next() {
+                    Some(e @ '"') | Some(e @ '\\') | Some(e @ '$') | Some(e @ '`') => word.push(e),
+                    Some('\n') => (),
+                    Some(e) => {
+                        word.push('\\');
+                        word.push(e);
+                    }
+                    None => break,
+                },
+                c => word.push(c),
+            },
+        }
+    }
+    if state != State::Unquoted {
+        error = Some(String::from("a quote isn't closed"));
+    }
+    Scan {
+        words: words,
+        word: word,
+        in_word: in_word,
+        error: error,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{split, split_incomplete};
+
+    #[test]
+    fn whitespace() {
+        assert_eq!(split("  a b\t\tc\n d  ").unwrap(), ["a", "b", "c", "d"]);
+        assert!(split(" \n ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn quotes() {
+        assert_eq!(
+            split(r#"'a b' "c d" e'f'"g" '' "" 'h"i' "j'k""#).unwrap(),
+            ["a b", "c d", "efg", "", "", "h\"i", "j'k"]
+        );
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(
+            split(r#"a\ b \'c "d\"e\\f\g" 'h\i' j\
+k"#).unwrap(),
+            ["a b", "'c", "d\"e\\f\\g", "h\\i", "jk"]
+        );
+    }
+
+    #[test]
+    fn unclosed() {
+        assert!(split("'a").is_err());
+        assert!(split("\"a").is_err());
+        assert!(split("a\\").is_err());
+    }
+
+    #[test]
+    fn incomplete() {
+        assert_eq!(
+            split_incomplete("a 'b c' d"),
+            (vec![String::from("a"), String::from("b c")], String::from("d"))
+        );
+        assert_eq!(
+            split_incomplete("a \"b c"),
+            (vec![String::from("a")], String::from("b c"))
+        );
+        assert_eq!(
+            split_incomplete("a b "),
+            (vec![String::from("a"), String::from("b")], String::new())
+        );
+    }
+}
@@ -0,0 +1,146 @@
+extern crate clap;
+extern crate regex;
+
+include!("../clap-test.rs");
+
+use clap::{App, AppSettings, Arg, ErrorKind, SubCommand};
+
+static ALL_ERRORS: &'static str = "error: Found argument '--colour' which wasn't expected, or isn't valid in this context
+	Did you mean --color?
+
+error: The following required arguments were not provided:
+    <input>
+    --out <out>
+
+error: 'medium' isn't a valid value for '--mode <mode>'
+	[possible values: fast, slow]
+
+USAGE:
+    ctest <input> --mode <mode> --out <out>
+
+For more information try --help";
+
+fn app() -> App<'static, 'static> {
+    App::new("ctest")
+        .setting(AppSettings::ReportAllErrors)
+        .arg(Arg::with_name("input").required(true))
+        .arg(Arg::with_name("color").long("color"))
+        .arg(
+            Arg::with_name("mode")
+                .long("mode")
+                .takes_value(true)
+                .possible_values(&["fast", "slow"]),
+        )
+        .arg(
+            Arg::with_name("out")
+                .long("out")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("jobs")
+                .short("j")
+                .takes_value(true)
+                .value_parser(|s| s.parse::<u32>().map(|_| ()).map_err(|e| e.to_string())),
+        )
+}
+
+#[test]
+fn all_errors_kinds() {
+    let res = app().get_matches_from_safe(vec!["ctest", "--mode", "medium", "--colour", "-j", "x"]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MultipleErrors);
+    let kinds: Vec<_> = err.errors().iter().map(|e| e.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            ErrorKind::UnknownArgument,
+            ErrorKind::MissingRequiredArgument,
+            ErrorKind::InvalidValue,
+            ErrorKind::ValueValidation,
+        ]
+    );
+    assert!(err.errors()[0].message.contains("Did you mean"));
+    assert_eq!(err.errors()[0].info, Some(vec!["--colour".to_owned()]));
+}
+
+#[test]
+fn all_errors_output() {
+    assert!(test::compare_output(
+        app(),
+        "ctest --mode medium --colour",
+        ALL_ERRORS,
+        true
+    ));
+}
+
+#[test]
+fn all_errors_single() {
+    let res = app().get_matches_from_safe(vec!["ctest", "in", "--out", "o", "--mode", "medium"]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidValue);
+    assert!(err.errors().is_empty());
+}
+
+#[test]
+fn all_errors_ok() {
+    let m = app()
+        .get_matches_from_safe(vec!["ctest", "in", "--out", "o", "-j", "4"])
+        .unwrap();
+
+    assert_eq!(m.value_of("input"), Some("in"));
+}
+
+#[test]
+fn all_errors_conflicts() {
+    let res = App::new("ctest")
+        .setting(AppSettings::ReportAllErrors)
+        .arg(Arg::with_name("fast").long("fast").conflicts_with("safe"))
+        .arg(Arg::with_name("safe").long("safe"))
+        .arg(Arg::with_name("level").long("level").takes_value(true).required(true))
+        .get_matches_from_safe(vec!["ctest", "--fast", "--safe"]);
+
+    assert!(res.is_err());
+    let kinds: Vec<_> = res.unwrap_err().errors().iter().map(|e| e.kind).collect();
+    assert_eq!(
+        kinds,
+        [ErrorKind::ArgumentConflict, ErrorKind::MissingRequiredArgument]
+    );
+}
+
+#[test]
+fn all_errors_subcommand() {
+    let res = App::new("ctest")
+        .global_setting(AppSettings::ReportAllErrors)
+        .arg(Arg::with_name("input").required(true))
+        .subcommand(SubCommand::with_name("sub").arg(Arg::with_name("name").required(true)))
+        .get_matches_from_safe(vec!["ctest", "--bad", "sub", "--worse"]);
+
+    assert!(res.is_err());
+    let kinds: Vec<_> = res.unwrap_err().errors().iter().map(|e| e.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            ErrorKind::UnknownArgument,
+            ErrorKind::UnknownArgument,
+            ErrorKind::MissingRequiredArgument,
+            ErrorKind::MissingRequiredArgument,
+        ]
+    );
+}
+
+#[test]
+fn first_error_without_setting() {
+    let res = app()
+        .unset_setting(AppSettings::ReportAllErrors)
+        .get_matches_from_safe(vec!["ctest", "--mode", "medium", "--colour"]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownArgument);
+    assert!(err.errors().is_empty());
+}
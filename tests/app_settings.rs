@@ -250,6 +250,145 @@ fn infer_subcommands_fail_suggestions() {
     assert_eq!(m.unwrap_err().kind, ErrorKind::UnrecognizedSubcommand);
 }
 
+fn infer_long_args_app() -> App<'static, 'static> {
+    App::new("prog")
+        .setting(AppSettings::InferLongArgs)
+        .arg(Arg::from_usage("--verbose 'use verbose output'").alias("loud"))
+        .arg(Arg::from_usage("--verify 'verify the results'"))
+        .arg(Arg::from_usage("--color [when] 'when to use colors'"))
+        .arg(Arg::from_usage("--colors [scheme] 'the color scheme'"))
+}
+
+#[test]
+fn infer_long_args_pass() {
+    let m = infer_long_args_app()
+        .get_matches_from(vec!["prog", "--verb", "--veri", "--color=auto", "--colors", "dark"]);
+    assert!(m.is_present("verbose"));
+    assert!(m.is_present("verify"));
+    assert_eq!(m.value_of("color"), Some("auto"));
+    assert_eq!(m.value_of("colors"), Some("dark"));
+}
+
+#[test]
+fn infer_long_args_alias() {
+    let m = infer_long_args_app().get_matches_from(vec!["prog", "--lo"]);
+    assert!(m.is_present("verbose"));
+}
+
+#[test]
+fn infer_long_args_ambiguous_with_value() {
+    let res = infer_long_args_app().get_matches_from_safe(vec!["prog", "--colo=auto"]);
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::AmbiguousArgument);
+    assert_eq!(
+        err.info,
+        Some(vec!["--colo".to_owned(), "--color".to_owned(), "--colors".to_owned()])
+    );
+}
+
+#[test]
+fn infer_long_args_help() {
+    let res = infer_long_args_app().get_matches_from_safe(vec!["prog", "--he"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::HelpDisplayed);
+}
+
+#[test]
+fn infer_long_args_ambiguous() {
+    let res = infer_long_args_app().get_matches_from_safe(vec!["prog", "--ve"]);
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::AmbiguousArgument);
+    assert_eq!(
+        err.info,
+        Some(vec![
+            "--ve".to_owned(),
+            "--verbose".to_owned(),
+            "--verify".to_owned(),
+            "--version".to_owned(),
+        ])
+    );
+    assert!(err.message.starts_with(
+        "error: The argument '--ve' is ambiguous, it could be '--verbose', '--verify' or \
+         '--version'"
+    ));
+}
+
+#[test]
+fn infer_long_args_unknown() {
+    let res = infer_long_args_app().get_matches_from_safe(vec!["prog", "--verbosity"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::UnknownArgument);
+}
+
+#[test]
+fn infer_long_args_needs_setting() {
+    let res = App::new("prog")
+        .arg(Arg::from_usage("--verbose 'use verbose output'"))
+        .get_matches_from_safe(vec!["prog", "--verb"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::UnknownArgument);
+}
+
+fn multicall_app() -> App<'static, 'static> {
+    App::new("busybox")
+        .setting(AppSettings::Multicall)
+        .setting(AppSettings::SubcommandRequired)
+        .subcommand(SubCommand::with_name("true"))
+        .subcommand(SubCommand::with_name("echo")
+            .alias("print")
+            .arg(Arg::from_usage("-n 'no trailing newline'"))
+            .arg(Arg::from_usage("[text]... 'the text to print'")))
+}
+
+#[test]
+fn multicall_applet() {
+    let m = multicall_app().get_matches_from(vec!["/usr/bin/echo", "-n", "hi", "there"]);
+    assert_eq!(m.subcommand_name(), Some("echo"));
+    let sub_m = m.subcommand_matches("echo").unwrap();
+    assert!(sub_m.is_present("n"));
+    assert_eq!(sub_m.values_of("text").unwrap().collect::<Vec<_>>(), ["hi", "there"]);
+}
+
+#[test]
+fn multicall_applet_alias() {
+    let m = multicall_app().get_matches_from(vec!["print", "hi"]);
+    assert_eq!(m.subcommand_matches("echo").unwrap().value_of("text"), Some("hi"));
+}
+
+#[test]
+fn multicall_main_name() {
+    let m = multicall_app().get_matches_from(vec!["./busybox", "echo", "hi"]);
+    assert_eq!(m.subcommand_matches("echo").unwrap().value_of("text"), Some("hi"));
+
+    let res = multicall_app().get_matches_from_safe(vec!["busybox"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::MissingSubcommand);
+}
+
+#[test]
+fn multicall_applet_usage() {
+    let res = multicall_app().get_matches_from_safe(vec!["/bin/echo", "--bogus"]);
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnknownArgument);
+    assert!(err.message.contains("USAGE:\n    echo [FLAGS] [text]...\n"));
+
+    let res = multicall_app().get_matches_from_safe(vec!["true", "--help"]);
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.contains("USAGE:\n    true\n"));
+}
+
+#[test]
+fn multicall_needs_setting() {
+    let res = App::new("busybox")
+        .subcommand(SubCommand::with_name("true"))
+        .get_matches_from_safe(vec!["true", "true"]);
+    assert_eq!(res.unwrap().subcommand_name(), Some("true"));
+
+    let res = App::new("busybox")
+        .subcommand(SubCommand::with_name("true"))
+        .get_matches_from_safe(vec!["true"]);
+    assert_eq!(res.unwrap().subcommand_name(), None);
+}
+
 #[test]
 fn no_bin_name() {
     let result = App::new("arg_required")
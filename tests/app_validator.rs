@@ -0,0 +1,120 @@
+extern crate clap;
+extern crate regex;
+
+include!("../clap-test.rs");
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use clap::{App, Arg, ErrorKind, SubCommand};
+
+static RANGE_ERROR: &'static str = "error: '--start' must be before '--end'
+
+USAGE:
+    ctest [OPTIONS] --end <end>
+
+For more information try --help";
+
+fn range() -> App<'static, 'static> {
+    App::new("ctest")
+        .arg(Arg::with_name("start").long("start").takes_value(true))
+        .arg(
+            Arg::with_name("end")
+                .long("end")
+                .takes_value(true)
+                .required(true),
+        )
+        .validator(|m| {
+            let start = m.value_of("start").map_or(0, |s| s.parse().unwrap_or(0));
+            let end: u32 = m.value_of("end").unwrap().parse().unwrap_or(0);
+            if start > end {
+                Err(m.error(
+                    ErrorKind::ValueValidation,
+                    "'--start' must be before '--end'",
+                ))
+            } else {
+                Ok(())
+            }
+        })
+}
+
+#[test]
+fn app_validator_ok() {
+    let m = range()
+        .get_matches_from_safe(vec!["ctest", "--start", "1", "--end", "2"])
+        .unwrap();
+
+    assert_eq!(m.value_of("start"), Some("1"));
+}
+
+#[test]
+fn app_validator_fail() {
+    let res = range().get_matches_from_safe(vec!["ctest", "--start", "3", "--end", "2"]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueValidation);
+    assert!(err.info.is_none());
+}
+
+#[test]
+fn app_validator_output() {
+    assert!(test::compare_output(
+        range(),
+        "ctest --start 3 --end 2",
+        RANGE_ERROR,
+        true
+    ));
+}
+
+#[test]
+fn app_validator_after_other_errors() {
+    let res = range().get_matches_from_safe(vec!["ctest", "--start", "3"]);
+
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+}
+
+#[test]
+fn app_validator_custom_error() {
+    let res = App::new("ctest")
+        .validator(|_| Err(clap::Error::with_description("nope", ErrorKind::Io)))
+        .get_matches_from_safe(vec!["ctest"]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Io);
+    assert_eq!(err.message, "error: nope");
+}
+
+#[test]
+fn app_validator_subcommand() {
+    let runs = Rc::new(Cell::new(0));
+    let app_runs = Rc::clone(&runs);
+    let sc_runs = Rc::clone(&runs);
+    let res = App::new("ctest")
+        .validator(move |m| {
+            app_runs.set(app_runs.get() + 1);
+            assert!(m.subcommand_matches("sub").is_some());
+            Ok(())
+        })
+        .subcommand(
+            SubCommand::with_name("sub")
+                .arg(Arg::with_name("flag").long("flag"))
+                .validator(move |m| {
+                    sc_runs.set(sc_runs.get() + 10);
+                    if m.is_present("flag") {
+                        Ok(())
+                    } else {
+                        Err(m.error(ErrorKind::MissingRequiredArgument, "'--flag' is required"))
+                    }
+                }),
+        )
+        .get_matches_from_safe(vec!["ctest", "sub"]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+    assert!(err.message.contains("ctest sub [FLAGS]"));
+    assert_eq!(runs.get(), 10);
+}
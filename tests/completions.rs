@@ -1,7 +1,11 @@
 extern crate regex;
 extern crate clap;
 
-use clap::{App, Arg, SubCommand, Shell};
+use std::env;
+use std::fs::{self, File};
+use std::io::Read;
+
+use clap::{App, AppSettings, Arg, ErrorKind, SubCommand, Shell};
 use regex::Regex;
 
 static BASH: &'static str = r#"_myapp() {
@@ -639,6 +643,96 @@ _my_app_commands() {
 
 _my_app "$@""#;
 
+static ELVISH: &'static str = r#"use str
+
+set edit:completion:arg-completer[myapp] = {|@words|
+    fn cand {|text desc|
+        edit:complex-candidate $text &display=$text' '$desc
+    }
+    var subcommands = [
+        &'myapp;test'='myapp;test'
+        &'myapp;help'='myapp;help'
+    ]
+    var options = [
+        &'myapp;test;--case'=[]
+    ]
+    var command = 'myapp'
+    var option = ''
+    for word $words[1..-1] {
+        if (!=s $option '') {
+            set option = ''
+        } elif (has-key $options $command';'$word) {
+            set option = $command';'$word
+        } elif (has-key $subcommands $command';'$word) {
+            set command = $subcommands[$command';'$word]
+        }
+    }
+    var completions = [
+        &'myapp'= {
+            cand -h 'Prints help information'
+            cand --help 'Prints help information'
+            cand -V 'Prints version information'
+            cand --version 'Prints version information'
+            cand 'test' 'tests things'
+            cand 'help' 'Prints this message or the help of the given subcommand(s)'
+        }
+        &'myapp;test'= {
+            cand -h 'Prints help information'
+            cand --help 'Prints help information'
+            cand -V 'Prints version information'
+            cand --version 'Prints version information'
+            cand --case 'the case to test'
+        }
+        &'myapp;help'= {
+            cand -h 'Prints help information'
+            cand --help 'Prints help information'
+            cand -V 'Prints version information'
+            cand --version 'Prints version information'
+        }
+    ]
+    if (!=s $option '') {
+        all $options[$option]
+    } else {
+        $completions[$command]
+    }
+}
+"#;
+
+static NUSHELL: &'static str = r#"module completions {
+
+  # Tests completions
+  export extern "myapp" [
+    --help(-h)     # Prints help information
+    --version(-V)  # Prints version information
+    file?: string  # some input file
+  ]
+
+  # tests things
+  export extern "myapp test" [
+    --help(-h)      # Prints help information
+    --version(-V)   # Prints version information
+    --case: string  # the case to test
+  ]
+
+  # Prints this message or the help of the given subcommand(s)
+  export extern "myapp help" [
+    --help(-h)     # Prints help information
+    --version(-V)  # Prints version information
+  ]
+
+}
+
+export use completions *
+"#;
+
+static DYNAMIC_BASH: &'static str = r#"_my_app() {
+    local IFS=$'\n'
+    COMPREPLY=( $("${COMP_WORDS[0]}" --complete "${COMP_CWORD}" -- "${COMP_WORDS[@]}" 2>/dev/null | cut -f1) )
+}
+
+complete -F _my_app -o bashdefault -o default my_app
+"#;
+
 fn compare(left: &str, right: &str) -> bool {
     let b = left == right;
     if !b {
@@ -752,6 +846,239 @@ fn powershell_with_special_commands() {
     assert!(compare(&*string, POWERSHELL_SPECIAL_CMDS));
 }
 
+#[test]
+fn elvish() {
+    let mut app = build_app();
+    let mut buf = vec![];
+    app.gen_completions_to("myapp", Shell::Elvish, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(compare(&*string, ELVISH));
+}
+
+#[test]
+fn nushell() {
+    let mut app = build_app();
+    let mut buf = vec![];
+    app.gen_completions_to("myapp", Shell::Nushell, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(compare(&*string, NUSHELL));
+}
+
+fn build_app_values_and_aliases() -> App<'static, 'static> {
+    build_app_with_name("myapp")
+        .arg(Arg::with_name("color")
+            .short("C")
+            .long("color")
+            .takes_value(true)
+            .possible_values(&["always", "never"])
+            .help("when to use colors"))
+        .arg(Arg::with_name("verbose")
+            .short("v")
+            .long("verbose")
+            .visible_alias("loud")
+            .help("more 'output'"))
+        .subcommand(SubCommand::with_name("run").visible_alias("r").alias("go"))
+}
+
+#[test]
+fn elvish_values_and_aliases() {
+    let mut buf = vec![];
+    build_app_values_and_aliases().gen_completions_to("myapp", Shell::Elvish, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains("&'myapp;-C'=['always' 'never']"));
+    assert!(string.contains("&'myapp;--color'=['always' 'never']"));
+    assert!(string.contains("&'myapp;r'='myapp;run'"));
+    assert!(string.contains("&'myapp;go'='myapp;run'"));
+    assert!(string.contains("cand --loud 'more ''output'''"));
+    assert!(string.contains("cand 'r' 'r'"));
+    assert!(!string.contains("cand 'go'"));
+}
+
+#[test]
+fn nushell_values_and_aliases() {
+    let mut buf = vec![];
+    build_app_values_and_aliases().gen_completions_to("myapp", Shell::Nushell, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains("  def \"nu-complete myapp color\" [] {\n    [ \"always\" \"never\" ]\n  }\n"));
+    assert!(string.contains("--color(-C): string@\"nu-complete myapp color\"  # when to use colors"));
+    assert!(string.contains("--verbose(-v)"));
+    assert!(string.contains("--loud "));
+    assert!(string.contains("export extern \"myapp r\" ["));
+    assert!(string.contains("export extern \"myapp go\" ["));
+}
+
+fn build_app_infer_long_args() -> App<'static, 'static> {
+    build_app_with_name("myapp")
+        .setting(AppSettings::InferLongArgs)
+        .setting(AppSettings::DynamicCompletion)
+        .arg(Arg::with_name("config")
+            .long("config")
+            .takes_value(true)
+            .possible_values(&["debug", "release"]))
+        .arg(Arg::with_name("color")
+            .long("color")
+            .takes_value(true)
+            .possible_values(&["always", "never"]))
+}
+
+#[test]
+fn bash_infer_long_args() {
+    let mut buf = vec![];
+    build_app_infer_long_args().gen_completions_to("myapp", Shell::Bash, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains("\n                --config|--con|--conf|--confi)\n"));
+    assert!(string.contains("\n                --color|--col|--colo)\n"));
+}
+
+#[test]
+fn elvish_infer_long_args() {
+    let mut buf = vec![];
+    build_app_infer_long_args().gen_completions_to("myapp", Shell::Elvish, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains("&'myapp;--conf'=['debug' 'release']"));
+    assert!(string.contains("&'myapp;--colo'=['always' 'never']"));
+    assert!(!string.contains("&'myapp;--co'"));
+}
+
+#[test]
+fn zsh_infer_long_args() {
+    let mut buf = vec![];
+    build_app_infer_long_args().gen_completions_to("myapp", Shell::Zsh, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains("\n'!--conf=[]: :(debug release)' \\\n"));
+    assert!(string.contains("\n'!--colo=[]: :(always never)' \\\n"));
+    assert!(!string.contains("'--conf=["));
+    assert!(!string.contains("--co=["));
+}
+
+#[test]
+fn fish_infer_long_args() {
+    let mut buf = vec![];
+    build_app_infer_long_args().gen_completions_to("myapp", Shell::Fish, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains(" -l config -r -f -a \"debug release\"\n"));
+    assert!(string.contains(
+        "complete -c myapp -n \"__fish_using_command myapp --conf\" -f -a \"debug release\"\n"
+    ));
+    assert!(string.contains(
+        "complete -c myapp -n \"__fish_using_command myapp --colo\" -f -a \"always never\"\n"
+    ));
+    assert!(!string.contains("-l conf "));
+}
+
+#[test]
+fn powershell_infer_long_args() {
+    let mut buf = vec![];
+    build_app_infer_long_args().gen_completions_to("myapp", Shell::PowerShell, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains("[CompletionResult]::new('--config', 'config', "));
+    assert!(!string.contains("'--conf'"));
+    assert!(!string.contains("'--colo'"));
+}
+
+#[test]
+fn nushell_infer_long_args() {
+    let mut buf = vec![];
+    build_app_infer_long_args().gen_completions_to("myapp", Shell::Nushell, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains("    --config: string@\"nu-complete myapp config\"\n"));
+    assert!(!string.contains("    --conf:"));
+    assert!(!string.contains("    --colo:"));
+}
+
+#[test]
+fn dynamic_infer_long_args() {
+    assert_eq!(
+        complete(build_app_infer_long_args(), 2, &["myapp", "--conf", "d"]),
+        "debug"
+    );
+    assert_eq!(
+        complete(build_app_infer_long_args(), 1, &["myapp", "--colo=n"]),
+        "--colo=never"
+    );
+    // `--co` could be either option
+    assert!(!complete(build_app_infer_long_args(), 2, &["myapp", "--co", ""]).contains("debug"));
+}
+
+fn build_app_negatable() -> App<'static, 'static> {
+    build_app_with_name("myapp")
+        .setting(AppSettings::DynamicCompletion)
+        .arg(Arg::with_name("color")
+            .long("color")
+            .negatable(true)
+            .help("use colors"))
+}
+
+fn negatable_completions(shell: Shell) -> String {
+    let mut buf = vec![];
+    build_app_negatable().gen_completions_to("myapp", shell, &mut buf);
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn negatable_flags() {
+    assert!(negatable_completions(Shell::Bash).contains(" --version --no-color "));
+    assert!(negatable_completions(Shell::Zsh)
+        .contains("'(--color --no-color)'{--color,--no-color}'[use colors]' \\"));
+    assert!(negatable_completions(Shell::Fish)
+        .contains("-n \"__fish_using_command myapp\" -l color -l no-color -d 'use colors'"));
+    assert!(negatable_completions(Shell::PowerShell).contains(
+        "[CompletionResult]::new('--no-color', 'no-color', [CompletionResultType]::ParameterName, 'use colors')"
+    ));
+    assert!(negatable_completions(Shell::Elvish).contains("cand --no-color 'use colors'"));
+    assert!(negatable_completions(Shell::Nushell).contains("\n    --no-color     # use colors\n"));
+    assert_eq!(
+        complete(build_app_negatable(), 1, &["myapp", "--no"]),
+        "--no-color\tuse colors"
+    );
+}
+
+#[test]
+fn multicall_applet_files() {
+    let dir = env::temp_dir().join("clap-multicall-completions");
+    fs::create_dir_all(&dir).unwrap();
+    build_app_with_name("busybox")
+        .setting(AppSettings::Multicall)
+        .gen_completions("mc-busybox", Shell::Bash, &*dir);
+
+    let read = |name: &str| {
+        let mut string = String::new();
+        File::open(dir.join(name))
+            .unwrap()
+            .read_to_string(&mut string)
+            .unwrap();
+        string
+    };
+    let main = read("mc-busybox.bash");
+    let applet = read("test.bash");
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(main.contains("complete -F _mc-busybox -o bashdefault -o default mc-busybox\n"));
+    assert!(main.contains("cmd+=\"__test\""));
+    assert!(applet.contains("complete -F _test -o bashdefault -o default test\n"));
+    assert!(applet.contains("opts=\" -h -V  --help --version --case  \""));
+    assert!(!applet.contains("mc-busybox"));
+}
+
+#[test]
+fn shell_variants() {
+    for v in Shell::variants() {
+        assert!(v.parse::<Shell>().is_ok());
+    }
+    assert!("elvish".parse::<Shell>().is_ok());
+    assert!("NUSHELL".parse::<Shell>().is_ok());
+}
+
 #[test]
 fn bash_with_special_commands() {
     let mut app = build_app_special_commands();
@@ -801,3 +1128,112 @@ fn zsh_with_special_help() {
 
     assert!(compare(&*string, ZSH_SPECIAL_HELP));
 }
+
+fn build_app_dynamic() -> App<'static, 'static> {
+    build_app_with_name("myapp")
+        .setting(AppSettings::DynamicCompletion)
+        .arg(Arg::with_name("color")
+            .long("color")
+            .takes_value(true)
+            .possible_values(&["always", "auto", "never"])
+            .help("when to use colors"))
+        .arg(Arg::with_name("secret").long("secret").hidden(true))
+        .subcommand(SubCommand::with_name("checkout")
+            .arg(Arg::with_name("branch")
+                .index(1)
+                .completer(|cur: &str| {
+                    vec!["master", "maint", "next"]
+                        .into_iter()
+                        .filter(|b| b.starts_with(cur))
+                        .map(String::from)
+                        .collect()
+                })))
+}
+
+fn complete(app: App, cword: usize, words: &[&str]) -> String {
+    let mut args = vec!["myapp".to_owned(), "--complete".to_owned(), cword.to_string(), "--".to_owned()];
+    args.extend(words.iter().map(|w| w.to_string()));
+    let err = app.get_matches_from_safe(args).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::CompletionsDisplayed);
+    err.message
+}
+
+#[test]
+fn dynamic_bash() {
+    let mut app = build_app_dynamic();
+    let mut buf = vec![];
+    app.gen_dynamic_completions_to("my_app", Shell::Bash, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(compare(&*string, DYNAMIC_BASH));
+}
+
+#[test]
+fn dynamic_scripts_call_back() {
+    for shell in &[Shell::Zsh, Shell::Fish, Shell::PowerShell, Shell::Elvish, Shell::Nushell] {
+        let mut buf = vec![];
+        build_app_dynamic().gen_dynamic_completions_to("myapp", *shell, &mut buf);
+        let string = String::from_utf8(buf).unwrap();
+
+        assert!(string.contains("--complete"));
+    }
+}
+
+#[test]
+fn dynamic_subcommands() {
+    assert_eq!(
+        complete(build_app_dynamic(), 1, &["myapp", "t"]),
+        "test\ttests things"
+    );
+    assert_eq!(complete(build_app_dynamic(), 1, &["myapp", "ch"]), "checkout");
+}
+
+#[test]
+fn dynamic_switches() {
+    assert_eq!(
+        complete(build_app_dynamic(), 2, &["myapp", "test", "--c"]),
+        "--case\tthe case to test"
+    );
+    let out = complete(build_app_dynamic(), 1, &["myapp", "--"]);
+    assert!(out.contains("--color\twhen to use colors"));
+    assert!(out.contains("--help\tPrints help information"));
+    assert!(!out.contains("--secret"));
+}
+
+#[test]
+fn dynamic_possible_values() {
+    assert_eq!(
+        complete(build_app_dynamic(), 2, &["myapp", "--color", "a"]),
+        "always\nauto"
+    );
+    assert_eq!(
+        complete(build_app_dynamic(), 1, &["myapp", "--color=n"]),
+        "--color=never"
+    );
+    // bash splits on `=`
+    assert_eq!(
+        complete(build_app_dynamic(), 3, &["myapp", "--color", "=", "n"]),
+        "never"
+    );
+}
+
+#[test]
+fn dynamic_completer() {
+    assert_eq!(
+        complete(build_app_dynamic(), 2, &["myapp", "checkout", "ma"]),
+        "master\nmaint"
+    );
+    // the word being completed may be missing altogether
+    assert_eq!(
+        complete(build_app_dynamic(), 2, &["myapp", "checkout"]),
+        "master\nmaint\nnext"
+    );
+    // the value was already given
+    assert_eq!(complete(build_app_dynamic(), 3, &["myapp", "checkout", "next", ""]), "");
+}
+
+#[test]
+fn dynamic_completion_needs_setting() {
+    let res = build_app().get_matches_from_safe(vec!["myapp", "--complete", "1", "--", "myapp", ""]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::UnknownArgument);
+}
@@ -0,0 +1,200 @@
+extern crate clap;
+extern crate regex;
+
+include!("../clap-test.rs");
+
+use clap::{App, Arg, ArgGroup, Constraint, ErrorKind};
+
+static REQUIRES_ANY: &'static str = "error: The constraint '-d requires any of (-e, --fmt <fmt>)' on the following arguments wasn't satisfied:
+    -d
+    -e
+    --fmt <fmt>
+
+USAGE:
+    ctest -b -d
+
+For more information try --help";
+
+static EXACTLY_ONE: &'static str = "error: The constraint 'exactly one of (-a, -b) unless -c' on the following arguments wasn't satisfied:
+    -a
+    -b
+    -c
+
+USAGE:
+    ctest [FLAGS] [OPTIONS]
+
+For more information try --help";
+
+fn app() -> App<'static, 'static> {
+    App::new("ctest")
+        .args_from_usage(
+            "-a 'a'
+             -b 'b'
+             -c 'c'
+             -d 'd'
+             -e 'e'",
+        )
+        .arg(Arg::with_name("fmt").long("fmt").takes_value(true))
+        .constraint(Constraint::exactly_one(vec!["a", "b"]).unless("c"))
+        .constraint(Constraint::present("d").implies(Constraint::any(vec!["e", "fmt"])))
+}
+
+#[test]
+fn constraints_satisfied() {
+    for args in &[
+        vec!["ctest", "-a"],
+        vec!["ctest", "-b", "-d", "-e"],
+        vec!["ctest", "-c"],
+        vec!["ctest", "-c", "-a", "-b"],
+        vec!["ctest", "-a", "-d", "--fmt", "json"],
+    ] {
+        assert!(app().get_matches_from_safe(args).is_ok(), "{:?}", args);
+    }
+}
+
+#[test]
+fn constraint_exactly_one_none() {
+    let res = app().get_matches_from_safe(vec!["ctest"]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ConstraintViolation);
+    assert_eq!(err.info, Some(vec!["a".into(), "b".into(), "c".into()]));
+}
+
+#[test]
+fn constraint_exactly_one_both() {
+    let res = app().get_matches_from_safe(vec!["ctest", "-a", "-b"]);
+
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind, ErrorKind::ConstraintViolation);
+}
+
+#[test]
+fn constraint_exactly_one_output() {
+    assert!(test::compare_output(app(), "ctest", EXACTLY_ONE, true));
+}
+
+#[test]
+fn constraint_implies_output() {
+    assert!(test::compare_output(app(), "ctest -b -d", REQUIRES_ANY, true));
+}
+
+#[test]
+fn constraint_value() {
+    let app = App::new("ctest")
+        .arg(Arg::with_name("format").long("format").takes_value(true))
+        .arg(Arg::with_name("template").long("template").takes_value(true))
+        .constraint(Constraint::value("format", "custom").implies("template"));
+
+    assert!(app.clone()
+        .get_matches_from_safe(vec!["ctest", "--format", "json"])
+        .is_ok());
+    assert!(app.clone()
+        .get_matches_from_safe(vec!["ctest", "--format", "custom", "--template", "t"])
+        .is_ok());
+    let res = app.get_matches_from_safe(vec!["ctest", "--format", "custom"]);
+    assert!(res.is_err());
+    assert!(res.unwrap_err()
+        .message
+        .contains("'(--format <format> is 'custom') requires --template <template>'"));
+}
+
+#[test]
+fn constraint_not() {
+    let app = App::new("ctest")
+        .args_from_usage(
+            "--fast 'fast'
+             --safe 'safe'")
+        .constraint(Constraint::not(Constraint::present("fast").and("safe")));
+
+    assert!(app.clone().get_matches_from_safe(vec!["ctest", "--fast"]).is_ok());
+    let res = app.get_matches_from_safe(vec!["ctest", "--fast", "--safe"]);
+    assert!(res.is_err());
+    assert!(res.unwrap_err()
+        .message
+        .contains("'not all of (--fast, --safe)'"));
+}
+
+#[test]
+fn constraint_and_blames_first_violated() {
+    let res = App::new("ctest")
+        .args_from_usage(
+            "-x 'x'
+             -y 'y'
+             -z 'z'")
+        .constraint(
+            Constraint::present("x")
+                .implies("y")
+                .and(Constraint::present("y").implies("z")),
+        )
+        .get_matches_from_safe(vec!["ctest", "-x", "-y"]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert!(err.message.contains("'-y requires -z'"));
+    assert_eq!(err.info, Some(vec!["y".into(), "z".into()]));
+}
+
+#[test]
+fn constraint_message() {
+    let res = App::new("ctest")
+        .args_from_usage(
+            "--start [start] 'start'
+             --end [end] 'end'")
+        .constraint(
+            Constraint::present("end")
+                .implies("start")
+                .message("a range needs a start"),
+        )
+        .get_matches_from_safe(vec!["ctest", "--end", "9"]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert!(err.message.starts_with("error: a range needs a start\n\nUSAGE:"));
+}
+
+#[test]
+fn constraint_group() {
+    let app = App::new("ctest")
+        .args_from_usage(
+            "--json 'json'
+             --yaml 'yaml'
+             -o [out] 'out'")
+        .group(ArgGroup::with_name("format").args(&["json", "yaml"]))
+        .constraint(Constraint::present("o").implies("format"));
+
+    assert!(app.clone()
+        .get_matches_from_safe(vec!["ctest", "-o", "f", "--yaml"])
+        .is_ok());
+    let res = app.get_matches_from_safe(vec!["ctest", "-o", "f"]);
+    assert!(res.is_err());
+    assert!(res.unwrap_err()
+        .message
+        .contains("'-o <out> requires <--json|--yaml>'"));
+}
+
+#[test]
+#[should_panic]
+fn constraint_non_existing_arg() {
+    let _ = App::new("ctest")
+        .args_from_usage(
+            "--config [config] 'config'
+             --dry 'dry'")
+        .constraint(Constraint::present("dry").implies("cofig"))
+        .get_matches_from_safe(vec!["ctest"]);
+}
+
+#[test]
+fn constraint_after_required() {
+    let res = App::new("ctest")
+        .arg(Arg::with_name("input").required(true))
+        .args_from_usage(
+            "-a 'a'
+             -b 'b'")
+        .constraint(Constraint::any(vec!["a", "b"]))
+        .get_matches_from_safe(vec!["ctest"]);
+
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+}
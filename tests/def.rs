@@ -0,0 +1,164 @@
+extern crate clap;
+extern crate regex;
+
+include!("../clap-test.rs");
+
+use clap::{App, AppDef, AppSettings, ArgDef, ArgGroupDef, ErrorKind};
+
+// Everything is created from runtime strings so nothing outlives this function
+fn runtime_def(name: &str, opts: &[&str]) -> AppDef {
+    let mut app = AppDef::new(name.to_owned())
+        .version(format!("{}.{}", 1, 0))
+        .about(format!("the {} tool", name))
+        .setting(AppSettings::SubcommandsNegateReqs);
+    for opt in opts {
+        app = app.arg(
+            ArgDef::with_name(opt.to_string())
+                .long(opt.to_string())
+                .help(format!("sets the {}", opt))
+                .takes_value(true),
+        );
+    }
+    app.arg(ArgDef {
+        index: Some(1),
+        required: true,
+        help: Some("the file to process".into()),
+        ..ArgDef::with_name("input")
+    }).subcommand(AppDef::new("info").about(String::from("prints info")))
+}
+
+static DEF_HELP: &'static str = "prog 1.0
+the prog tool
+
+USAGE:
+    prog [OPTIONS] <input>
+    prog [OPTIONS] <SUBCOMMAND>
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+OPTIONS:
+        --color <color>    sets the color
+        --width <width>    sets the width
+
+ARGS:
+    <input>    the file to process
+
+SUBCOMMANDS:
+    help    Prints this message or the help of the given subcommand(s)
+    info    prints info";
+
+#[test]
+fn def_from_runtime_strings() {
+    let def = runtime_def("prog", &["width", "color"]);
+    let m = def.to_app()
+        .get_matches_from_safe(vec!["prog", "--width", "80", "file"])
+        .unwrap();
+    assert_eq!(m.value_of("width"), Some("80"));
+    assert_eq!(m.value_of("input"), Some("file"));
+    assert!(!m.is_present("color"));
+}
+
+#[test]
+fn def_subcommand() {
+    let def = runtime_def("prog", &[]);
+    let m = App::from(&def)
+        .get_matches_from_safe(vec!["prog", "info"])
+        .unwrap();
+    assert_eq!(m.subcommand_name(), Some("info"));
+}
+
+#[test]
+fn def_help() {
+    let def = runtime_def("prog", &["width", "color"]);
+    assert!(test::compare_output(def.to_app(), "prog --help", DEF_HELP, false));
+}
+
+#[test]
+fn def_arg_into_borrowed_app() {
+    let def = ArgDef {
+        short: Some("o".into()),
+        takes_value: true,
+        multiple: true,
+        use_delimiter: true,
+        possible_values: vec!["fast".into(), "slow".into()],
+        ..ArgDef::with_name("opt")
+    };
+    let m = App::new("prog")
+        .arg(&def)
+        .get_matches_from_safe(vec!["prog", "-o", "fast,slow"])
+        .unwrap();
+    assert_eq!(
+        m.values_of("opt").unwrap().collect::<Vec<_>>(),
+        ["fast", "slow"]
+    );
+
+    let res = App::new("prog")
+        .arg(def.to_arg())
+        .get_matches_from_safe(vec!["prog", "-o", "medium"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::InvalidValue);
+}
+
+#[test]
+fn def_default_and_conditional_values() {
+    let def = AppDef::new("prog")
+        .arg(ArgDef::with_name("flag").long("flag"))
+        .arg(ArgDef {
+            takes_value: true,
+            long: Some("level".into()),
+            default_value: Some("1".into()),
+            default_value_ifs: vec![("flag".into(), None, "9".into())],
+            ..ArgDef::with_name("level")
+        });
+    let m = def.to_app().get_matches_from(vec!["prog"]);
+    assert_eq!(m.value_of("level"), Some("1"));
+    let m = def.to_app().get_matches_from(vec!["prog", "--flag"]);
+    assert_eq!(m.value_of("level"), Some("9"));
+}
+
+#[test]
+fn def_requirements_and_conflicts() {
+    let def = AppDef::new("prog")
+        .arg(ArgDef {
+            conflicts_with: vec!["b".into()],
+            ..ArgDef::with_name("a").long("a")
+        })
+        .arg(ArgDef::with_name("b").long("b"))
+        .arg(ArgDef {
+            requires: vec!["a".into()],
+            ..ArgDef::with_name("c").long("c")
+        });
+    let res = def.to_app()
+        .get_matches_from_safe(vec!["prog", "--a", "--b"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::ArgumentConflict);
+    let res = def.to_app().get_matches_from_safe(vec!["prog", "--c"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+    assert!(def.to_app().get_matches_from_safe(vec!["prog", "--c", "--a"]).is_ok());
+}
+
+#[test]
+fn def_group() {
+    let def = AppDef::new("prog")
+        .arg(ArgDef::with_name("a").long("a"))
+        .arg(ArgDef::with_name("b").long("b"))
+        .group(ArgGroupDef::with_name("mode").arg("a").arg("b").required(true));
+    let res = def.to_app().get_matches_from_safe(vec!["prog"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+    let res = def.to_app()
+        .get_matches_from_safe(vec!["prog", "--a", "--b"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::ArgumentConflict);
+    let m = def.to_app().get_matches_from(vec!["prog", "--b"]);
+    assert!(m.is_present("mode"));
+}
+
+#[test]
+fn def_empty_values_default() {
+    let def = AppDef::new("prog").arg(ArgDef {
+        empty_values: false,
+        ..ArgDef::with_name("opt").long("opt").takes_value(true)
+    });
+    let res = def.to_app().get_matches_from_safe(vec!["prog", "--opt="]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::EmptyValue);
+    assert!(ArgDef::default().empty_values);
+}
@@ -0,0 +1,211 @@
+#![cfg(feature = "derive")]
+
+#[macro_use]
+extern crate clap;
+
+use clap::{ClapApp, ErrorKind, IntoApp};
+
+arg_enum! {
+    #[derive(Debug, PartialEq)]
+    enum Color {
+        Always,
+        Never,
+        Auto
+    }
+}
+
+/// Does things with files
+#[derive(Clap, Debug, PartialEq)]
+#[clap(name = "prog", version = "1.0")]
+struct Opts {
+    /// Sets the level of verbosity
+    #[clap(short, long, from_occurrences)]
+    verbose: u8,
+    /// Runs without touching anything
+    #[clap(short = "n", long)]
+    dry_run: bool,
+    /// The number of jobs to run in parallel
+    #[clap(short, long, default_value = "1")]
+    jobs: usize,
+    /// When to colorize
+    #[clap(long, arg_enum)]
+    color: Option<Color>,
+    /// Extra paths to search
+    #[clap(short = "I", long = "include")]
+    includes: Vec<String>,
+    /// The output format
+    #[clap(long, possible_values("json", "text"), default_value = "text")]
+    format: String,
+    /// The file to read
+    input: String,
+}
+
+#[test]
+fn derive_struct_defaults() {
+    let opts = Opts::parse_from(vec!["prog", "file.txt"]);
+    assert_eq!(
+        opts,
+        Opts {
+            verbose: 0,
+            dry_run: false,
+            jobs: 1,
+            color: None,
+            includes: vec![],
+            format: "text".into(),
+            input: "file.txt".into(),
+        }
+    );
+}
+
+#[test]
+fn derive_struct_all_set() {
+    let opts = Opts::parse_from(vec![
+        "prog", "-vvv", "-n", "--jobs", "4", "--color", "never", "-I", "a", "--include", "b",
+        "--format", "json", "file.txt",
+    ]);
+    assert_eq!(
+        opts,
+        Opts {
+            verbose: 3,
+            dry_run: true,
+            jobs: 4,
+            color: Some(Color::Never),
+            includes: vec!["a".into(), "b".into()],
+            format: "json".into(),
+            input: "file.txt".into(),
+        }
+    );
+}
+
+#[test]
+fn derive_invalid_value_is_a_parse_error() {
+    let res = Opts::try_parse_from(vec!["prog", "--jobs", "four", "file.txt"]);
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind, ErrorKind::ValueValidation);
+}
+
+#[test]
+fn derive_possible_values() {
+    let res = Opts::try_parse_from(vec!["prog", "--format", "xml", "file.txt"]);
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind, ErrorKind::InvalidValue);
+}
+
+#[test]
+fn derive_missing_required() {
+    let res = Opts::try_parse_from(vec!["prog"]);
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+}
+
+#[test]
+fn derive_help_from_doc_comments() {
+    let mut help = vec![];
+    Opts::into_app().write_help(&mut help).unwrap();
+    let help = String::from_utf8(help).unwrap();
+    assert!(help.contains("Does things with files"));
+    assert!(help.contains("-n, --dry-run"));
+    assert!(help.contains("Runs without touching anything"));
+    assert!(help.contains("[possible values: Always, Never, Auto]"));
+}
+
+#[derive(Clap, Debug, PartialEq)]
+struct AddOpts {
+    /// Overwrite existing entries
+    #[clap(short, long)]
+    force: bool,
+    names: Vec<String>,
+}
+
+#[derive(Clap, Debug, PartialEq)]
+enum Cmd {
+    /// Adds entries
+    Add(AddOpts),
+    /// Removes an entry
+    Remove {
+        #[clap(short, long)]
+        recursive: bool,
+        name: String,
+    },
+    /// Shows the status
+    #[clap(name = "st", alias = "status")]
+    Status,
+}
+
+#[derive(Clap, Debug, PartialEq)]
+#[clap(name = "tool")]
+struct Tool {
+    #[clap(short, long)]
+    quiet: bool,
+    #[clap(subcommand)]
+    cmd: Cmd,
+}
+
+#[derive(Clap, Debug, PartialEq)]
+#[clap(name = "tool")]
+struct MaybeTool {
+    #[clap(subcommand)]
+    cmd: Option<Cmd>,
+}
+
+#[test]
+fn derive_subcommand_tuple_variant() {
+    let tool = Tool::parse_from(vec!["tool", "-q", "add", "-f", "a", "b"]);
+    assert_eq!(
+        tool,
+        Tool {
+            quiet: true,
+            cmd: Cmd::Add(AddOpts {
+                force: true,
+                names: vec!["a".into(), "b".into()],
+            }),
+        }
+    );
+}
+
+#[test]
+fn derive_subcommand_struct_variant() {
+    let tool = Tool::parse_from(vec!["tool", "remove", "--recursive", "dir"]);
+    assert_eq!(
+        tool.cmd,
+        Cmd::Remove {
+            recursive: true,
+            name: "dir".into(),
+        }
+    );
+}
+
+#[test]
+fn derive_subcommand_unit_variant_with_alias() {
+    assert_eq!(Tool::parse_from(vec!["tool", "st"]).cmd, Cmd::Status);
+    assert_eq!(Tool::parse_from(vec!["tool", "status"]).cmd, Cmd::Status);
+}
+
+#[test]
+fn derive_subcommand_required() {
+    let res = Tool::try_parse_from(vec!["tool"]);
+    assert!(res.is_err());
+    assert_eq!(
+        res.unwrap_err().kind,
+        ErrorKind::MissingArgumentOrSubcommand
+    );
+}
+
+#[test]
+fn derive_subcommand_optional() {
+    assert_eq!(
+        MaybeTool::parse_from(vec!["tool"]),
+        MaybeTool { cmd: None }
+    );
+    assert_eq!(
+        MaybeTool::parse_from(vec!["tool", "st"]),
+        MaybeTool {
+            cmd: Some(Cmd::Status),
+        }
+    );
+}
+
+#[test]
+fn derive_enum_as_app() {
+    assert_eq!(Cmd::parse_from(vec!["cmd", "st"]), Cmd::Status);
+}
@@ -0,0 +1,218 @@
+extern crate clap;
+extern crate regex;
+
+use std::env;
+use std::fs::{self, File};
+use std::io::Read;
+
+use clap::{App, AppSettings, Arg, ArgGroup, DocFormat, SubCommand};
+use regex::Regex;
+
+static MARKDOWN: &'static str = r#"# myapp 1.0
+
+Tests \<docs\>
+
+```text
+myapp [FLAGS] [OPTIONS] <--fast|--slow> [file]... [SUBCOMMAND]
+```
+
+## Flags
+
+- `--fast`
+- `-h, --help`: Prints help information
+- `--slow`
+- `-V, --version`: Prints version information
+- `-v, --verbose`: more output
+
+## Options
+
+- `--color=<color>`: when to use colors
+  - Default value: `never`
+  - Possible values: `always`, `never`
+- `-c, --config <FILE>`: Sets the config file.
+
+  The file must exist.
+  - Aliases: `cfg`
+  - Environment variable: `MYAPP_CONFIG`
+
+## Arguments
+
+- `<file>...`: the input files
+
+## Groups
+
+- `speed`: Exactly one of these arguments must be used
+  - Arguments: `--fast`, `--slow`
+
+## Subcommands
+
+- `help`: Prints this message or the help of the given subcommand(s)
+- [`test`](#myapp-test): tests things
+  - Aliases: `t`
+
+## myapp test
+
+tests things
+
+```text
+myapp test [OPTIONS]
+```
+
+### Flags
+
+- `-h, --help`: Prints help information
+- `-V, --version`: Prints version information
+
+### Options
+
+- `--case <case>`: the case to test
+
+"#;
+
+static HTML: &'static str = r##"<h1 id="myapp">myapp 1.0</h1>
+<p>Tests &lt;docs&gt;</p>
+<pre><code>myapp [FLAGS] [OPTIONS] &lt;--fast|--slow&gt; [file]... [SUBCOMMAND]</code></pre>
+<h2>Flags</h2>
+<dl>
+<dt><code>--fast</code></dt>
+<dd></dd>
+<dt><code>-h, --help</code></dt>
+<dd><p>Prints help information</p></dd>
+<dt><code>--slow</code></dt>
+<dd></dd>
+<dt><code>-V, --version</code></dt>
+<dd><p>Prints version information</p></dd>
+<dt><code>-v, --verbose</code></dt>
+<dd><p>more output</p></dd>
+</dl>
+<h2>Options</h2>
+<dl>
+<dt><code>--color=&lt;color&gt;</code></dt>
+<dd><p>when to use colors</p><ul><li>Default value: <code>never</code></li><li>Possible values: <code>always</code>, <code>never</code></li></ul></dd>
+<dt><code>-c, --config &lt;FILE&gt;</code></dt>
+<dd><p>Sets the config file.</p><p>The file must exist.</p><ul><li>Aliases: <code>cfg</code></li><li>Environment variable: <code>MYAPP_CONFIG</code></li></ul></dd>
+</dl>
+<h2>Arguments</h2>
+<dl>
+<dt><code>&lt;file&gt;...</code></dt>
+<dd><p>the input files</p></dd>
+</dl>
+<h2>Groups</h2>
+<dl>
+<dt><code>speed</code></dt>
+<dd><p>Exactly one of these arguments must be used</p><ul><li>Arguments: <code>--fast</code>, <code>--slow</code></li></ul></dd>
+</dl>
+<h2>Subcommands</h2>
+<dl>
+<dt><code>help</code></dt>
+<dd><p>Prints this message or the help of the given subcommand(s)</p></dd>
+<dt><a href="#myapp-test"><code>test</code></a></dt>
+<dd><p>tests things</p><ul><li>Aliases: <code>t</code></li></ul></dd>
+</dl>
+<h2 id="myapp-test">myapp test</h2>
+<p>tests things</p>
+<pre><code>myapp test [OPTIONS]</code></pre>
+<h3>Flags</h3>
+<dl>
+<dt><code>-h, --help</code></dt>
+<dd><p>Prints help information</p></dd>
+<dt><code>-V, --version</code></dt>
+<dd><p>Prints version information</p></dd>
+</dl>
+<h3>Options</h3>
+<dl>
+<dt><code>--case &lt;case&gt;</code></dt>
+<dd><p>the case to test</p></dd>
+</dl>
+"##;
+
+fn compare(left: &str, right: &str) -> bool {
+    let b = left == right;
+    if !b {
+        let re = Regex::new(" ").unwrap();
+        println!("");
+        println!("--> left");
+        println!("{}", re.replace_all(left, "\u{2022}"));
+        println!("--> right");
+        println!("{}", re.replace_all(right, "\u{2022}"));
+        println!("--")
+    }
+    b
+}
+
+fn build_app() -> App<'static, 'static> {
+    App::new("myapp")
+        .version("1.0")
+        .about("Tests <docs>")
+        .arg(Arg::with_name("verbose")
+            .short("v")
+            .long("verbose")
+            .multiple(true)
+            .help("more output"))
+        .arg(Arg::with_name("config")
+            .short("c")
+            .long("config")
+            .value_name("FILE")
+            .env("MYAPP_CONFIG")
+            .visible_alias("cfg")
+            .help("sets the config")
+            .long_help("Sets the config file.\n\nThe file must exist."))
+        .arg(Arg::with_name("color")
+            .long("color")
+            .takes_value(true)
+            .require_equals(true)
+            .possible_values(&["always", "never"])
+            .default_value("never")
+            .display_order(1)
+            .help("when to use colors"))
+        .arg(Arg::with_name("secret").long("secret").hidden(true))
+        .arg(Arg::with_name("fast").long("fast").group("speed"))
+        .arg(Arg::with_name("slow").long("slow").group("speed"))
+        .group(ArgGroup::with_name("speed").required(true))
+        .arg(Arg::with_name("file").multiple(true).help("the input files"))
+        .subcommand(SubCommand::with_name("test")
+            .about("tests things")
+            .visible_alias("t")
+            .arg(Arg::with_name("case")
+                .long("case")
+                .takes_value(true)
+                .help("the case to test")))
+        .subcommand(SubCommand::with_name("hidden").setting(AppSettings::Hidden))
+}
+
+#[test]
+fn markdown() {
+    let mut buf = vec![];
+    build_app().gen_docs_to("myapp", DocFormat::Markdown, &mut buf);
+    assert!(compare(&*String::from_utf8(buf).unwrap(), MARKDOWN));
+}
+
+#[test]
+fn html() {
+    let mut buf = vec![];
+    build_app().gen_docs_to("myapp", DocFormat::Html, &mut buf);
+    assert!(compare(&*String::from_utf8(buf).unwrap(), HTML));
+}
+
+#[test]
+fn docs_file() {
+    let dir = env::temp_dir();
+    build_app().gen_docs("myapp-docs-test", DocFormat::Html, &*dir);
+    let mut string = String::new();
+    File::open(dir.join("myapp-docs-test.html"))
+        .unwrap()
+        .read_to_string(&mut string)
+        .unwrap();
+    fs::remove_file(dir.join("myapp-docs-test.html")).unwrap();
+
+    assert!(string.starts_with("<h1 id=\"myapp-docs-test\">myapp-docs-test 1.0</h1>\n"));
+    assert!(!string.contains("secret"));
+    assert!(!string.contains("hidden"));
+}
+
+#[test]
+fn doc_format_from_str() {
+    assert_eq!("markdown".parse::<DocFormat>(), Ok(DocFormat::Markdown));
+    assert_eq!("HTML".parse::<DocFormat>(), Ok(DocFormat::Html));
+    assert!("pdf".parse::<DocFormat>().is_err());
+}
@@ -261,3 +261,20 @@ fn validator_invalid() {
 
     assert!(r.is_err());
 }
+
+#[test]
+fn multiple_user_override() {
+    env::set_var("CLP_TEST_ENV_MULTI_OVERRIDE", "env1,env2");
+
+    let r = App::new("df")
+        .arg(
+            Arg::from_usage("--arg [FILE]... 'some arg'")
+                .env("CLP_TEST_ENV_MULTI_OVERRIDE")
+                .use_delimiter(true),
+        )
+        .get_matches_from_safe(vec!["", "--arg", "opt"]);
+
+    assert!(r.is_ok());
+    let m = r.unwrap();
+    assert_eq!(m.values_of("arg").unwrap().collect::<Vec<_>>(), vec!["opt"]);
+}
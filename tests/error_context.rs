@@ -0,0 +1,171 @@
+extern crate clap;
+extern crate regex;
+
+include!("../clap-test.rs");
+
+use clap::{App, AppSettings, Arg, Constraint, ErrorKind, ErrorStyle, SubCommand};
+
+static SHORT_ALL: &'static str = "error: Found argument '--frobnicate' which wasn't expected, or isn't valid in this context
+error: 'medium' isn't a valid value for '--mode <mode>'";
+
+fn app() -> App<'static, 'static> {
+    App::new("ctest")
+        .setting(AppSettings::ColorNever)
+        .arg(Arg::with_name("color").long("color").conflicts_with("plain"))
+        .arg(Arg::with_name("plain").long("plain"))
+        .arg(
+            Arg::with_name("mode")
+                .long("mode")
+                .takes_value(true)
+                .possible_values(&["fast", "slow"]),
+        )
+}
+
+#[test]
+fn context_unknown_argument() {
+    let err = app()
+        .get_matches_from_safe(vec!["ctest", "--colour"])
+        .unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::UnknownArgument);
+    assert_eq!(err.context().arg, Some("--colour".to_owned()));
+    assert_eq!(err.context().suggestion, Some("--color".to_owned()));
+    assert_eq!(
+        err.context().usage,
+        Some("USAGE:\n    ctest --color".to_owned())
+    );
+    assert!(err.context().description.ends_with("\n\tDid you mean --color?"));
+}
+
+#[test]
+fn context_invalid_value() {
+    let err = app()
+        .get_matches_from_safe(vec!["ctest", "--mode", "slwo"])
+        .unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::InvalidValue);
+    assert_eq!(err.context().arg, Some("--mode <mode>".to_owned()));
+    assert_eq!(err.context().value, Some("slwo".to_owned()));
+    assert_eq!(err.context().valid_values, ["fast", "slow"]);
+    assert_eq!(err.context().suggestion, Some("slow".to_owned()));
+    assert_eq!(err.context().conflicting_arg, None);
+}
+
+#[test]
+fn context_conflict() {
+    let err = app()
+        .get_matches_from_safe(vec!["ctest", "--color", "--plain"])
+        .unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::ArgumentConflict);
+    assert!(err.context().arg.is_some());
+    assert!(err.context().conflicting_arg.is_some());
+    assert_ne!(err.context().arg, err.context().conflicting_arg);
+}
+
+#[test]
+fn context_missing_required() {
+    let err = app()
+        .arg(Arg::with_name("input").required(true))
+        .arg(Arg::with_name("out").long("out").takes_value(true).required(true))
+        .get_matches_from_safe(vec!["ctest"])
+        .unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+    assert_eq!(err.context().arg, Some("<input>".to_owned()));
+    assert_eq!(err.context().args, ["<input>", "--out <out>"]);
+}
+
+#[test]
+fn context_constraint_violation() {
+    let err = app()
+        .constraint(Constraint::exactly_one(vec!["color", "mode"]))
+        .get_matches_from_safe(vec!["ctest", "--color", "--mode", "fast"])
+        .unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::ConstraintViolation);
+    assert_eq!(err.context().arg, Some("--color".to_owned()));
+    assert_eq!(err.context().args, ["--color", "--mode <mode>"]);
+}
+
+#[test]
+fn context_missing_subcommand() {
+    let err = app()
+        .setting(AppSettings::SubcommandRequired)
+        .subcommand(SubCommand::with_name("build"))
+        .subcommand(SubCommand::with_name("test"))
+        .subcommand(SubCommand::with_name("secret").setting(AppSettings::Hidden))
+        .get_matches_from_safe(vec!["ctest"])
+        .unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::MissingSubcommand);
+    assert_eq!(err.context().arg, Some("ctest".to_owned()));
+    assert_eq!(err.context().valid_values, ["build", "test", "help"]);
+}
+
+#[test]
+fn context_app_validation() {
+    let err = app()
+        .validator(|m| Err(m.error(ErrorKind::ValueValidation, "nope")))
+        .get_matches_from_safe(vec!["ctest", "--plain", "--mode", "fast"])
+        .unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::ValueValidation);
+    assert_eq!(err.context().args, ["mode", "plain"]);
+}
+
+#[test]
+fn render_full_is_message() {
+    for args in &[
+        vec!["ctest", "--frobnicate"],
+        vec!["ctest", "--mode", "medium"],
+        vec!["ctest", "--color", "--plain"],
+        vec!["ctest", "--mode"],
+    ] {
+        let err = app().get_matches_from_safe(args).unwrap_err();
+        assert_eq!(err.render(ErrorStyle::Full), err.message, "{:?}", args);
+    }
+}
+
+#[test]
+fn render_without_usage() {
+    let err = app()
+        .get_matches_from_safe(vec!["ctest", "--mode"])
+        .unwrap_err();
+
+    assert_eq!(
+        err.render(ErrorStyle::WithoutUsage),
+        "error: The argument '--mode <mode>' requires a value but none was supplied"
+    );
+}
+
+#[test]
+fn render_multiple_errors() {
+    let err = app()
+        .setting(AppSettings::ReportAllErrors)
+        .get_matches_from_safe(vec!["ctest", "--mode", "medium", "--frobnicate"])
+        .unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::MultipleErrors);
+    assert_eq!(err.render(ErrorStyle::Full), err.message);
+    assert_eq!(err.render(ErrorStyle::Short), SHORT_ALL);
+}
+
+#[test]
+fn render_help() {
+    let err = app()
+        .get_matches_from_safe(vec!["ctest", "--help"])
+        .unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert_eq!(err.render(ErrorStyle::Short), err.message);
+}
+
+#[test]
+fn context_custom_error() {
+    let err = clap::Error::with_description("nope", ErrorKind::Io);
+
+    assert_eq!(err.context().description, "nope");
+    assert_eq!(err.context().usage, None);
+    assert_eq!(err.render(ErrorStyle::Full), "error: nope");
+}
@@ -1,6 +1,6 @@
 extern crate clap;
 
-use clap::{App, Arg, ArgSettings};
+use clap::{App, AppSettings, Arg, ArgSettings, ErrorKind};
 
 #[test]
 fn flag_using_short() {
@@ -145,3 +145,71 @@ fn short_flag_name_missing() {
     assert!(a.v.num_vals.is_none());
 
 }
+
+fn negatable_app() -> App<'static, 'static> {
+    App::new("flag")
+        .arg(Arg::from_usage("-c, --color 'use colors'").negatable(true))
+        .arg(Arg::from_usage("-v, --verbose... 'more output'").negatable(true))
+        .arg(Arg::from_usage("--plain 'no negation'"))
+}
+
+#[test]
+fn negatable_flag() {
+    let m = negatable_app().get_matches_from(vec!["", "--no-color"]);
+    assert!(!m.is_present("color"));
+    assert_eq!(m.flag_state("color"), Some(false));
+    assert_eq!(m.flag_state("verbose"), None);
+}
+
+#[test]
+fn negatable_flag_last_wins() {
+    let m = negatable_app().get_matches_from(vec!["", "--no-color", "-c"]);
+    assert!(m.is_present("color"));
+    assert_eq!(m.flag_state("color"), Some(true));
+
+    let m = negatable_app().get_matches_from(vec!["", "-c", "--no-color"]);
+    assert!(!m.is_present("color"));
+    assert_eq!(m.flag_state("color"), Some(false));
+}
+
+#[test]
+fn negatable_flag_resets_occurrences() {
+    let m = negatable_app().get_matches_from(vec!["", "-vvv", "--no-verbose", "-v"]);
+    assert_eq!(m.occurrences_of("verbose"), 1);
+    assert_eq!(m.flag_state("verbose"), Some(true));
+}
+
+#[test]
+fn negatable_flag_needs_setting() {
+    let res = negatable_app().get_matches_from_safe(vec!["", "--no-plain"]);
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind, ErrorKind::UnknownArgument);
+}
+
+#[test]
+fn negatable_flag_real_arg_wins() {
+    let m = negatable_app()
+        .arg(Arg::from_usage("--no-color 'a flag of its own'"))
+        .get_matches_from(vec!["", "-c", "--no-color"]);
+    assert!(m.is_present("color"));
+    assert!(m.is_present("no-color"));
+}
+
+#[test]
+fn negatable_flag_abbreviated() {
+    let app = negatable_app()
+        .setting(AppSettings::InferLongArgs)
+        .arg(Arg::from_usage("--columns [n] 'not negatable'"));
+    let m = app.clone().get_matches_from(vec!["", "--colo", "--no-col"]);
+    assert_eq!(m.flag_state("color"), Some(false));
+
+    let m = app.clone().get_matches_from(vec!["", "--no-verb"]);
+    assert_eq!(m.flag_state("verbose"), Some(false));
+
+    // Only one of them is negatable
+    let m = app.clone().get_matches_from(vec!["", "-c", "--no-co"]);
+    assert_eq!(m.flag_state("color"), Some(false));
+
+    let res = app.get_matches_from_safe(vec!["", "--no-p"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::UnknownArgument);
+}
@@ -34,4 +34,27 @@ mod tests {
         app.get_matches_from_safe_borrow(vec!["myprog"]);
         app.get_matches_from_safe_borrow(vec!["myprog"]);
     }
+
+    #[test]
+    fn global_negatable_flag() {
+        let app = App::new("prog")
+            .arg(Arg::with_name("color").long("color").global(true).negatable(true))
+            .subcommand(SubCommand::with_name("sub")
+                .subcommand(SubCommand::with_name("inner")));
+
+        let m = app.clone()
+            .get_matches_from(vec!["prog", "--color", "sub", "--no-color", "inner"]);
+        assert_eq!(m.flag_state("color"), Some(false));
+        let sub_m = m.subcommand_matches("sub").unwrap();
+        assert_eq!(sub_m.flag_state("color"), Some(false));
+        let inner_m = sub_m.subcommand_matches("inner").unwrap();
+        assert_eq!(inner_m.flag_state("color"), Some(false));
+
+        let m = app.get_matches_from(vec!["prog", "--no-color", "sub", "inner", "--color"]);
+        assert_eq!(m.flag_state("color"), Some(true));
+        let sub_m = m.subcommand_matches("sub").unwrap();
+        assert_eq!(sub_m.flag_state("color"), Some(true));
+        let inner_m = sub_m.subcommand_matches("inner").unwrap();
+        assert_eq!(inner_m.flag_state("color"), Some(true));
+    }
 }
@@ -27,6 +27,33 @@ USAGE:
 
 For more information try --help";
 
+static GROUP_MAX_USAGE: &'static str = "error: The group '<-a|-b|-c|-d>{2,3}' allows at most 3 arguments, but 4 were provided
+
+USAGE:
+    clap-test <-a|-b|-c|-d>{2,3}
+
+For more information try --help";
+
+static GROUP_MIN_USAGE: &'static str = "error: The group '<-a|-b|-c|-d>{2,3}' requires at least 2 arguments, but only 1 was provided
+
+USAGE:
+    clap-test <-a|-b|-c|-d>{2,3}
+
+For more information try --help";
+
+fn bounded_group() -> App<'static, 'static> {
+    App::new("clap-test")
+        .args_from_usage("-a 'a'
+                          -b 'b'
+                          -c 'c'
+                          -d 'd'")
+        .group(ArgGroup::with_name("inputs")
+            .args(&["a", "b", "c", "d"])
+            .min_args(2)
+            .max_args(3)
+            .required(true))
+}
+
 #[test]
 fn required_group_missing_arg() {
     let result = App::new("group")
@@ -41,6 +68,24 @@ fn required_group_missing_arg() {
     assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
 }
 
+#[test]
+fn required_group_negated_arg() {
+    let app = App::new("group")
+        .args_from_usage("-f, --flag 'some flag'")
+        .arg(Arg::with_name("color").long("color").negatable(true))
+        .group(ArgGroup::with_name("g")
+            .args(&["color"])
+            .required(true));
+
+    let result = app.clone().get_matches_from_safe(vec!["", "--color", "--no-color"]);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+
+    let m = app.get_matches_from(vec!["", "--no-color", "--color"]);
+    assert!(m.is_present("color"));
+    assert!(m.is_present("g"));
+}
+
 #[test]
 #[should_panic]
 fn non_existing_arg() {
@@ -191,3 +236,89 @@ fn group_multiple_args_error() {
     let err = result.unwrap_err();
     assert_eq!(err.kind, ErrorKind::ArgumentConflict);
 }
+
+#[test]
+fn group_min_max_args() {
+    for args in &[vec!["clap-test", "-a", "-b"], vec!["clap-test", "-a", "-c", "-d"]] {
+        assert!(bounded_group().get_matches_from_safe(args).is_ok(), "{:?}", args);
+    }
+}
+
+#[test]
+fn group_max_args_error() {
+    let result = bounded_group().get_matches_from_safe(vec!["clap-test", "-a", "-b", "-c", "-d"]);
+    assert!(result.is_err());
+    let err = result.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ArgumentConflict);
+    assert_eq!(err.info, Some(vec!["inputs".to_owned()]));
+}
+
+#[test]
+fn group_min_args_error() {
+    let result = bounded_group().get_matches_from_safe(vec!["clap-test", "-b"]);
+    assert!(result.is_err());
+    let err = result.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+    assert_eq!(err.info, Some(vec!["inputs".to_owned()]));
+}
+
+#[test]
+fn group_min_args_required() {
+    let result = bounded_group().get_matches_from_safe(vec!["clap-test"]);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+}
+
+#[test]
+fn group_min_args_optional() {
+    let result = App::new("group")
+        .args_from_usage("-f, --flag 'some flag'
+                          -c, --color 'some other flag'")
+        .group(ArgGroup::with_name("both")
+            .args(&["flag", "color"])
+            .min_args(2))
+        .get_matches_from_safe(vec!["group"]);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn group_min_args_ignores_defaults() {
+    let app = App::new("group")
+        .arg(Arg::with_name("a").long("a").takes_value(true).default_value("x"))
+        .arg(Arg::with_name("b").long("b"))
+        .arg(Arg::with_name("c").long("c"))
+        .group(ArgGroup::with_name("g")
+            .args(&["a", "b", "c"])
+            .min_args(2));
+    assert!(app.clone().get_matches_from_safe(vec!["group"]).is_ok());
+    let result = app.clone().get_matches_from_safe(vec!["group", "--b"]);
+    assert_eq!(result.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+    assert!(app.get_matches_from_safe(vec!["group", "--b", "--a", "y"]).is_ok());
+}
+
+#[test]
+fn group_max_args_ignores_defaults() {
+    let result = App::new("group")
+        .arg(Arg::with_name("out").long("out").takes_value(true).default_value("-"))
+        .arg(Arg::with_name("json").long("json"))
+        .group(ArgGroup::with_name("outputs")
+            .args(&["out", "json"])
+            .max_args(1))
+        .get_matches_from_safe(vec!["group", "--json"]);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn group_max_args_usage_string() {
+    assert!(test::compare_output(
+        bounded_group(),
+        "clap-test -a -b -c -d",
+        GROUP_MAX_USAGE,
+        true
+    ));
+}
+
+#[test]
+fn group_min_args_usage_string() {
+    assert!(test::compare_output(bounded_group(), "clap-test -b", GROUP_MIN_USAGE, true));
+}
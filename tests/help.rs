@@ -517,6 +517,28 @@ OPTIONS:
     -c, --cafe <FILE>    A coffeehouse, coffee shop, or café. [env: ENVVAR=MYVAL]
     -p, --pos <VAL>      Some vals [possible values: fast, slow]";
 
+static NEGATABLE_FLAGS: &'static str = "ctest 0.1
+
+USAGE:
+    ctest [FLAGS]
+
+FLAGS:
+    -c, --[no-]color    Use colors
+    -h, --help          Prints help information
+    -V, --version       Prints version information
+        --[no-]x        Short long";
+
+static MAX_OCCURRENCES: &'static str = "ctest 0.1
+
+USAGE:
+    ctest [FLAGS]
+
+FLAGS:
+    -h, --help       Prints help information
+    -q, --quiet      Less output [max: 2]
+    -V, --version    Prints version information
+    -v, --verbose    More output [max: 3]";
+
 fn setup() -> App<'static, 'static> {
     App::new("test")
         .author("Kevin K.")
@@ -1157,4 +1179,31 @@ fn show_env_vals() {
             .help("A coffeehouse, coffee shop, or café.")
             .takes_value(true));
     assert!(test::compare_output(app, "ctest --help", SHOW_ENV_VALS, false));
-}
\ No newline at end of file
+}
+
+#[test]
+fn negatable_flags() {
+    let app = App::new("ctest")
+        .version("0.1")
+        .arg(Arg::with_name("color")
+            .short("c")
+            .long("color")
+            .negatable(true)
+            .help("Use colors"))
+        .arg(Arg::with_name("x")
+            .long("x")
+            .negatable(true)
+            .help("Short long"));
+    assert!(test::compare_output(app, "ctest --help", NEGATABLE_FLAGS, false));
+}
+
+#[test]
+fn max_occurrences() {
+    let app = App::new("ctest")
+        .version("0.1")
+        .arg(Arg::from_usage("-v, --verbose 'More output'").max_occurrences(3))
+        .arg(Arg::from_usage("-q, --quiet 'Less output'")
+            .max_occurrences(2)
+            .decrements("verbose"));
+    assert!(test::compare_output(app, "ctest --help", MAX_OCCURRENCES, false));
+}
@@ -0,0 +1,202 @@
+extern crate clap;
+extern crate regex;
+
+include!("../clap-test.rs");
+
+use std::collections::BTreeMap;
+
+use clap::{App, Arg, ErrorKind};
+
+#[cfg(feature = "suggestions")]
+static PK_ERROR: &'static str = "error: 'widht' isn't a valid key for '--set <set>...'
+\t[possible keys: height, width]
+
+\tDid you mean 'width'?
+
+USAGE:
+    ctest --set <set>...
+
+For more information try --help";
+
+#[cfg(not(feature = "suggestions"))]
+static PK_ERROR: &'static str = "error: 'widht' isn't a valid key for '--set <set>...'
+\t[possible keys: height, width]
+
+
+USAGE:
+    ctest --set <set>...
+
+For more information try --help";
+
+static PK_HELP: &'static str = "ctest 0.1
+
+USAGE:
+    ctest [OPTIONS]
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+OPTIONS:
+        --set <set>...    sets a property [possible keys: width, height]";
+
+fn defines() -> App<'static, 'static> {
+    App::new("ctest").arg(
+        Arg::with_name("define")
+            .short("D")
+            .multiple(true)
+            .number_of_values(1)
+            .key_value_delimiter("="),
+    )
+}
+
+fn props() -> App<'static, 'static> {
+    App::new("ctest").version("0.1").arg(
+        Arg::with_name("set")
+            .long("set")
+            .multiple(true)
+            .key_value_delimiter("=")
+            .possible_keys(&["width", "height"])
+            .help("sets a property"),
+    )
+}
+
+#[test]
+fn key_values_in_order() {
+    let m = defines()
+        .get_matches_from_safe(vec!["ctest", "-D", "b=2", "-Da=1", "-D", "b=3"])
+        .unwrap();
+
+    let kvs: Vec<_> = m.key_values_of("define").unwrap().collect();
+    assert_eq!(kvs, [("b", "2"), ("a", "1"), ("b", "3")]);
+}
+
+#[test]
+fn key_values_last_wins() {
+    let m = defines()
+        .get_matches_from_safe(vec!["ctest", "-D", "b=2", "-D", "a=1", "-D", "b=3"])
+        .unwrap();
+
+    let map: BTreeMap<_, _> = m.key_values_of("define").unwrap().collect();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["a"], "1");
+    assert_eq!(map["b"], "3");
+}
+
+#[test]
+fn key_values_split_on_first_delimiter() {
+    let m = defines()
+        .get_matches_from_safe(vec!["ctest", "-D", "opts=-O2=fast", "-D", "empty="])
+        .unwrap();
+
+    let kvs: Vec<_> = m.key_values_of("define").unwrap().collect();
+    assert_eq!(kvs, [("opts", "-O2=fast"), ("empty", "")]);
+}
+
+#[test]
+fn key_values_custom_delimiter() {
+    let m = App::new("ctest")
+        .arg(
+            Arg::with_name("header")
+                .long("header")
+                .key_value_delimiter(":"),
+        )
+        .get_matches_from_safe(vec!["ctest", "--header", "Accept:text/plain"])
+        .unwrap();
+
+    assert_eq!(
+        m.key_values_of("header").unwrap().collect::<Vec<_>>(),
+        [("Accept", "text/plain")]
+    );
+    assert_eq!(m.value_of("header"), Some("Accept:text/plain"));
+}
+
+#[test]
+fn key_values_not_present() {
+    let m = defines().get_matches_from_safe(vec!["ctest"]).unwrap();
+
+    assert!(m.key_values_of("define").is_none());
+}
+
+#[test]
+fn key_values_missing_delimiter() {
+    let res = defines().get_matches_from_safe(vec!["ctest", "-D", "a=1", "-D", "b"]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueValidation);
+    assert_eq!(err.info, Some(vec!["define".into(), "b".into()]));
+}
+
+#[test]
+fn key_values_empty_key() {
+    let res = defines().get_matches_from_safe(vec!["ctest", "-D", "=1"]);
+
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind, ErrorKind::ValueValidation);
+}
+
+#[test]
+fn possible_keys() {
+    let m = props()
+        .get_matches_from_safe(vec!["ctest", "--set", "width=80", "height=24"])
+        .unwrap();
+
+    let kvs: Vec<_> = m.key_values_of("set").unwrap().collect();
+    assert_eq!(kvs, [("width", "80"), ("height", "24")]);
+}
+
+#[test]
+fn possible_keys_fail() {
+    let res = props().get_matches_from_safe(vec!["ctest", "--set", "depth=8"]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidValue);
+    assert_eq!(err.info, Some(vec!["set".into(), "depth".into()]));
+}
+
+#[test]
+fn possible_keys_output() {
+    assert!(test::compare_output(
+        props(),
+        "ctest --set widht=80",
+        PK_ERROR,
+        true
+    ));
+}
+
+#[test]
+fn possible_keys_help() {
+    assert!(test::compare_output(props(), "ctest --help", PK_HELP, false));
+}
+
+#[test]
+#[should_panic]
+fn key_values_of_plain_option() {
+    let m = App::new("ctest")
+        .arg(Arg::with_name("opt").short("o").takes_value(true))
+        .get_matches_from(vec!["ctest", "-o", "a=1"]);
+
+    m.key_values_of("opt");
+}
+
+#[test]
+#[should_panic]
+fn key_values_of_flag() {
+    let m = App::new("ctest")
+        .arg(Arg::with_name("flag").short("f"))
+        .get_matches_from(vec!["ctest", "-f"]);
+
+    m.key_values_of("flag");
+}
+
+#[test]
+#[should_panic]
+fn key_values_of_plain_option_without_values() {
+    let m = App::new("ctest")
+        .arg(Arg::with_name("opt").short("o").takes_value(true).min_values(0))
+        .get_matches_from(vec!["ctest", "-o"]);
+
+    m.key_values_of("opt");
+}
@@ -0,0 +1,87 @@
+extern crate clap;
+
+use std::ffi::OsString;
+
+use clap::{App, Arg, ErrorKind, SubCommand};
+
+fn os(args: &[&str]) -> Vec<OsString> { args.iter().map(OsString::from).collect() }
+
+fn app() -> App<'static, 'static> {
+    App::new("wrapper")
+        .arg(Arg::from_usage("-v, --verbose 'log what is being run'"))
+        .arg(Arg::from_usage("-c, --config [file] 'the config file'"))
+}
+
+#[test]
+fn known_args_in_any_position() {
+    let (m, rest) = app()
+        .get_known_matches_from_safe(vec![
+            "wrapper", "--jobs", "4", "-v", "--release", "-c", "my.toml", "--target=x86",
+        ])
+        .unwrap();
+
+    assert!(m.is_present("verbose"));
+    assert_eq!(m.value_of("config"), Some("my.toml"));
+    assert_eq!(rest, os(&["--jobs", "4", "--release", "--target=x86"]));
+}
+
+#[test]
+fn known_args_value_after_equals() {
+    let (_, rest) = app()
+        .get_known_matches_from_safe(vec!["wrapper", "--color=always", "file"])
+        .unwrap();
+
+    assert_eq!(rest, os(&["--color=always", "file"]));
+}
+
+#[test]
+fn known_args_short_groups() {
+    let (m, rest) = app()
+        .get_known_matches_from_safe(vec!["wrapper", "-vxy", "-j", "4", "-Ofast"])
+        .unwrap();
+
+    assert!(m.is_present("verbose"));
+    assert_eq!(rest, os(&["-xy", "-j", "4", "-Ofast"]));
+}
+
+#[test]
+fn known_args_positional_wins() {
+    let (m, rest) = app()
+        .arg(Arg::from_usage("[input] 'the input'"))
+        .get_known_matches_from_safe(vec!["wrapper", "--fast", "in.txt", "out.txt"])
+        .unwrap();
+
+    assert_eq!(m.value_of("input"), Some("in.txt"));
+    assert_eq!(rest, os(&["--fast", "out.txt"]));
+}
+
+#[test]
+fn known_args_subcommand() {
+    let (m, rest) = app()
+        .subcommand(SubCommand::with_name("run").arg(Arg::from_usage("-q, --quiet 'be quiet'")))
+        .get_known_matches_from_safe(vec!["wrapper", "--top", "run", "-q", "--bottom", "x"])
+        .unwrap();
+
+    assert!(m.subcommand_matches("run").unwrap().is_present("quiet"));
+    assert_eq!(rest, os(&["--top", "--bottom", "x"]));
+}
+
+#[test]
+fn known_args_trailing() {
+    let (m, rest) = app()
+        .get_known_matches_from_safe(vec!["wrapper", "-v", "--", "-v", "file"])
+        .unwrap();
+
+    assert_eq!(m.occurrences_of("verbose"), 1);
+    assert_eq!(rest, os(&["--", "-v", "file"]));
+}
+
+#[test]
+fn known_args_other_errors() {
+    let res = app()
+        .arg(Arg::from_usage("<input> 'the input'"))
+        .get_known_matches_from_safe(vec!["wrapper", "--fast"]);
+
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind, ErrorKind::MissingRequiredArgument);
+}
@@ -0,0 +1,149 @@
+extern crate clap;
+extern crate regex;
+
+use std::env;
+use std::fs::{self, File};
+use std::io::Read;
+
+use clap::{App, AppSettings, Arg, SubCommand};
+use regex::Regex;
+
+static MAN: &'static str = r#".TH "MYAPP" 1 "" "myapp 1.0"
+.SH NAME
+myapp \- Tests man pages
+.SH SYNOPSIS
+\fBmyapp [FLAGS] [OPTIONS] [file]... [SUBCOMMAND]\fR
+.SH DESCRIPTION
+Tests man pages.
+.sp
+The description may use \- and \e freely.
+.SH OPTIONS
+.TP
+\fB\-\-color\fR=\fI<color>\fR
+when to use colors
+.sp
+[default: never] [possible values: always, never]
+.TP
+\fB\-c\fR, \fB\-\-config\fR \fI<FILE>\fR
+Sets the config file.
+\&.ini files are supported too.
+.TP
+\fB\-h\fR, \fB\-\-help\fR
+Prints help information
+.TP
+\fB\-V\fR, \fB\-\-version\fR
+Prints version information
+.TP
+\fB\-v\fR, \fB\-\-verbose\fR
+more output
+.SH ARGUMENTS
+.TP
+\fI<file>...\fR
+the input files
+.SH SUBCOMMANDS
+.TP
+\fBhelp\fR
+Prints this message or the help of the given subcommand(s)
+.TP
+\fBtest\fR
+tests things
+.SH "MYAPP TEST"
+.SS SYNOPSIS
+\fBmyapp test [OPTIONS]\fR
+.SS DESCRIPTION
+tests things
+.SS OPTIONS
+.TP
+\fB\-\-case\fR \fI<case>\fR
+the case to test
+.TP
+\fB\-h\fR, \fB\-\-help\fR
+Prints help information
+.TP
+\fB\-V\fR, \fB\-\-version\fR
+Prints version information
+.SH ENVIRONMENT
+.TP
+\fBMYAPP_CONFIG\fR
+sets the config
+.SH VERSION
+1.0
+.SH AUTHORS
+Kevin K. <kbknapp@gmail.com>
+"#;
+
+fn compare(left: &str, right: &str) -> bool {
+    let b = left == right;
+    if !b {
+        let re = Regex::new(" ").unwrap();
+        println!("");
+        println!("--> left");
+        println!("{}", re.replace_all(left, "\u{2022}"));
+        println!("--> right");
+        println!("{}", re.replace_all(right, "\u{2022}"));
+        println!("--")
+    }
+    b
+}
+
+fn build_app() -> App<'static, 'static> {
+    App::new("myapp")
+        .version("1.0")
+        .author("Kevin K. <kbknapp@gmail.com>")
+        .about("Tests man pages")
+        .long_about("Tests man pages.\n\nThe description may use - and \\ freely.")
+        .arg(Arg::with_name("verbose")
+            .short("v")
+            .long("verbose")
+            .multiple(true)
+            .help("more output"))
+        .arg(Arg::with_name("config")
+            .short("c")
+            .long("config")
+            .value_name("FILE")
+            .env("MYAPP_CONFIG")
+            .help("sets the config")
+            .long_help("Sets the config file.\n.ini files are supported too."))
+        .arg(Arg::with_name("color")
+            .long("color")
+            .takes_value(true)
+            .require_equals(true)
+            .possible_values(&["always", "never"])
+            .default_value("never")
+            .display_order(1)
+            .help("when to use colors"))
+        .arg(Arg::with_name("secret").long("secret").hidden(true))
+        .arg(Arg::with_name("file").multiple(true).help("the input files"))
+        .subcommand(SubCommand::with_name("test")
+            .about("tests things")
+            .arg(Arg::with_name("case")
+                .long("case")
+                .takes_value(true)
+                .help("the case to test")))
+        .subcommand(SubCommand::with_name("hidden").setting(AppSettings::Hidden))
+}
+
+#[test]
+fn man_page() {
+    let mut buf = vec![];
+    build_app().gen_man_to("myapp", &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(compare(&*string, MAN));
+}
+
+#[test]
+fn man_page_file() {
+    let dir = env::temp_dir();
+    build_app().gen_man("myapp-man-test", &*dir);
+    let mut string = String::new();
+    File::open(dir.join("myapp-man-test.1"))
+        .unwrap()
+        .read_to_string(&mut string)
+        .unwrap();
+    fs::remove_file(dir.join("myapp-man-test.1")).unwrap();
+
+    assert!(string.starts_with(".TH \"MYAPP\\-MAN\\-TEST\" 1 \"\" \"myapp\\-man\\-test 1.0\"\n"));
+    assert!(!string.contains("secret"));
+    assert!(!string.contains("hidden"));
+}
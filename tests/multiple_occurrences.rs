@@ -1,6 +1,6 @@
 extern crate clap;
 
-use clap::{App, Arg};
+use clap::{App, Arg, SubCommand};
 
 #[test]
 fn multiple_occurrences_of_flags_long() {
@@ -73,3 +73,67 @@ fn multiple_occurrences_of_flags_large_quantity() {
     assert!(m.is_present("multflag"));
     assert_eq!(m.occurrences_of("multflag"), 1024);
 }
+
+#[test]
+fn max_occurrences_of_flags() {
+    let m = App::new("mo_flags_max")
+                .arg(Arg::from_usage("-v --verbose 'capped flag'")
+                    .max_occurrences(2))
+                .get_matches_from(vec![
+                    "",
+                    "-vvv",
+                    "--verbose"
+                    ]);
+    assert!(m.is_present("verbose"));
+    assert_eq!(m.occurrences_of("verbose"), 2);
+}
+
+fn verbosity_app() -> App<'static, 'static> {
+    App::new("verbosity")
+        .arg(Arg::from_usage("-v --verbose 'more output'")
+            .max_occurrences(3)
+            .global(true))
+        .arg(Arg::from_usage("-q --quiet 'less output'")
+            .max_occurrences(2)
+            .decrements("verbose")
+            .global(true))
+        .subcommand(SubCommand::with_name("sub"))
+}
+
+#[test]
+fn verbosity_level() {
+    let m = verbosity_app().get_matches_from(vec!["", "-vvvvv", "-q"]);
+    assert_eq!(m.verbosity("verbose"), 2);
+    assert_eq!(m.occurrences_of("verbose"), 3);
+    assert_eq!(m.occurrences_of("quiet"), 1);
+
+    let m = verbosity_app().get_matches_from(vec!["", "-qqqq", "-v"]);
+    assert_eq!(m.verbosity("verbose"), -1);
+
+    let m = verbosity_app().get_matches_from(vec![""]);
+    assert_eq!(m.verbosity("verbose"), 0);
+}
+
+#[test]
+fn verbosity_of_global_flags() {
+    let m = verbosity_app().get_matches_from(vec!["", "-vv", "sub", "-q"]);
+    assert_eq!(m.verbosity("verbose"), 1);
+    assert_eq!(m.subcommand_matches("sub").unwrap().verbosity("verbose"), 1);
+}
+
+#[test]
+fn verbosity_without_decrements() {
+    let m = App::new("verbosity")
+                .arg(Arg::from_usage("-v --verbose... 'more output'"))
+                .get_matches_from(vec!["", "-vvvv"]);
+    assert_eq!(m.verbosity("verbose"), 4);
+    assert_eq!(m.verbosity("quiet"), 0);
+}
+
+#[test]
+#[should_panic]
+fn max_occurrences_of_option() {
+    App::new("mo_opts_max")
+        .arg(Arg::from_usage("-o [out] 'capped option'").max_occurrences(1))
+        .get_matches_from(vec!["", "-o", "a", "-o", "b"]);
+}
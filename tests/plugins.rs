@@ -0,0 +1,183 @@
+#![cfg(unix)]
+
+extern crate clap;
+extern crate regex;
+
+include!("../clap-test.rs");
+
+use std::env;
+use std::fs::{self, File};
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+
+use clap::{App, AppSettings, ErrorKind, Repl, Shell, SubCommand};
+
+static PLUGINS_HELP: &'static str = "tool 1.0
+
+USAGE:
+    tool [SUBCOMMAND]
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+SUBCOMMANDS:
+    build    Builds the project
+    fmt      
+    help     Prints this message or the help of the given subcommand(s)
+    lint";
+
+// Creates a directory holding files with the given names and modes
+fn plugin_dir(name: &str, files: &[(&str, u32)]) -> PathBuf {
+    let dir = env::temp_dir().join(format!("clap-plugins-{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    for &(file, mode) in files {
+        let path = dir.join(file);
+        File::create(&path).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(mode)).unwrap();
+    }
+    dir
+}
+
+fn tool(dir: &PathBuf) -> App<'static, 'static> {
+    App::new("tool")
+        .version("1.0")
+        .plugin_dir(dir)
+        .subcommand(SubCommand::with_name("build").about("Builds the project"))
+}
+
+fn std_plugins(name: &str) -> PathBuf {
+    plugin_dir(
+        name,
+        &[
+            ("tool-lint", 0o755),
+            ("tool-fmt", 0o700),
+            ("tool-build", 0o755),
+            ("tool-notes", 0o644),
+            ("other-lint", 0o755),
+        ],
+    )
+}
+
+#[test]
+fn plugin_subcommand() {
+    let dir = std_plugins("subcommand");
+    let m = tool(&dir)
+        .get_matches_from_safe(vec!["tool", "lint", "--fix", "src"])
+        .unwrap();
+
+    match m.subcommand() {
+        ("lint", Some(sub_m)) => {
+            let args: Vec<_> = sub_m.values_of("").unwrap().collect();
+            assert_eq!(args, ["--fix", "src"]);
+        }
+        _ => panic!("expected the lint plugin"),
+    }
+    assert_eq!(m.plugin_path(), Some(&*dir.join("tool-lint")));
+}
+
+#[test]
+fn plugin_shadowed_by_subcommand() {
+    let dir = std_plugins("shadowed");
+    let m = tool(&dir).get_matches_from_safe(vec!["tool", "build"]).unwrap();
+
+    assert_eq!(m.subcommand_name(), Some("build"));
+    assert!(m.plugin_path().is_none());
+}
+
+#[test]
+fn plugin_not_executable() {
+    let dir = std_plugins("not-executable");
+    let res = tool(&dir).get_matches_from_safe(vec!["tool", "notes"]);
+
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind, ErrorKind::UnknownArgument);
+}
+
+#[test]
+fn plugin_first_dir_wins() {
+    let first = plugin_dir("first", &[("tool-lint", 0o755)]);
+    let second = plugin_dir("second", &[("tool-lint", 0o755), ("tool-fmt", 0o755)]);
+    let app = App::new("tool").plugin_dir(&first).plugin_dir(&second);
+
+    let m = app.clone().get_matches_from_safe(vec!["tool", "lint"]).unwrap();
+    assert_eq!(m.plugin_path(), Some(&*first.join("tool-lint")));
+
+    let m = app.get_matches_from_safe(vec!["tool", "fmt"]).unwrap();
+    assert_eq!(m.plugin_path(), Some(&*second.join("tool-fmt")));
+}
+
+#[test]
+fn plugins_of_bin_name() {
+    let dir = std_plugins("bin_name");
+    let m = App::new("Tool Suite")
+        .plugin_dir(&dir)
+        .get_matches_from_safe(vec!["/usr/bin/tool", "lint"])
+        .unwrap();
+    assert_eq!(m.plugin_path(), Some(&*dir.join("tool-lint")));
+}
+
+#[test]
+fn plugins_of_subcommand() {
+    let dir = plugin_dir("subcommand", &[("tool-remote-add", 0o755), ("tool-lint", 0o755)]);
+    let m = App::new("tool")
+        .plugin_dir(&dir)
+        .subcommand(SubCommand::with_name("remote").plugin_dir(&dir))
+        .get_matches_from_safe(vec!["tool", "remote", "add"])
+        .unwrap();
+    let sub_m = m.subcommand_matches("remote").unwrap();
+    assert_eq!(sub_m.subcommand_name(), Some("add"));
+}
+
+#[test]
+fn plugins_help() {
+    let dir = std_plugins("help");
+    assert!(test::compare_output(tool(&dir), "tool --help", PLUGINS_HELP, false));
+}
+
+#[test]
+#[cfg(feature = "suggestions")]
+fn plugins_did_you_mean() {
+    let dir = std_plugins("did-you-mean");
+    let res = tool(&dir).get_matches_from_safe(vec!["tool", "lnit"]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidSubcommand);
+    assert!(err.message.contains("Did you mean 'lint'?"));
+}
+
+#[test]
+fn plugins_dynamic_completions() {
+    let dir = std_plugins("dynamic");
+    let mut repl = Repl::new(tool(&dir));
+
+    assert_eq!(repl.complete("l"), ["lint"]);
+}
+
+#[test]
+fn plugins_completion_scripts() {
+    let dir = std_plugins("scripts");
+    let mut buf = vec![];
+    tool(&dir).gen_completions_to("tool", Shell::Bash, &mut buf);
+    let script = String::from_utf8(buf).unwrap();
+
+    assert!(script.contains("build help fmt lint"));
+}
+
+#[test]
+fn plugins_from_path() {
+    let dir = std_plugins("path");
+    env::set_var("PATH", &dir);
+    let app = App::new("tool").subcommand(SubCommand::with_name("build"));
+
+    let res = app.clone().get_matches_from_safe(vec!["tool", "lint"]);
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind, ErrorKind::UnknownArgument);
+
+    let m = app.setting(AppSettings::PluginSubcommands)
+        .get_matches_from_safe(vec!["tool", "lint"])
+        .unwrap();
+    assert_eq!(m.plugin_path(), Some(&*dir.join("tool-lint")));
+}
@@ -0,0 +1,201 @@
+extern crate clap;
+
+use std::env;
+use std::ffi::OsString;
+
+use clap::{App, Arg, ErrorKind, SubCommand, ValueProvider, ValueSource};
+
+struct Config {
+    name: &'static str,
+    entries: Vec<(&'static [&'static str], &'static str, &'static str)>,
+}
+
+impl ValueProvider for Config {
+    fn name(&self) -> &str { self.name }
+
+    fn values(&self, cmd: &[&str], arg: &str) -> Option<Vec<OsString>> {
+        self.entries
+            .iter()
+            .find(|e| e.0 == cmd && e.1 == arg)
+            .map(|e| vec![OsString::from(e.2)])
+    }
+}
+
+fn config(entries: Vec<(&'static [&'static str], &'static str, &'static str)>) -> Config {
+    Config {
+        name: "config",
+        entries: entries,
+    }
+}
+
+#[test]
+fn provided() {
+    let m = App::new("prog")
+        .value_provider(config(vec![(&[], "opt", "provided")]))
+        .arg(Arg::from_usage("--opt [VAL] 'some opt'"))
+        .get_matches_from_safe(vec!["prog"])
+        .unwrap();
+
+    assert!(m.is_present("opt"));
+    assert_eq!(m.occurrences_of("opt"), 0);
+    assert_eq!(m.value_of("opt"), Some("provided"));
+    assert_eq!(
+        m.value_source("opt"),
+        Some(ValueSource::Provider("config".into()))
+    );
+}
+
+#[test]
+fn user_override() {
+    let m = App::new("prog")
+        .value_provider(config(vec![(&[], "opt", "provided")]))
+        .arg(Arg::from_usage("--opt [VAL] 'some opt'"))
+        .get_matches_from_safe(vec!["prog", "--opt", "user"])
+        .unwrap();
+
+    assert_eq!(m.values_of("opt").unwrap().collect::<Vec<_>>(), ["user"]);
+    assert_eq!(m.value_source("opt"), Some(ValueSource::CommandLine));
+}
+
+#[test]
+fn env_override() {
+    env::set_var("CLP_TEST_PROVIDER_ENV", "env");
+
+    let m = App::new("prog")
+        .value_provider(config(vec![(&[], "opt", "provided")]))
+        .arg(Arg::from_usage("--opt [VAL] 'some opt'").env("CLP_TEST_PROVIDER_ENV"))
+        .get_matches_from_safe(vec!["prog"])
+        .unwrap();
+
+    assert_eq!(m.value_of("opt"), Some("env"));
+    assert_eq!(m.value_source("opt"), Some(ValueSource::EnvVariable));
+}
+
+#[test]
+fn unset_env_falls_back_to_provider() {
+    env::remove_var("CLP_TEST_PROVIDER_NO_ENV");
+
+    let m = App::new("prog")
+        .value_provider(config(vec![(&[], "opt", "provided")]))
+        .arg(Arg::from_usage("--opt [VAL] 'some opt'").env("CLP_TEST_PROVIDER_NO_ENV"))
+        .get_matches_from_safe(vec!["prog"])
+        .unwrap();
+
+    assert_eq!(m.value_of("opt"), Some("provided"));
+}
+
+#[test]
+fn overrides_default() {
+    let m = App::new("prog")
+        .value_provider(config(vec![(&[], "pos", "provided")]))
+        .arg(Arg::from_usage("[pos] 'some pos'").default_value("default"))
+        .arg(Arg::from_usage("--opt [VAL] 'some opt'").default_value("default"))
+        .get_matches_from_safe(vec!["prog"])
+        .unwrap();
+
+    assert_eq!(m.value_of("pos"), Some("provided"));
+    assert_eq!(
+        m.value_source("pos"),
+        Some(ValueSource::Provider("config".into()))
+    );
+    assert_eq!(m.value_of("opt"), Some("default"));
+    assert_eq!(m.value_source("opt"), Some(ValueSource::DefaultValue));
+}
+
+#[test]
+fn first_provider_wins() {
+    let m = App::new("prog")
+        .value_provider(Config {
+            name: "project",
+            entries: vec![(&[], "opt", "project")],
+        })
+        .value_provider(Config {
+            name: "user",
+            entries: vec![(&[], "opt", "user"), (&[], "other", "user")],
+        })
+        .arg(Arg::from_usage("--opt [VAL] 'some opt'"))
+        .arg(Arg::from_usage("--other [VAL] 'some other opt'"))
+        .get_matches_from_safe(vec!["prog"])
+        .unwrap();
+
+    assert_eq!(m.value_of("opt"), Some("project"));
+    assert_eq!(m.value_of("other"), Some("user"));
+    assert_eq!(
+        m.value_source("other"),
+        Some(ValueSource::Provider("user".into()))
+    );
+}
+
+#[test]
+fn subcommand_path() {
+    let m = App::new("prog")
+        .value_provider(config(vec![
+            (&[], "opt", "top"),
+            (&["remote", "add"], "opt", "nested"),
+        ]))
+        .arg(Arg::from_usage("--opt [VAL] 'some opt'"))
+        .subcommand(SubCommand::with_name("remote").subcommand(
+            SubCommand::with_name("add").arg(Arg::from_usage("--opt [VAL] 'some opt'")),
+        ))
+        .get_matches_from_safe(vec!["prog", "remote", "add"])
+        .unwrap();
+
+    assert_eq!(m.value_of("opt"), Some("top"));
+    let add = m.subcommand_matches("remote")
+        .unwrap()
+        .subcommand_matches("add")
+        .unwrap();
+    assert_eq!(add.value_of("opt"), Some("nested"));
+}
+
+#[test]
+fn subcommand_provider_first() {
+    let m = App::new("prog")
+        .value_provider(Config {
+            name: "app",
+            entries: vec![(&["sub"], "opt", "app")],
+        })
+        .subcommand(
+            SubCommand::with_name("sub")
+                .value_provider(Config {
+                    name: "sub",
+                    entries: vec![(&["sub"], "opt", "sub")],
+                })
+                .arg(Arg::from_usage("--opt [VAL] 'some opt'")),
+        )
+        .get_matches_from_safe(vec!["prog", "sub"])
+        .unwrap();
+
+    assert_eq!(
+        m.subcommand_matches("sub").unwrap().value_of("opt"),
+        Some("sub")
+    );
+}
+
+#[test]
+fn provided_values_are_validated() {
+    let r = App::new("prog")
+        .value_provider(config(vec![(&[], "color", "sometimes")]))
+        .arg(
+            Arg::from_usage("--color [WHEN] 'when to use colors'")
+                .possible_values(&["always", "never"]),
+        )
+        .get_matches_from_safe(vec!["prog"]);
+
+    assert!(r.is_err());
+    assert_eq!(r.unwrap_err().kind, ErrorKind::InvalidValue);
+}
+
+#[test]
+fn provided_values_are_delimited() {
+    let m = App::new("prog")
+        .value_provider(config(vec![(&[], "opt", "a,b,c")]))
+        .arg(Arg::from_usage("--opt [VAL]... 'some opt'").use_delimiter(true))
+        .get_matches_from_safe(vec!["prog"])
+        .unwrap();
+
+    assert_eq!(
+        m.values_of("opt").unwrap().collect::<Vec<_>>(),
+        ["a", "b", "c"]
+    );
+}
@@ -0,0 +1,118 @@
+extern crate clap;
+
+use clap::{App, AppSettings, Arg, ErrorKind, Repl, SubCommand};
+
+fn console() -> Repl<'static, 'static> {
+    Repl::new(App::new("console")
+        .setting(AppSettings::SubcommandRequired)
+        .subcommand(SubCommand::with_name("say")
+            .arg(Arg::from_usage("-l, --loud 'say it loudly'"))
+            .arg(Arg::from_usage("<words>... 'what to say'")))
+        .subcommand(SubCommand::with_name("set")
+            .arg(Arg::from_usage("--level <level> 'the log level'")
+                .possible_values(&["info", "debug"]))
+            .arg(Arg::from_usage("--quiet 'log nothing'").overrides_with("level"))
+            .arg(Arg::from_usage("--verbose 'log everything'").overrides_with("quiet")))
+        .subcommand(SubCommand::with_name("shutdown")))
+}
+
+#[test]
+fn repl_lines() {
+    let mut repl = console();
+
+    let m = repl.parse_line("say -l \"hi there\" 'you'\n").unwrap().unwrap();
+    let say = m.subcommand_matches("say").unwrap();
+    assert!(say.is_present("loud"));
+    assert_eq!(say.values_of("words").unwrap().collect::<Vec<_>>(), ["hi there", "you"]);
+
+    let m = repl.parse_line("say again").unwrap().unwrap();
+    let say = m.subcommand_matches("say").unwrap();
+    assert!(!say.is_present("loud"));
+    assert_eq!(say.values_of("words").unwrap().collect::<Vec<_>>(), ["again"]);
+}
+
+#[test]
+fn repl_blank_line() {
+    assert!(console().parse_line(" \t\n").unwrap().is_none());
+}
+
+#[test]
+fn repl_invalid_line() {
+    let err = console().parse_line("say 'hi").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidLine);
+    assert_eq!(err.info, Some(vec!["say 'hi".to_owned()]));
+}
+
+#[test]
+fn repl_keeps_going() {
+    let mut repl = console();
+
+    let err = repl.parse_line("say --help").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.contains("USAGE:\n    say [FLAGS] <words>..."));
+
+    let err = repl.parse_line("shout").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidSubcommand);
+
+    assert!(repl.parse_line("shutdown").unwrap().unwrap().is_present("shutdown"));
+}
+
+#[test]
+fn repl_requirements_survive_overrides() {
+    let mut repl = console();
+
+    let m = repl.parse_line("set --level debug --quiet").unwrap().unwrap();
+    assert!(!m.subcommand_matches("set").unwrap().is_present("level"));
+
+    let err = repl.parse_line("set").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+
+    let m = repl.parse_line("set --quiet --verbose --level info").unwrap().unwrap();
+    let set = m.subcommand_matches("set").unwrap();
+    assert!(set.is_present("verbose"));
+    assert!(!set.is_present("quiet"));
+    assert_eq!(set.value_of("level"), Some("info"));
+}
+
+#[test]
+fn repl_trailing_values_reset() {
+    let mut repl = console();
+
+    let m = repl.parse_line("say -- -l").unwrap().unwrap();
+    assert_eq!(m.subcommand_matches("say").unwrap().value_of("words"), Some("-l"));
+
+    let m = repl.parse_line("say -l x").unwrap().unwrap();
+    assert!(m.subcommand_matches("say").unwrap().is_present("loud"));
+}
+
+#[test]
+fn repl_complete() {
+    let mut repl = console();
+
+    assert_eq!(repl.complete("s"), ["say", "set", "shutdown"]);
+    assert_eq!(repl.complete("sa"), ["say"]);
+    assert_eq!(repl.complete("say --"), ["--loud", "--help", "--version"]);
+    assert_eq!(repl.complete("set --level "), ["info", "debug"]);
+    assert_eq!(repl.complete("set --level 'd"), ["debug"]);
+
+    // Completing doesn't get in the way of parsing
+    assert!(repl.parse_line("shutdown").unwrap().unwrap().is_present("shutdown"));
+}
+
+#[test]
+fn repl_run() {
+    let mut repl = console();
+    let mut out = vec![];
+    let mut said = vec![];
+    repl.run("> ", &b"say hi\n\nbogus\nshutdown\nsay bye\n"[..], &mut out, |m| {
+        if let Some(say) = m.subcommand_matches("say") {
+            said.push(say.value_of("words").unwrap().to_owned());
+        }
+        !m.is_present("shutdown")
+    }).unwrap();
+
+    assert_eq!(said, ["hi"]);
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("> > > error: "));
+    assert!(out.ends_with("\n> "));
+}
@@ -0,0 +1,151 @@
+extern crate clap;
+
+use std::env;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+
+use clap::{App, AppSettings, Arg, ErrorKind};
+
+fn response_file(name: &str, contents: &str) -> PathBuf {
+    let path = env::temp_dir().join(name);
+    File::create(&path)
+        .and_then(|mut f| f.write_all(contents.as_bytes()))
+        .unwrap();
+    path
+}
+
+fn at(path: &PathBuf) -> String { format!("@{}", path.display()) }
+
+fn app() -> App<'static, 'static> {
+    App::new("prog")
+        .setting(AppSettings::ResponseFiles)
+        .arg(Arg::from_usage("-f, --flag 'some flag'"))
+        .arg(Arg::from_usage("-o, --opt [val]... 'some option'"))
+        .arg(Arg::from_usage("[files]... 'some files'"))
+}
+
+#[test]
+fn response_file_expanded() {
+    let path = response_file(
+        "clap-response-expanded.txt",
+        "--opt 'one two' \"three\\\"four\"\n  -f\tfile\\ name\n",
+    );
+    let m = app()
+        .get_matches_from_safe(vec!["prog".to_owned(), at(&path), "last".to_owned()])
+        .unwrap();
+
+    assert!(m.is_present("flag"));
+    assert_eq!(
+        m.values_of("opt").unwrap().collect::<Vec<_>>(),
+        ["one two", "three\"four"]
+    );
+    assert_eq!(
+        m.values_of("files").unwrap().collect::<Vec<_>>(),
+        ["file name", "last"]
+    );
+}
+
+#[test]
+fn response_file_indices() {
+    let path = response_file("clap-response-indices.txt", "-o val -f");
+    let m = app()
+        .get_matches_from_safe(vec!["prog".to_owned(), at(&path), "file".to_owned()])
+        .unwrap();
+
+    assert_eq!(m.index_of("opt"), Some(2));
+    assert_eq!(m.index_of("flag"), Some(3));
+    assert_eq!(m.index_of("files"), Some(4));
+}
+
+#[test]
+fn response_file_includes() {
+    let inner = response_file("clap-response-inner.txt", "-f inner");
+    let outer = response_file(
+        "clap-response-outer.txt",
+        &format!("outer '{}' after", at(&inner)),
+    );
+    let m = app()
+        .get_matches_from_safe(vec!["prog".to_owned(), at(&outer)])
+        .unwrap();
+
+    assert!(m.is_present("flag"));
+    assert_eq!(
+        m.values_of("files").unwrap().collect::<Vec<_>>(),
+        ["outer", "inner", "after"]
+    );
+}
+
+#[test]
+fn response_file_includes_itself() {
+    let path = env::temp_dir().join("clap-response-cycle.txt");
+    response_file("clap-response-cycle.txt", &format!("a '{}'", at(&path)));
+    let res = app().get_matches_from_safe(vec!["prog".to_owned(), at(&path)]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Io);
+    assert!(err.message.contains("it includes itself"));
+}
+
+#[test]
+fn response_file_included_twice() {
+    let path = response_file("clap-response-twice.txt", "a");
+    let m = app()
+        .get_matches_from_safe(vec!["prog".to_owned(), at(&path), at(&path)])
+        .unwrap();
+
+    assert_eq!(m.values_of("files").unwrap().collect::<Vec<_>>(), ["a", "a"]);
+}
+
+#[test]
+fn response_file_missing() {
+    let res = app().get_matches_from_safe(vec!["prog", "@clap/does/not/exist.txt"]);
+
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Io);
+    assert_eq!(err.info, Some(vec!["clap/does/not/exist.txt".to_owned()]));
+}
+
+#[test]
+fn response_file_unclosed_quote() {
+    let path = response_file("clap-response-unclosed.txt", "'a b");
+    let res = app().get_matches_from_safe(vec!["prog".to_owned(), at(&path)]);
+
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind, ErrorKind::Io);
+}
+
+#[test]
+fn response_file_not_expanded_without_setting() {
+    let m = App::new("prog")
+        .arg(Arg::from_usage("[files]... 'some files'"))
+        .get_matches_from_safe(vec!["prog", "@clap/does/not/exist.txt"])
+        .unwrap();
+
+    assert_eq!(m.value_of("files"), Some("@clap/does/not/exist.txt"));
+}
+
+#[test]
+fn response_file_not_expanded_after_double_dash() {
+    let path = response_file("clap-response-dash.txt", "a -- @clap/does/not/exist.txt");
+    let m = app()
+        .get_matches_from_safe(vec![
+            "prog".to_owned(),
+            at(&path),
+            "@clap/does/not/exist.txt".to_owned(),
+            "@".to_owned(),
+        ])
+        .unwrap();
+
+    assert_eq!(
+        m.values_of("files").unwrap().collect::<Vec<_>>(),
+        [
+            "a",
+            "@clap/does/not/exist.txt",
+            "@clap/does/not/exist.txt",
+            "@",
+        ]
+    );
+}
@@ -0,0 +1,252 @@
+#![cfg(feature = "serde_defs")]
+
+extern crate clap;
+
+use clap::{App, AppDef, AppSettings, Arg, ArgGroup, SubCommand};
+
+static JSON: &'static str = r#"{
+    "name": "claptests",
+    "version": "1.0",
+    "about": "tests clap library",
+    "settings": ["ArgRequiredElseHelp"],
+    "args": [
+        { "opt": { "short": "o", "long": "option", "multiple": true, "help": "tests options" } },
+        { "positional": { "index": 1, "help": "tests positionals" } },
+        { "flag": { "short": "f", "long": "flag", "global": true, "group": "mode" } },
+        { "level": {
+            "long": "level",
+            "takes_value": true,
+            "possible_values": ["low", "high"],
+            "default_value_if": [["flag", null, "high"]]
+        } }
+    ],
+    "groups": [
+        { "mode": { "args": ["opt"] } }
+    ],
+    "subcommands": [
+        { "subcmd": {
+            "about": "tests subcommands",
+            "args": [{ "scoption": { "short": "o", "takes_value": true } }]
+        } }
+    ]
+}"#;
+
+static TOML: &'static str = r#"
+name = "claptests"
+version = "1.0"
+about = "tests clap library"
+settings = ["ArgRequiredElseHelp"]
+
+[[args]]
+opt = { short = "o", long = "option", multiple = true, help = "tests options" }
+
+[[args]]
+positional = { index = 1, help = "tests positionals" }
+
+[[args]]
+flag = { short = "f", long = "flag", global = true, group = "mode" }
+
+[[args]]
+level = { long = "level", takes_value = true, possible_values = ["low", "high"] }
+
+[[groups]]
+mode = { args = ["opt"] }
+
+[[subcommands]]
+[subcommands.subcmd]
+about = "tests subcommands"
+args = [{ scoption = { short = "o", takes_value = true } }]
+"#;
+
+fn help(app: &App) -> String {
+    let mut out = vec![];
+    app.clone().write_long_help(&mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn json_app() {
+    let def = AppDef::from_json(JSON).unwrap();
+    assert_eq!(def.name, "claptests");
+    assert_eq!(def.settings, [AppSettings::ArgRequiredElseHelp]);
+    let m = def.to_app()
+        .get_matches_from_safe(vec!["claptests", "-f", "pos", "subcmd", "-o", "val"])
+        .unwrap();
+    assert!(m.is_present("flag"));
+    assert!(m.is_present("mode"));
+    assert_eq!(m.value_of("positional"), Some("pos"));
+    assert_eq!(m.value_of("level"), Some("high"));
+    let sub_m = m.subcommand_matches("subcmd").unwrap();
+    assert_eq!(sub_m.value_of("scoption"), Some("val"));
+}
+
+#[test]
+fn toml_app() {
+    let def = AppDef::from_toml(TOML).unwrap();
+    let json = AppDef::from_json(JSON).unwrap();
+    // TOML can't express the `null` in default_value_if, everything else is the same
+    assert_eq!(def.args[3].default_value_ifs.len(), 0);
+    assert_eq!(help(&def.to_app()), help(&json.to_app()));
+}
+
+#[test]
+fn yaml_style_named_root() {
+    let def = AppDef::from_json(r#"{ "prog": { "version": "2.0" } }"#).unwrap();
+    assert_eq!(def.name, "prog");
+    assert_eq!(def.version, Some("2.0".into()));
+}
+
+#[test]
+fn single_string_for_array() {
+    let def = AppDef::from_json(
+        r#"{ "name": "prog", "args": [{ "a": { "long": "a", "requires": "b" } }, { "b": null }] }"#,
+    ).unwrap();
+    assert_eq!(def.args[0].requires, ["b"]);
+    assert_eq!(def.args[1].name, "b");
+}
+
+#[test]
+fn error_wrong_type() {
+    let err = AppDef::from_json(
+        r#"{ "name": "prog", "subcommands": [{ "test": { "args": [{ "v": { "multiple": "yes" } }] } }] }"#,
+    ).unwrap_err();
+    assert_eq!(err.path, "subcommands[0].test.args[0].v.multiple");
+    assert_eq!(err.message, "expected a boolean, found a string");
+    assert_eq!(
+        err.to_string(),
+        "subcommands[0].test.args[0].v.multiple: expected a boolean, found a string"
+    );
+}
+
+#[test]
+fn error_unknown_key() {
+    let err = AppDef::from_toml("name = \"prog\"\n[[args]]\nv = { shrot = \"v\" }\n").unwrap_err();
+    assert_eq!(err.path, "args[0].v.shrot");
+    assert_eq!(err.message, "unknown key");
+}
+
+#[test]
+fn error_unknown_setting() {
+    let err = AppDef::from_json(r#"{ "name": "prog", "settings": ["ColoredHelp", "Colourful"] }"#)
+        .unwrap_err();
+    assert_eq!(err.path, "settings[1]");
+    assert_eq!(err.message, "unknown setting 'Colourful'");
+}
+
+#[test]
+fn error_bad_tuple() {
+    let err = AppDef::from_json(
+        r#"{ "name": "prog", "args": [{ "a": { "required_if": [["b"]] } }] }"#,
+    ).unwrap_err();
+    assert_eq!(err.path, "args[0].a.required_if[0]");
+    assert_eq!(err.message, "expected an array of 2 elements, found an array");
+}
+
+#[test]
+fn error_missing_name() {
+    let err = AppDef::from_json(r#"{ "version": "1.0", "about": "no name" }"#).unwrap_err();
+    assert_eq!(err.path, "");
+    assert_eq!(err.message, "missing key 'name'");
+}
+
+#[test]
+fn error_syntax() {
+    let err = AppDef::from_json(r#"{ "name": "prog", "#).unwrap_err();
+    assert_eq!(err.path, "");
+    assert!(!err.message.is_empty());
+}
+
+fn complex_app() -> App<'static, 'static> {
+    App::new("prog")
+        .version("1.0")
+        .author("Kevin K.")
+        .about("does things")
+        .after_help("more help")
+        .setting(AppSettings::SubcommandsNegateReqs)
+        .global_setting(AppSettings::ColoredHelp)
+        .arg(
+            Arg::with_name("verbose")
+                .short("v")
+                .long("verbose")
+                .multiple(true)
+                .help("more output"),
+        )
+        .arg(
+            Arg::with_name("config")
+                .short("c")
+                .long("config")
+                .value_name("FILE")
+                .env("PROG_CONFIG")
+                .visible_alias("cfg")
+                .help("sets the config"),
+        )
+        .arg(
+            Arg::with_name("mode")
+                .long("mode")
+                .possible_values(&["fast", "slow"])
+                .default_value("fast")
+                .required_if("verbose", "x")
+                .hidden(true),
+        )
+        .arg(
+            Arg::with_name("list")
+                .long("list")
+                .takes_value(true)
+                .multiple(true)
+                .require_delimiter(true)
+                .value_delimiter(":")
+                .conflicts_with("b"),
+        )
+        .arg(Arg::with_name("input").required(true).help("the input"))
+        .arg(Arg::with_name("rest").multiple(true).last(true))
+        .arg(Arg::with_name("a").long("a").group("grp"))
+        .arg(Arg::with_name("b").long("b"))
+        .group(ArgGroup::with_name("grp").arg("b").required(false).max_args(2))
+        .subcommand(
+            SubCommand::with_name("sub")
+                .about("a subcommand")
+                .visible_alias("s")
+                .arg(Arg::with_name("opt").short("o").takes_value(true)),
+        )
+}
+
+#[test]
+fn app_to_def_roundtrip() {
+    let app = complex_app();
+    let def = AppDef::from(&app);
+    assert!(def.settings.contains(&AppSettings::SubcommandsNegateReqs));
+    assert_eq!(def.global_settings, [AppSettings::ColoredHelp]);
+    assert_eq!(help(&def.to_app()), help(&app));
+
+    let json = AppDef::from_json(&def.to_json()).unwrap();
+    assert_eq!(json, def);
+    let toml = AppDef::from_toml(&def.to_toml().unwrap()).unwrap();
+    assert_eq!(toml, def);
+}
+
+#[test]
+fn app_to_def_parses_the_same() {
+    let def = AppDef::from(&complex_app());
+    let args = vec!["prog", "-vv", "--list", "a:b", "--cfg", "c.toml", "--a", "in", "--", "r"];
+    let m1 = complex_app().get_matches_from(args.clone());
+    let m2 = def.to_app().get_matches_from(args);
+    assert_eq!(m1.occurrences_of("verbose"), m2.occurrences_of("verbose"));
+    assert_eq!(
+        m1.values_of("list").unwrap().collect::<Vec<_>>(),
+        m2.values_of("list").unwrap().collect::<Vec<_>>()
+    );
+    assert_eq!(m2.value_of("config"), Some("c.toml"));
+    assert_eq!(m2.value_of("mode"), Some("fast"));
+    assert_eq!(m2.value_of("rest"), Some("r"));
+    assert!(m2.is_present("grp"));
+}
+
+#[test]
+fn built_app_leaves_out_generated_args() {
+    let mut app = complex_app();
+    let _ = app.get_matches_from_safe_borrow(vec!["prog", "in"]);
+    let def = AppDef::from(&app);
+    assert!(def.args.iter().all(|a| a.long != Some("help".into())));
+    assert!(def.args.iter().all(|a| a.long != Some("version".into())));
+    assert!(def.subcommands.iter().all(|sc| sc.name != "help"));
+}
@@ -3,7 +3,7 @@ extern crate regex;
 
 include!("../clap-test.rs");
 
-use clap::{App, Arg, SubCommand, ErrorKind};
+use clap::{App, AppSettings, Arg, SubCommand, ErrorKind};
 
 static VISIBLE_ALIAS_HELP: &'static str = "clap-test 2.6
 
@@ -18,6 +18,29 @@ SUBCOMMANDS:
     help    Prints this message or the help of the given subcommand(s)
     test    Some help [aliases: dongle, done]";
 
+static CHAIN_MISSING_ERROR: &'static str = "error: The following required arguments were not provided:
+    <suite>
+
+USAGE:
+    tool test <suite>
+
+For more information try --help";
+
+static CHAIN_HELP: &'static str = "tool 1.0
+
+USAGE:
+    tool [SUBCOMMAND]...
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+SUBCOMMANDS:
+    build      Builds the project
+    help       Prints this message or the help of the given subcommand(s)
+    publish    Publishes the project
+    test       Tests the project";
+
 static INVISIBLE_ALIAS_HELP: &'static str = "clap-test 2.6
 
 USAGE:
@@ -198,4 +221,134 @@ fn issue_1031_args_with_same_name_no_more_vals() {
     let m = res.unwrap();
     assert_eq!(m.value_of("ui-path"), Some("value"));
     assert_eq!(m.subcommand_name(), Some("signer"));
-}
\ No newline at end of file
+}
+fn chain_app() -> App<'static, 'static> {
+    App::new("tool")
+        .setting(AppSettings::ChainSubcommands)
+        .arg(Arg::from_usage("-v, --verbose 'log everything'").global(true))
+        .subcommand(SubCommand::with_name("build")
+            .alias("b")
+            .arg(Arg::from_usage("--release 'build in release mode'"))
+            .arg(Arg::from_usage("--target [target] 'the target to build'")))
+        .subcommand(SubCommand::with_name("test")
+            .arg(Arg::from_usage("--quick 'skip slow tests'"))
+            .arg(Arg::from_usage("[suite] 'the suite to run'")))
+        .subcommand(SubCommand::with_name("publish"))
+}
+
+fn sc_names<'a>(m: &'a clap::ArgMatches) -> Vec<&'a str> {
+    m.subcommands().map(|(name, _)| name).collect()
+}
+
+#[test]
+fn chain_subcommands() {
+    let m = chain_app()
+        .get_matches_from_safe(vec!["tool", "build", "--release", "test", "--quick", "publish"])
+        .unwrap();
+
+    assert_eq!(sc_names(&m), ["build", "test", "publish"]);
+    assert_eq!(m.subcommand_name(), Some("build"));
+    assert!(m.is_present("publish"));
+    assert!(m.subcommand_matches("build").unwrap().is_present("release"));
+    assert!(!m.subcommand_matches("build").unwrap().is_present("quick"));
+    assert!(m.subcommand_matches("test").unwrap().is_present("quick"));
+}
+
+#[test]
+fn chain_subcommands_repeated() {
+    let m = chain_app()
+        .get_matches_from_safe(vec!["tool", "test", "unit", "b", "test", "--quick"])
+        .unwrap();
+
+    let scs: Vec<_> = m.subcommands().collect();
+    assert_eq!(scs.len(), 3);
+    assert_eq!(scs[0].0, "test");
+    assert_eq!(scs[0].1.value_of("suite"), Some("unit"));
+    assert_eq!(scs[1].0, "build");
+    assert_eq!(scs[2].0, "test");
+    assert!(scs[2].1.is_present("quick"));
+    assert!(!scs[2].1.is_present("suite"));
+}
+
+#[test]
+fn chain_subcommands_option_values() {
+    let m = chain_app()
+        .get_matches_from_safe(vec!["tool", "build", "--target", "test", "publish"])
+        .unwrap();
+
+    assert_eq!(sc_names(&m), ["build", "publish"]);
+    assert_eq!(
+        m.subcommand_matches("build").unwrap().value_of("target"),
+        Some("test")
+    );
+}
+
+#[test]
+fn chain_subcommands_after_trailing() {
+    let m = chain_app()
+        .get_matches_from_safe(vec!["tool", "test", "--", "publish"])
+        .unwrap();
+
+    assert_eq!(sc_names(&m), ["test"]);
+    assert_eq!(m.subcommand_matches("test").unwrap().value_of("suite"), Some("publish"));
+}
+
+#[test]
+fn chain_subcommands_global_args() {
+    let m = chain_app()
+        .setting(AppSettings::PropagateGlobalValuesDown)
+        .get_matches_from_safe(vec!["tool", "-v", "build", "test"])
+        .unwrap();
+
+    for (_, sc_m) in m.subcommands() {
+        assert!(sc_m.is_present("verbose"));
+    }
+}
+
+#[test]
+fn chain_subcommands_nested_first() {
+    let m = App::new("tool")
+        .setting(AppSettings::ChainSubcommands)
+        .subcommand(SubCommand::with_name("remote")
+            .subcommand(SubCommand::with_name("add")))
+        .subcommand(SubCommand::with_name("add"))
+        .get_matches_from_safe(vec!["tool", "remote", "add"])
+        .unwrap();
+
+    assert_eq!(sc_names(&m), ["remote"]);
+    assert_eq!(m.subcommand_matches("remote").unwrap().subcommand_name(), Some("add"));
+}
+
+#[test]
+fn chain_subcommands_validated_separately() {
+    let app = App::new("tool")
+        .setting(AppSettings::ChainSubcommands)
+        .subcommand(SubCommand::with_name("build"))
+        .subcommand(SubCommand::with_name("test")
+            .arg(Arg::from_usage("<suite> 'the suite to run'")));
+
+    assert!(test::compare_output(app, "tool build test", CHAIN_MISSING_ERROR, true));
+}
+
+#[test]
+fn chain_subcommands_help() {
+    let app = App::new("tool")
+        .version("1.0")
+        .setting(AppSettings::ChainSubcommands)
+        .subcommand(SubCommand::with_name("build").about("Builds the project"))
+        .subcommand(SubCommand::with_name("test").about("Tests the project"))
+        .subcommand(SubCommand::with_name("publish").about("Publishes the project"));
+
+    assert!(test::compare_output(app, "tool --help", CHAIN_HELP, false));
+}
+
+#[test]
+fn chain_subcommands_needs_setting() {
+    let res = App::new("tool")
+        .subcommand(SubCommand::with_name("build"))
+        .subcommand(SubCommand::with_name("test"))
+        .get_matches_from_safe(vec!["tool", "build", "test"]);
+
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind, ErrorKind::UnknownArgument);
+}
@@ -0,0 +1,160 @@
+extern crate clap;
+extern crate regex;
+
+include!("../clap-test.rs");
+
+use std::net::Ipv4Addr;
+
+use clap::{App, Arg, ErrorKind};
+
+fn port_parser(s: &str) -> Result<u16, String> { s.parse::<u16>().map_err(|e| e.to_string()) }
+
+static PARSE_ERR: &'static str = "error: Invalid value 'http' for '--port <port>': invalid digit found in string
+
+USAGE:
+    prog --port <port>
+
+For more information try --help";
+
+#[test]
+fn value_parser_single() {
+    let m = App::new("prog")
+        .arg(
+            Arg::with_name("port")
+                .long("port")
+                .takes_value(true)
+                .value_parser(port_parser),
+        )
+        .get_matches_from(vec!["prog", "--port", "8080"]);
+    assert_eq!(m.get::<u16>("port"), Some(&8080));
+    assert_eq!(m.value_of("port"), Some("8080"));
+}
+
+#[test]
+fn value_parser_not_present() {
+    let m = App::new("prog")
+        .arg(
+            Arg::with_name("port")
+                .long("port")
+                .takes_value(true)
+                .value_parser(port_parser),
+        )
+        .get_matches_from(vec!["prog"]);
+    assert_eq!(m.get::<u16>("port"), None);
+    assert!(m.get_many::<u16>("port").is_none());
+}
+
+#[test]
+fn value_parser_multiple() {
+    let m = App::new("prog")
+        .arg(
+            Arg::with_name("addr")
+                .short("a")
+                .multiple(true)
+                .takes_value(true)
+                .value_parser(|s: &str| s.parse::<Ipv4Addr>().map_err(|e| e.to_string())),
+        )
+        .get_matches_from(vec!["prog", "-a", "127.0.0.1", "-a", "10.0.0.1"]);
+    let addrs: Vec<_> = m.get_many::<Ipv4Addr>("addr").unwrap().collect();
+    assert_eq!(
+        addrs,
+        [&Ipv4Addr::new(127, 0, 0, 1), &Ipv4Addr::new(10, 0, 0, 1)]
+    );
+}
+
+#[test]
+fn value_parser_positional() {
+    let m = App::new("prog")
+        .arg(Arg::with_name("nums").multiple(true).value_parser(|s: &str| {
+            s.parse::<i64>().map_err(|e| e.to_string())
+        }))
+        .get_matches_from(vec!["prog", "1", "2", "3"]);
+    assert_eq!(m.get_many::<i64>("nums").unwrap().sum::<i64>(), 6);
+}
+
+#[test]
+fn value_parser_default_value() {
+    let m = App::new("prog")
+        .arg(
+            Arg::with_name("port")
+                .long("port")
+                .takes_value(true)
+                .default_value("80")
+                .value_parser(port_parser),
+        )
+        .get_matches_from(vec!["prog"]);
+    assert_eq!(m.get::<u16>("port"), Some(&80));
+}
+
+#[test]
+fn value_parser_custom_type() {
+    #[derive(Debug, PartialEq)]
+    struct Pair(String, String);
+
+    let m = App::new("prog")
+        .arg(
+            Arg::with_name("pair")
+                .long("pair")
+                .takes_value(true)
+                .value_parser(|s: &str| {
+                    let mut it = s.splitn(2, ':');
+                    match (it.next(), it.next()) {
+                        (Some(a), Some(b)) => Ok(Pair(a.into(), b.into())),
+                        _ => Err(String::from("expected two values separated by ':'")),
+                    }
+                }),
+        )
+        .get_matches_from(vec!["prog", "--pair", "a:b"]);
+    assert_eq!(m.get::<Pair>("pair"), Some(&Pair("a".into(), "b".into())));
+}
+
+#[test]
+fn value_parser_error() {
+    let res = App::new("prog")
+        .arg(
+            Arg::with_name("port")
+                .long("port")
+                .takes_value(true)
+                .value_parser(port_parser),
+        )
+        .get_matches_from_safe(vec!["prog", "--port", "http"]);
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ValueValidation);
+    assert_eq!(err.info, Some(vec!["port".to_owned(), "http".to_owned()]));
+}
+
+#[test]
+fn value_parser_error_output() {
+    let app = App::new("prog").arg(
+        Arg::with_name("port")
+            .long("port")
+            .takes_value(true)
+            .required(true)
+            .value_parser(port_parser),
+    );
+    assert!(test::compare_output(app, "prog --port http", PARSE_ERR, true));
+}
+
+#[test]
+#[should_panic]
+fn get_without_value_parser() {
+    let m = App::new("prog")
+        .arg(Arg::with_name("port").long("port").takes_value(true))
+        .get_matches_from(vec!["prog", "--port", "8080"]);
+    m.get::<u16>("port");
+}
+
+#[test]
+#[should_panic]
+fn get_wrong_type() {
+    let m = App::new("prog")
+        .arg(
+            Arg::with_name("port")
+                .long("port")
+                .takes_value(true)
+                .value_parser(port_parser),
+        )
+        .get_matches_from(vec!["prog", "--port", "8080"]);
+    m.get::<u32>("port");
+}
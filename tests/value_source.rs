@@ -0,0 +1,134 @@
+extern crate clap;
+
+use std::env;
+
+use clap::{App, AppSettings, Arg, ArgGroup, SubCommand, ValueSource};
+
+#[test]
+fn command_line() {
+    let m = App::new("prog")
+        .arg(Arg::from_usage("-o, --opt [VAL]... 'some opt'"))
+        .arg(Arg::from_usage("-f, --flag 'some flag'"))
+        .arg(Arg::from_usage("[pos] 'some pos'"))
+        .get_matches_from(vec!["prog", "-o", "a", "b", "-f", "p"]);
+
+    assert_eq!(m.value_source("opt"), Some(ValueSource::CommandLine));
+    assert_eq!(m.value_source("flag"), Some(ValueSource::CommandLine));
+    assert_eq!(m.value_source("pos"), Some(ValueSource::CommandLine));
+    assert_eq!(m.value_sources_of("opt").unwrap().len(), 2);
+    assert_eq!(m.value_sources_of("flag").unwrap().len(), 0);
+}
+
+#[test]
+fn not_present() {
+    let m = App::new("prog")
+        .arg(Arg::from_usage("-o, --opt [VAL] 'some opt'"))
+        .get_matches_from(vec!["prog"]);
+
+    assert_eq!(m.value_source("opt"), None);
+    assert!(m.value_sources_of("opt").is_none());
+}
+
+#[test]
+fn env_variable() {
+    env::set_var("CLP_TEST_VALUE_SOURCE_ENV", "env");
+
+    let m = App::new("prog")
+        .arg(
+            Arg::from_usage("-o, --opt [VAL] 'some opt'")
+                .env("CLP_TEST_VALUE_SOURCE_ENV")
+                .default_value("default"),
+        )
+        .get_matches_from(vec!["prog"]);
+
+    assert_eq!(m.value_of("opt"), Some("env"));
+    assert_eq!(m.value_source("opt"), Some(ValueSource::EnvVariable));
+}
+
+#[test]
+fn default_value() {
+    let m = App::new("prog")
+        .arg(Arg::from_usage("-o, --opt [VAL] 'some opt'").default_value("default"))
+        .get_matches_from(vec!["prog"]);
+
+    assert!(m.is_present("opt"));
+    assert_eq!(m.value_source("opt"), Some(ValueSource::DefaultValue));
+}
+
+#[test]
+fn default_value_if() {
+    let m = App::new("prog")
+        .arg(Arg::from_usage("-f, --flag 'some flag'"))
+        .arg(
+            Arg::from_usage("-o, --opt [VAL] 'some opt'")
+                .default_value_if("flag", None, "default"),
+        )
+        .get_matches_from(vec!["prog", "-f"]);
+
+    assert_eq!(m.value_of("opt"), Some("default"));
+    assert_eq!(m.value_source("opt"), Some(ValueSource::DefaultValue));
+}
+
+#[test]
+fn used_without_value() {
+    let m = App::new("prog")
+        .arg(
+            Arg::from_usage("-o, --opt [VAL] 'some opt'")
+                .min_values(0)
+                .default_value("default"),
+        )
+        .get_matches_from(vec!["prog", "--opt"]);
+
+    assert_eq!(m.value_of("opt"), Some("default"));
+    assert_eq!(m.value_source("opt"), Some(ValueSource::CommandLine));
+    assert_eq!(
+        m.value_sources_of("opt").unwrap().collect::<Vec<_>>(),
+        [&ValueSource::DefaultValue]
+    );
+}
+
+#[test]
+fn group_values() {
+    let m = App::new("prog")
+        .arg(Arg::from_usage("--input [FILE] 'some input'"))
+        .arg(Arg::from_usage("--stdin [NAME] 'stdin name'").default_value("-"))
+        .group(
+            ArgGroup::with_name("sources")
+                .args(&["input", "stdin"])
+                .multiple(true),
+        )
+        .get_matches_from(vec!["prog", "--input", "in.txt"]);
+
+    assert_eq!(m.value_source("sources"), Some(ValueSource::CommandLine));
+    assert_eq!(
+        m.value_sources_of("sources").unwrap().collect::<Vec<_>>(),
+        [&ValueSource::CommandLine, &ValueSource::DefaultValue]
+    );
+}
+
+#[test]
+fn self_override_keeps_sources_aligned() {
+    let m = App::new("prog")
+        .setting(AppSettings::AllArgsOverrideSelf)
+        .arg(Arg::from_usage("-o, --opt [VAL] 'some opt'"))
+        .get_matches_from(vec!["prog", "-o", "a", "-o", "b"]);
+
+    assert_eq!(m.value_of("opt"), Some("b"));
+    assert_eq!(m.value_sources_of("opt").unwrap().len(), 1);
+}
+
+#[test]
+fn global_arg_in_subcommand() {
+    let m = App::new("prog")
+        .arg(
+            Arg::from_usage("-o, --opt [VAL] 'some opt'")
+                .global(true)
+                .default_value("default"),
+        )
+        .subcommand(SubCommand::with_name("sub"))
+        .get_matches_from(vec!["prog", "sub", "-o", "val"]);
+
+    let sub_m = m.subcommand_matches("sub").unwrap();
+    assert_eq!(sub_m.value_of("opt"), Some("val"));
+    assert_eq!(sub_m.value_source("opt"), Some(ValueSource::CommandLine));
+}